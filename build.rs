@@ -0,0 +1,21 @@
+use std::env;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    if env::var_os("CARGO_FEATURE_BUILD_TIME").is_some() {
+        // Honor SOURCE_DATE_EPOCH for reproducible builds.
+        let seconds = env::var("SOURCE_DATE_EPOCH")
+            .ok()
+            .and_then(|value| value.parse::<u64>().ok())
+            .unwrap_or_else(|| {
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|duration| duration.as_secs())
+                    .unwrap_or(0)
+            });
+        println!("cargo:rustc-env=RX8900_BUILD_TIME={}", seconds);
+    }
+}
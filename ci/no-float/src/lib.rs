@@ -0,0 +1,35 @@
+#![no_std]
+//! Monomorphizes the driver API with the simulator bus, so the resulting static library contains
+//! every code path a firmware built with the default features could link.
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use rx8900::{sim::Simulator, Rx8900};
+
+#[panic_handler]
+fn panic(_: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
+
+#[no_mangle]
+pub extern "C" fn rx8900_no_float_check(seed: u8) -> i32 {
+    let datetime = NaiveDateTime::new(
+        NaiveDate::from_ymd_opt(2000 + seed as i32, 1, 1).unwrap_or_default(),
+        NaiveTime::from_hms_opt(0, 0, 0).unwrap_or_default(),
+    );
+    let Some(initialized) = Rx8900::uninitialized(Simulator::new()).init().ok() else {
+        return 0;
+    };
+    let Some(mut rx8900) = initialized.or_set_datetime(datetime).ok() else {
+        return 0;
+    };
+    let _ = rx8900.set_datetime(datetime);
+    let _ = rx8900.sleep_until(datetime);
+    let _ = rx8900.datetime();
+    let _ = rx8900.week_alarm();
+    let _ = rx8900.timer_counter();
+    let _ = rx8900.set_timer_counter(seed as u16);
+    let _ = rx8900.compensation_interval_type();
+    let _ = rx8900.fout_frequency();
+    let _ = rx8900.backup_mode_sampling_time();
+    rx8900.temp_in_millicelsius().unwrap_or_default()
+}
@@ -0,0 +1,85 @@
+#![no_std]
+#![no_main]
+
+//! Low-power example for the nRF52840: the RX8900 alarm wakes the MCU from System OFF.
+//!
+//! Wiring (nRF52840-DK):
+//! * P0.26 - SDA
+//! * P0.27 - SCL
+//! * P0.02 - /INT (open drain, pulled up by the MCU)
+//!
+//! Every boot arms the alarm for the next minute via `sleep_until` and enters System OFF, where
+//! the MCU draws only a few hundred nanoamperes. When the RX8900 asserts /INT, the GPIO sense
+//! logic wakes the chip up through a reset and the program starts again from `main`.
+
+use cortex_m_rt::entry;
+use nrf52840_hal as hal;
+use hal::{
+    gpio::{p0, Level},
+    pac,
+    twim::{self, Twim},
+};
+use panic_halt as _;
+
+// LED related types
+use embedded_hal::digital::v2::OutputPin;
+
+// RTC related types
+use chrono::{Duration, NaiveDateTime, Timelike};
+use rx8900::{Flag, Rx8900};
+
+/// Port 0 pin number connected to the RX8900 /INT output.
+const INT_PIN: usize = 2;
+
+#[entry]
+fn main() -> ! {
+    let p = pac::Peripherals::take().unwrap();
+
+    // The OFF bit of RESETREAS tells a wake from System OFF apart from a cold boot.
+    let woke_from_off = p.POWER.resetreas.read().off().is_detected();
+    p.POWER.resetreas.write(|w| w.off().set_bit());
+
+    let port0 = p0::Parts::new(p.P0);
+    let mut led_pin = port0.p0_13.into_push_pull_output(Level::High);
+    let _int_pin = port0.p0_02.into_pullup_input();
+
+    let scl = port0.p0_27.into_floating_input().degrade();
+    let sda = port0.p0_26.into_floating_input().degrade();
+    let i2c = Twim::new(p.TWIM0, twim::Pins { scl, sda }, twim::Frequency::K100);
+
+    let mut rx8900 = if woke_from_off {
+        // The cold boot initialized the chip and set the time.
+        let mut rx8900 = Rx8900::new_unchecked(i2c);
+        // Acknowledge the alarm so /INT is released before going back to sleep.
+        rx8900.clear_flag(Flag::Alarm).unwrap();
+        led_pin.set_low().unwrap();
+        rx8900
+    } else {
+        // The time is only set if the backup supply was lost, so it is not valid anymore.
+        let datetime = NaiveDateTime::new(
+            chrono::NaiveDate::from_ymd_opt(2001, 2, 3).unwrap(),
+            chrono::NaiveTime::from_hms_opt(4, 5, 6).unwrap(),
+        );
+        let Ok(initialized) = Rx8900::uninitialized(i2c).init() else {
+            panic!("the RX8900 did not answer");
+        };
+        let Ok(rx8900) = initialized.or_set_datetime(datetime) else {
+            panic!("the time could not be set");
+        };
+        rx8900
+    };
+
+    // Wake up at the start of the next minute.
+    let now = rx8900.datetime().unwrap();
+    let next = now.with_second(0).unwrap() + Duration::minutes(1);
+    rx8900.sleep_until(next).unwrap();
+
+    // Let the /INT falling edge wake the chip from System OFF.
+    let p0_regs = unsafe { &*pac::P0::ptr() };
+    p0_regs.pin_cnf[INT_PIN].modify(|_, w| w.sense().low());
+
+    p.POWER.systemoff.write(|w| w.systemoff().enter());
+    loop {
+        cortex_m::asm::wfe();
+    }
+}
@@ -104,15 +104,19 @@ fn main() -> ! {
 
     let mut led_pin = pins.led.into_push_pull_output();
 
-    let mut rx8900 = Rx8900::new(i2c);
-    rx8900.init().unwrap();
+    let Ok(initialized) = Rx8900::uninitialized(i2c).init() else {
+        panic!("the RX8900 did not answer");
+    };
     delay.delay_ms(10);
 
+    // The time is only set if it was lost with the backup supply.
     let datetime = NaiveDateTime::new(
         chrono::NaiveDate::from_ymd_opt(2001, 2, 3).unwrap(),
         chrono::NaiveTime::from_hms_opt(4, 5, 6).unwrap(),
     );
-    rx8900.set_datetime(datetime).unwrap();
+    let Ok(mut rx8900) = initialized.or_set_datetime(datetime) else {
+        panic!("the time could not be set");
+    };
 
     let mut count = 0;
     loop {
@@ -0,0 +1,33 @@
+#![no_main]
+
+//! Feeds arbitrary register images into every decoding path of the driver.
+//!
+//! Run with `cargo +nightly fuzz run decode --target x86_64-unknown-linux-gnu` from the
+//! repository root. Any panic is a bug: garbage read from the chip must never crash the firmware.
+
+use libfuzzer_sys::fuzz_target;
+use rx8900::{sim::Simulator, Rx8900};
+
+fuzz_target!(|data: &[u8]| {
+    // Only whole images of the compatible (16 bytes) or full (32 bytes) register map.
+    if data.len() != 16 && data.len() != 32 {
+        return;
+    }
+    let mut rx8900 = Rx8900::new_unchecked(Simulator::from_registers(data));
+
+    let _ = rx8900.datetime();
+    let _ = rx8900.week();
+
+    let _ = rx8900.min_alarm();
+    let _ = rx8900.hour_alarm();
+    let _ = rx8900.alarm_type();
+    let _ = rx8900.week_alarm();
+    let _ = rx8900.day_alarm();
+
+    let _ = rx8900.timer_counter();
+    let _ = rx8900.source_clock();
+    let _ = rx8900.fout_frequency();
+    let _ = rx8900.update_interrupt_type();
+    let _ = rx8900.compensation_interval_type();
+    let _ = rx8900.backup_mode_sampling_time();
+});
@@ -0,0 +1,84 @@
+#![no_std]
+//! Board bring-up shared by the hardware-in-loop tests.
+
+use defmt_rtt as _;
+use panic_probe as _;
+
+use rp_pico::hal::{
+    self,
+    clocks::init_clocks_and_plls,
+    fugit::RateExtU32,
+    gpio::{bank0::Gpio16, bank0::Gpio17, FunctionI2C, Pin, PullUp},
+    pac,
+    sio::Sio,
+    watchdog::Watchdog,
+    Timer,
+};
+use rx8900::Rx8900;
+
+/// The I2C bus the RX8900 is attached to (GPIO16 = SDA, GPIO17 = SCL).
+pub type Bus = hal::I2C<
+    pac::I2C0,
+    (
+        Pin<Gpio16, FunctionI2C, PullUp>,
+        Pin<Gpio17, FunctionI2C, PullUp>,
+    ),
+>;
+
+/// Everything a test needs: the driver under test and a microsecond time base.
+pub struct Board {
+    pub rx8900: Rx8900<Bus>,
+    pub timer: Timer,
+}
+
+impl Board {
+    /// Brings up the clocks, the I2C bus and the microsecond timer.
+    pub fn take() -> Self {
+        let mut pac = pac::Peripherals::take().unwrap();
+        let mut watchdog = Watchdog::new(pac.WATCHDOG);
+        let clocks = init_clocks_and_plls(
+            rp_pico::XOSC_CRYSTAL_FREQ,
+            pac.XOSC,
+            pac.CLOCKS,
+            pac.PLL_SYS,
+            pac.PLL_USB,
+            &mut pac.RESETS,
+            &mut watchdog,
+        )
+        .ok()
+        .unwrap();
+
+        let sio = Sio::new(pac.SIO);
+        let pins = rp_pico::Pins::new(
+            pac.IO_BANK0,
+            pac.PADS_BANK0,
+            sio.gpio_bank0,
+            &mut pac.RESETS,
+        );
+        let i2c = hal::I2C::i2c0(
+            pac.I2C0,
+            pins.gpio16.reconfigure(),
+            pins.gpio17.reconfigure(),
+            400.kHz(),
+            &mut pac.RESETS,
+            &clocks.system_clock,
+        );
+        let timer = Timer::new(pac.TIMER, &mut pac.RESETS, &clocks);
+
+        Self {
+            rx8900: Rx8900::new_unchecked(i2c),
+            timer,
+        }
+    }
+
+    /// Returns the microseconds elapsed since boot.
+    pub fn now_us(&self) -> u64 {
+        self.timer.get_counter().ticks()
+    }
+
+    /// Busy-waits for the given number of microseconds.
+    pub fn wait_us(&self, us: u64) {
+        let start = self.now_us();
+        while self.now_us() - start < us {}
+    }
+}
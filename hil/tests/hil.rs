@@ -0,0 +1,105 @@
+#![no_std]
+#![no_main]
+
+use rx8900_hil as _; // global logger and panicking behavior
+
+#[defmt_test::tests]
+mod tests {
+    use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+    use defmt::assert;
+    use rx8900::{Flag, SourceClock};
+    use rx8900_hil::Board;
+
+    fn datetime(h: u32, m: u32, s: u32) -> NaiveDateTime {
+        NaiveDateTime::new(
+            NaiveDate::from_ymd_opt(2024, 2, 29).unwrap(),
+            NaiveTime::from_hms_opt(h, m, s).unwrap(),
+        )
+    }
+
+    #[init]
+    fn init() -> Board {
+        let mut board = Board::take();
+        board.rx8900.init().unwrap();
+        board.wait_us(10_000);
+        board
+    }
+
+    #[test]
+    fn datetime_round_trip(board: &mut Board) {
+        let expected = datetime(12, 34, 56);
+        board.rx8900.set_datetime(expected).unwrap();
+        let actual = board.rx8900.datetime().unwrap();
+        assert!(actual >= expected);
+        assert!((actual - expected).num_seconds() <= 1);
+    }
+
+    #[test]
+    fn consecutive_reads_are_consistent(board: &mut Board) {
+        // Cross several second and minute rollovers; the time must never go backwards.
+        board.rx8900.set_datetime(datetime(23, 59, 58)).unwrap();
+        let mut last = board.rx8900.datetime().unwrap();
+        let start = board.now_us();
+        while board.now_us() - start < 3_000_000 {
+            let now = board.rx8900.datetime().unwrap();
+            assert!(now >= last);
+            assert!((now - last).num_seconds() <= 1);
+            last = now;
+        }
+    }
+
+    #[test]
+    fn timer_period_is_accurate(board: &mut Board) {
+        // 64 ticks of the 64 Hz source clock make one second.
+        board.rx8900.reset_timer_enable().unwrap();
+        board.rx8900.set_source_clock(SourceClock::SourceClock64Hz).unwrap();
+        board.rx8900.set_timer_counter(64).unwrap();
+        board.rx8900.clear_flag(Flag::Timer).unwrap();
+        board.rx8900.set_timer_enable().unwrap();
+        let start = board.now_us();
+        while !board.rx8900.tf().unwrap() {
+            assert!(board.now_us() - start < 2_000_000);
+        }
+        let elapsed = board.now_us() - start;
+        board.rx8900.reset_timer_enable().unwrap();
+        defmt::info!("1 s countdown took {} us", elapsed);
+        // One 64 Hz period of start-up uncertainty plus polling latency.
+        assert!(elapsed > 1_000_000 - 16_000 && elapsed < 1_000_000 + 16_000);
+    }
+
+    #[test]
+    fn timer_flag_clears(board: &mut Board) {
+        board.rx8900.reset_timer_enable().unwrap();
+        board.rx8900.set_source_clock(SourceClock::SourceClock4096Hz).unwrap();
+        board.rx8900.set_timer_counter(1).unwrap();
+        board.rx8900.set_timer_enable().unwrap();
+        board.wait_us(10_000);
+        board.rx8900.reset_timer_enable().unwrap();
+        assert!(board.rx8900.tf().unwrap());
+        board.rx8900.clear_flag(Flag::Timer).unwrap();
+        assert!(!board.rx8900.tf().unwrap());
+    }
+
+    #[test]
+    fn alarm_fires(board: &mut Board) {
+        board.rx8900.set_datetime(datetime(6, 59, 58)).unwrap();
+        board.rx8900.sleep_until(datetime(7, 0, 0)).unwrap();
+        assert!(!board.rx8900.af().unwrap());
+        let start = board.now_us();
+        while !board.rx8900.af().unwrap() {
+            assert!(board.now_us() - start < 3_000_000);
+        }
+        assert!(board.rx8900.datetime().unwrap().minute() == 0);
+    }
+
+    #[test]
+    fn alarm_flag_clears(board: &mut Board) {
+        board.rx8900.set_datetime(datetime(7, 59, 59)).unwrap();
+        board.rx8900.sleep_until(datetime(8, 0, 0)).unwrap();
+        board.wait_us(1_500_000);
+        assert!(board.rx8900.af().unwrap());
+        board.rx8900.clear_flag(Flag::Alarm).unwrap();
+        board.rx8900.reset_alarm_interrupt_enable().unwrap();
+        assert!(!board.rx8900.af().unwrap());
+    }
+}
@@ -0,0 +1,298 @@
+//! The register accessors shared by the blocking `Rx8900` and the async `Rx8900Async`.
+//!
+//! The accessors are written once, in `register_access!`, and expanded in both drivers: in the impl over a
+//! `Transport` as plain functions, and in the impl over an `AsyncTransport` as `async fn`s awaiting each transfer.
+//! Add accessors that only move bytes and decode them here rather than to one of the two impls, so the blocking
+//! and async APIs cannot drift apart.
+//!
+//! The expansion site provides `E`, the error of the transport, the `i2c`, `shadow` and `decode_mode` fields, a
+//! `chunk_len` method splitting bursts and a `check_writable` method refusing writes the driver may not make; it
+//! must also import `Datelike` and `Timelike`, used by `set_datetime`.
+
+/// Expands the shared accessors; without arguments as blocking functions, with `async await` as async ones.
+macro_rules! register_access {
+    ($($async:ident $await:ident)?) => {
+        /// Reads a single byte from a specified register.
+        ///
+        /// # Arguments
+        /// * `register` - The register from which to read.
+        ///
+        /// # Returns
+        /// * `Result<u8, E>` - The read byte on success or an error if the read fails.
+        $($async)? fn read_register(&mut self, register: $crate::RegisterTable) -> Result<u8, E> {
+            let mut data = [0];
+            self.i2c.read_registers(register as u8, &mut data)$(.$await)??;
+            self.shadow.store(register as u8, data[0]);
+            Ok(data[0])
+        }
+
+        /// Reads a specific bit from a register and returns it as a boolean value.
+        ///
+        /// # Arguments
+        /// * `register` - The register from which to read.
+        /// * `bit` - The bit position to read (0-7).
+        ///
+        /// # Returns
+        /// * `Result<bool, E>` - True if the bit is set, false otherwise, or an error if the read fails.
+        $($async)? fn read_register_1bit(&mut self, register: $crate::RegisterTable, bit: u8) -> Result<bool, E> {
+            let data = self.read_register(register)$(.$await)??;
+            Ok((data & (1 << bit)) == (1 << bit))
+        }
+
+        /// Reads consecutive registers in a single transaction, or in several if a burst limit is set.
+        ///
+        /// # Arguments
+        /// * `register` - The first register to read.
+        /// * `data` - The buffer receiving the register values.
+        ///
+        /// # Returns
+        /// * `Result<(), E>` - Ok if the read was successful, or an error if the read fails.
+        $($async)? fn read_registers(&mut self, register: $crate::RegisterTable, data: &mut [u8]) -> Result<(), E> {
+            self.read_burst(register as u8, data)$(.$await)?
+        }
+
+        /// Reads consecutive registers starting at a raw address, honoring the burst limit.
+        $($async)? fn read_burst(&mut self, address: u8, data: &mut [u8]) -> Result<(), E> {
+            let chunk_len = self.chunk_len(data.len());
+            for (index, chunk) in data.chunks_mut(chunk_len).enumerate() {
+                self.i2c.read_registers(address + (index * chunk_len) as u8, chunk)$(.$await)??;
+            }
+            for (offset, value) in data.iter().enumerate() {
+                self.shadow.store(address + offset as u8, *value);
+            }
+            Ok(())
+        }
+
+        /// Writes a single byte to a specified register.
+        ///
+        /// # Arguments
+        /// * `register` - The register to which the byte will be written.
+        /// * `data` - The data byte to write.
+        ///
+        /// # Returns
+        /// * `Result<(), Error<E>>` - Ok if the write was successful, `Error::NotOwner` if this driver may not write
+        ///   the chip, or an error if the write fails.
+        $($async)? fn write_register(
+            &mut self,
+            register: $crate::RegisterTable,
+            data: u8,
+        ) -> Result<(), $crate::Error<E>> {
+            self.check_writable()?;
+            self.i2c.write_registers(register as u8, &[data])$(.$await)??;
+            self.shadow.store(register as u8, data);
+            Ok(())
+        }
+
+        /// Writes consecutive registers in a single transaction, or in several if a burst limit is set.
+        ///
+        /// # Arguments
+        /// * `register` - The first register to write.
+        /// * `data` - The register values, at most 16 bytes.
+        ///
+        /// # Returns
+        /// * `Result<(), Error<E>>` - Ok if the write was successful, `Error::NotOwner` if this driver may not write
+        ///   the chip, or an error if the write fails.
+        $($async)? fn write_registers(
+            &mut self,
+            register: $crate::RegisterTable,
+            data: &[u8],
+        ) -> Result<(), $crate::Error<E>> {
+            self.write_burst(register as u8, data)$(.$await)?
+        }
+
+        /// Writes consecutive registers starting at a raw address, honoring the burst limit and the 16 bytes a
+        /// transport accepts per write.
+        $($async)? fn write_burst(&mut self, address: u8, data: &[u8]) -> Result<(), $crate::Error<E>> {
+            self.check_writable()?;
+            let chunk_len = self.chunk_len(data.len()).min($crate::MAX_WRITE_LEN);
+            for (index, chunk) in data.chunks(chunk_len).enumerate() {
+                self.i2c.write_registers(address + (index * chunk_len) as u8, chunk)$(.$await)??;
+            }
+            for (offset, value) in data.iter().enumerate() {
+                self.shadow.store(address + offset as u8, *value);
+            }
+            Ok(())
+        }
+
+        /// Clears flags in the flag register with a single write and no read.
+        ///
+        /// Flags are cleared by writing 0 and left unchanged by writing 1, so flags raised by the device between a
+        /// read and this write are never lost.
+        ///
+        /// # Arguments
+        /// * `flags` - The flag bits to clear.
+        ///
+        /// # Returns
+        /// * `Result<(), Error<E>>` - Ok if the flags were successfully cleared, `Error::NotOwner` if this driver may
+        ///   not write the chip, or an error if the write fails.
+        $($async)? fn clear_flags(&mut self, flags: u8) -> Result<(), $crate::Error<E>> {
+            let data = $crate::FLAG_BITS & !flags;
+            self.write_register($crate::RegisterTable::CompatibleFlagRegister, data)$(.$await)?
+        }
+
+        /// Clears a status flag, leaving the other flags unchanged even if the device raises them meanwhile.
+        ///
+        /// # Arguments
+        /// * `flag` - The flag to clear.
+        ///
+        /// # Returns
+        /// * `Result<(), Error<E>>` - Ok if the flag was successfully cleared, `Error::NotOwner` if this driver may
+        ///   not write the chip, or an error if the write fails.
+        pub $($async)? fn clear_flag(&mut self, flag: $crate::Flag) -> Result<(), $crate::Error<E>> {
+            self.clear_flags(flag as u8)$(.$await)?
+        }
+
+        /// Reads the update flag which indicates if an update occurred.
+        ///
+        /// # Returns
+        /// * `Result<bool, E>` - True if an update has occurred, otherwise false, or an error if the read fails.
+        pub $($async)? fn uf(&mut self) -> Result<bool, E> {
+            self.read_register_1bit($crate::RegisterTable::CompatibleFlagRegister, 5)$(.$await)?
+        }
+
+        /// Reads the timer flag which indicates if a timer event has occurred.
+        ///
+        /// # Returns
+        /// * `Result<bool, E>` - True if a timer event has occurred, otherwise false, or an error if the read fails.
+        #[cfg(feature = "timer")]
+        pub $($async)? fn tf(&mut self) -> Result<bool, E> {
+            self.read_register_1bit($crate::RegisterTable::CompatibleFlagRegister, 4)$(.$await)?
+        }
+
+        /// Reads the alarm flag which indicates if an alarm has been triggered.
+        ///
+        /// # Returns
+        /// * `Result<bool, E>` - True if an alarm has been triggered, otherwise false, or an error if the read
+        ///   fails.
+        #[cfg(feature = "alarm")]
+        pub $($async)? fn af(&mut self) -> Result<bool, E> {
+            self.read_register_1bit($crate::RegisterTable::CompatibleFlagRegister, 3)$(.$await)?
+        }
+
+        /// Reads the voltage low flag which indicates if the battery voltage has dropped below a critical
+        /// threshold.
+        ///
+        /// # Returns
+        /// * `Result<bool, E>` - True if the voltage is low, otherwise false, or an error if the read fails.
+        pub $($async)? fn vlf(&mut self) -> Result<bool, E> {
+            self.read_register_1bit($crate::RegisterTable::CompatibleFlagRegister, 1)$(.$await)?
+        }
+
+        /// Reads the voltage detect flag which indicates if a voltage drop has been detected.
+        ///
+        /// # Returns
+        /// * `Result<bool, E>` - True if a voltage drop has been detected, otherwise false, or an error if the read
+        ///   fails.
+        pub $($async)? fn vdet(&mut self) -> Result<bool, E> {
+            self.read_register_1bit($crate::RegisterTable::CompatibleFlagRegister, 0)$(.$await)?
+        }
+
+        /// Reads the current temperature value from the RTC's temperature sensor.
+        ///
+        /// # Returns
+        /// * `Result<u8, E>` - The raw temperature value, or an error if the read fails.
+        #[cfg(feature = "temperature")]
+        pub $($async)? fn temp(&mut self) -> Result<u8, E> {
+            self.read_register($crate::RegisterTable::ExtendedTEMP)$(.$await)?
+        }
+
+        /// Converts the raw temperature value to thousandths of a degree Celsius using integer math only.
+        ///
+        /// # Returns
+        /// * `Result<i32, E>` - The temperature in millidegrees Celsius, or an error if the read fails.
+        #[cfg(feature = "temperature")]
+        pub $($async)? fn temp_in_millicelsius(&mut self) -> Result<i32, E> {
+            let data = self.temp()$(.$await)?? as i32;
+            Ok((data * 2_000_000 - 187_190_000) / 3_218)
+        }
+
+        /// Reads the SEC to YEAR registers in one burst, without decoding them.
+        ///
+        /// Log these bytes when a field report shows a corrupted time: they are exactly what the chip returned.
+        ///
+        /// # Returns
+        /// * `Result<[u8; 7], E>` - The SEC, MIN, HOUR, WEEK, DAY, MONTH and YEAR registers, or an error if the read
+        ///   fails.
+        pub $($async)? fn read_time_raw(&mut self) -> Result<[u8; 7], E> {
+            let mut raw = [0; 7];
+            self.read_registers($crate::RegisterTable::CompatibleSEC, &mut raw)$(.$await)??;
+            Ok(raw)
+        }
+
+        /// Reads the current date and time from the RTC.
+        ///
+        /// The SEC to YEAR registers are read in one transaction, during which the chip holds its clock update, so
+        /// the fields cannot tear across a rollover.
+        ///
+        /// # Returns
+        /// * `Result<NaiveDateTime, Error<E>>` - The current date and time, `Error::InvalidTime` with the raw
+        ///   registers if they hold no valid date and time in the decode mode, or an error if the read fails.
+        pub $($async)? fn datetime(&mut self) -> Result<chrono::NaiveDateTime, $crate::Error<E>> {
+            let raw = self.read_time_raw()$(.$await)??;
+            self.decode_mode.decode_time_in(&raw, 2000).ok_or($crate::Error::InvalidTime(raw))
+        }
+
+        /// Reads only the hours, minutes and seconds, in a 3-register burst, for interrupt handlers that need a
+        /// cheap timestamp.
+        ///
+        /// The transaction clocks 57 bits, 570 µs at 100 kHz and 143 µs at 400 kHz, against 93 bits for
+        /// `datetime`. It is a single transaction unless `set_burst_limit` is below 3, so the chip latches the three
+        /// registers together. In the worst case, a burst limit of 1, it takes three transactions of 39 bits, 1.17 ms
+        /// at 100 kHz, plus three bus free times on a `SlowBus`, and a rollover may tear the fields between them.
+        ///
+        /// # Returns
+        /// * `Result<NaiveTime, Error<E>>` - The time of day, `Error::InvalidTime` with the SEC, MIN and HOUR
+        ///   registers as read followed by zeros if they hold no valid time in the decode mode, or an error if the
+        ///   read fails.
+        pub $($async)? fn datetime_fast(&mut self) -> Result<chrono::NaiveTime, $crate::Error<E>> {
+            let mut raw = [0; 7];
+            self.read_registers($crate::RegisterTable::CompatibleSEC, &mut raw[..3])$(.$await)??;
+            self.decode_mode.decode_time_of_day(&[raw[0], raw[1], raw[2]]).ok_or($crate::Error::InvalidTime(raw))
+        }
+
+        /// Sets a full date and time in the RTC.
+        ///
+        /// A `DateTime` in any time zone is converted to UTC first; a `NaiveDateTime` is written as is. The SEC to
+        /// YEAR registers are written in one burst, with the fractional seconds truncated.
+        ///
+        /// # Arguments
+        /// * `data` - The `NaiveDateTime` or `DateTime<Tz>` containing the date and time to be set.
+        ///
+        /// # Returns
+        /// * `Result<(), Error<E>>` - Ok if the date and time were successfully set, `Error::InvalidInput` if the
+        ///   year is outside 2000-2099, or an error if the write fails.
+        pub $($async)? fn set_datetime(&mut self, data: impl $crate::IntoNaiveUtc) -> Result<(), $crate::Error<E>> {
+            self.set_datetime_in(data.into_naive_utc(), 2000)$(.$await)?
+        }
+
+        /// Writes a date and time of the century starting at `base` to the SEC to YEAR registers in one burst.
+        ///
+        /// # Arguments
+        /// * `data` - The date and time to be set.
+        /// * `base` - The first year of the century the YEAR register counts from.
+        ///
+        /// # Returns
+        /// * `Result<(), Error<E>>` - Ok if the date and time were successfully set, `Error::InvalidInput` if the
+        ///   year is outside `base` to `base + 99`, or an error if the write fails.
+        pub(crate) $($async)? fn set_datetime_in(
+            &mut self,
+            data: chrono::NaiveDateTime,
+            base: i32,
+        ) -> Result<(), $crate::Error<E>> {
+            if !(base..base + 100).contains(&data.year()) {
+                return Err($crate::Error::InvalidInput);
+            }
+            // Every field but the year is in range for any date and time; the year was checked above.
+            let data = [
+                $crate::bcd::to_bcd(data.second() as u8),
+                $crate::bcd::to_bcd(data.minute() as u8),
+                $crate::bcd::to_bcd(data.hour() as u8),
+                $crate::weekday::weekday_to_bit(data.weekday()),
+                $crate::bcd::to_bcd(data.day() as u8),
+                $crate::bcd::to_bcd(data.month() as u8),
+                $crate::bcd::to_bcd((data.year() - base) as u8),
+            ];
+            self.write_registers($crate::RegisterTable::CompatibleSEC, &data)$(.$await)?
+        }
+    };
+}
@@ -0,0 +1,248 @@
+//! Alarm configuration and a guard that disarms the alarm when it goes out of scope.
+
+use core::marker::PhantomData;
+use chrono::{Datelike, Duration, NaiveDateTime, Timelike, Weekday};
+use heapless::Vec;
+
+use crate::{bcd, weekday, AlarmType, Error, RegisterTable, Rx8900, Transport};
+
+/// The day part of an alarm.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum AlarmDay {
+    /// Every day.
+    #[default]
+    Any,
+    /// A day of the month (1-31), programmed as a day alarm.
+    Day(u8),
+    /// One or more days of the week, programmed as a week alarm.
+    Weekdays(Vec<Weekday, 7>),
+}
+
+/// The time at which the alarm fires; fields set to None match any value.
+///
+/// The default fires every minute; narrow it down with the setters, e.g.
+/// `AlarmConfig::default().hour(7).minute(30)` for every day at 07:30.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub struct AlarmConfig {
+    /// Minute (0-59).
+    pub minute: Option<u8>,
+    /// Hour (0-23).
+    pub hour: Option<u8>,
+    /// Day of the month or days of the week.
+    pub day: AlarmDay,
+}
+
+impl AlarmConfig {
+    /// Returns an alarm firing once at the given date and time, ignoring the seconds.
+    ///
+    /// # Arguments
+    /// * `data` - The date and time; only the day of the month, hour and minute are compared by the chip.
+    pub fn at(data: NaiveDateTime) -> Self {
+        Self { minute: Some(data.minute() as u8), hour: Some(data.hour() as u8), day: AlarmDay::Day(data.day() as u8) }
+    }
+
+    /// Sets the minute (0-59) at which the alarm fires.
+    pub fn minute(mut self, minute: u8) -> Self {
+        self.minute = Some(minute);
+        self
+    }
+
+    /// Sets the hour (0-23) at which the alarm fires.
+    pub fn hour(mut self, hour: u8) -> Self {
+        self.hour = Some(hour);
+        self
+    }
+
+    /// Sets the day of the month or the days of the week on which the alarm fires.
+    pub fn day(mut self, day: AlarmDay) -> Self {
+        self.day = day;
+        self
+    }
+
+    /// Returns the shortest time between two firings of the alarm.
+    ///
+    /// # Returns
+    /// * `Duration` - One minute with a wildcard minute, one hour with a wildcard hour, otherwise the shortest gap
+    ///   between two matching days; 28 days for a day of the month.
+    pub fn min_period(&self) -> Duration {
+        if self.minute.is_none() {
+            return Duration::minutes(1);
+        }
+        if self.hour.is_none() {
+            return Duration::hours(1);
+        }
+        match &self.day {
+            AlarmDay::Any => Duration::days(1),
+            AlarmDay::Day(_) => Duration::days(28),
+            AlarmDay::Weekdays(days) => {
+                let bits = days.iter().fold(0, |bits, day| bits | weekday::weekday_to_bit(*day));
+                // The shortest gap between two selected days, going round the week.
+                let gap = (1..7)
+                    .find(|gap| (0..7).any(|day| bits & 1 << day != 0 && bits & 1 << ((day + gap) % 7) != 0))
+                    .unwrap_or(7);
+                Duration::days(gap)
+            }
+        }
+    }
+
+    /// Returns whether every field is within the range supported by the alarm registers.
+    pub(crate) fn is_valid(&self) -> bool {
+        self.minute.map_or(true, |minute| minute < 60)
+            && self.hour.map_or(true, |hour| hour < 24)
+            && match &self.day {
+                AlarmDay::Any => true,
+                AlarmDay::Day(day) => (1..=31).contains(day),
+                AlarmDay::Weekdays(days) => !days.is_empty(),
+            }
+    }
+}
+
+/// An armed alarm that is disarmed when dropped.
+///
+/// Created by `Rx8900::arm_alarm`. Dropping the guard disables the alarm interrupt and clears the
+/// alarm flag, so an alarm belonging to an operation that was cancelled cannot wake the product
+/// later. Bus errors during the drop are ignored; call `disarm` to observe them, or `keep` to leave
+/// the alarm armed on purpose.
+pub struct ArmedAlarm<'a, I2C, E>
+where
+    I2C: Transport<Error = E>,
+{
+    rx8900: &'a mut Rx8900<I2C>,
+    _error: PhantomData<E>,
+}
+
+impl<'a, I2C, E> ArmedAlarm<'a, I2C, E>
+where
+    I2C: Transport<Error = E>,
+{
+    pub(crate) fn new(rx8900: &'a mut Rx8900<I2C>) -> Self {
+        Self { rx8900, _error: PhantomData }
+    }
+
+    /// Returns whether the alarm has fired.
+    ///
+    /// # Returns
+    /// * `Result<bool, E>` - True if the alarm flag is set, or an error if the read fails.
+    pub fn fired(&mut self) -> Result<bool, E> {
+        self.rx8900.af()
+    }
+
+    /// Gives access to the RTC while the alarm stays armed.
+    pub fn rtc(&mut self) -> &mut Rx8900<I2C> {
+        self.rx8900
+    }
+
+    /// Disables the alarm interrupt and clears the alarm flag.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the alarm was disarmed, or an error if the operation fails.
+    pub fn disarm(mut self) -> Result<(), Error<E>> {
+        let result = self.disarm_registers();
+        core::mem::forget(self);
+        result
+    }
+
+    /// Consumes the guard without disarming the alarm.
+    pub fn keep(self) {
+        core::mem::forget(self);
+    }
+
+    fn disarm_registers(&mut self) -> Result<(), Error<E>> {
+        self.rx8900.set_aie(false)?;
+        // AF
+        self.rx8900.clear_flags(0b00001000)
+    }
+}
+
+impl<'a, I2C, E> Drop for ArmedAlarm<'a, I2C, E>
+where
+    I2C: Transport<Error = E>,
+{
+    fn drop(&mut self) {
+        let _ = self.disarm_registers();
+    }
+}
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Writes the alarm registers for a configuration, with the alarm interrupt disabled and the alarm flag cleared.
+    pub(crate) fn write_alarm(&mut self, config: &AlarmConfig) -> Result<(), Error<E>> {
+        // The alarm registers must not be updated while AIE is set.
+        self.set_aie(false)?;
+        // AF
+        self.clear_flags(0b00001000)?;
+        // AE (bit 7) set: the register is ignored by the comparison.
+        let minute = config.minute.map_or(0b10000000, bcd::to_bcd);
+        let hour = config.hour.map_or(0b10000000, bcd::to_bcd);
+        let day = match &config.day {
+            AlarmDay::Any => 0b10000000,
+            AlarmDay::Day(day) => bcd::to_bcd(*day),
+            AlarmDay::Weekdays(days) => days.iter().fold(0, |value, day| value | weekday::weekday_to_bit(*day)),
+        };
+        let alarm_type = match config.day {
+            AlarmDay::Weekdays(_) => AlarmType::WeekAlarm,
+            _ => AlarmType::DayAlarm,
+        };
+        self.set_alarm_type(alarm_type)?;
+        self.write_register(RegisterTable::CompatibleMinAlarm, minute)?;
+        self.write_register(RegisterTable::CompatibleHourAlarm, hour)?;
+        self.write_register(RegisterTable::CompatibleWeekDayAlarm, day)
+    }
+
+    /// Returns the next time the armed alarm fires, reconstructed from the registers of the chip only.
+    ///
+    /// After an unexpected reset, this tells the firmware which wake-up the previous run had armed, whether with
+    /// `sleep_until`, `arm_alarm` or any of the schedulers. The wake-up timer is not covered, since its remaining
+    /// count cannot be read back.
+    ///
+    /// # Returns
+    /// * `Result<Option<NaiveDateTime>, Error<E>>` - The first minute after now matching the alarm registers, None if
+    ///   the alarm interrupt is disabled or the registers never match within 62 days, `Error::InvalidTime` if the time
+    ///   registers hold no valid date and time, or an error if a read fails.
+    pub fn scheduled_wake(&mut self) -> Result<Option<NaiveDateTime>, Error<E>> {
+        if !self.aie()? {
+            return Ok(None);
+        }
+        let mut data = [0; 3];
+        self.read_registers(RegisterTable::CompatibleMinAlarm, &mut data)?;
+        let day_alarm = self.wada()?;
+        let now = self.datetime()?;
+        Ok(next_alarm_match(now, data, day_alarm))
+    }
+}
+
+/// Finds the first minute after `now` matched by the MIN, HOUR and WEEK/DAY Alarm registers.
+fn next_alarm_match(now: NaiveDateTime, [minute, hour, day]: [u8; 3], day_alarm: bool) -> Option<NaiveDateTime> {
+    // AE (bit 7) set: the register is ignored by the comparison.
+    let field = |data: u8, mask: u8, max: u8| match data & 0b10000000 {
+        0 => bcd::checked_from_bcd(data & mask).ok().filter(|&value| value <= max).map(Some),
+        _ => Some(None),
+    };
+    let minute = field(minute, 0b01111111, 59)?;
+    let hour = field(hour, 0b00111111, 23)?;
+    let start = now.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+    for date in start.date().iter_days().take(62) {
+        let day_matches = if day & 0b10000000 != 0 {
+            true
+        } else if day_alarm {
+            bcd::checked_from_bcd(day & 0b00111111).is_ok_and(|day| day as u32 == date.day())
+        } else {
+            day & weekday::weekday_to_bit(date.weekday()) != 0
+        };
+        if !day_matches {
+            continue;
+        }
+        let times = (0..24u32).flat_map(|hour| (0..60u32).map(move |minute| (hour, minute)));
+        let next = times
+            .filter(|&(h, m)| hour.map_or(true, |hour| hour as u32 == h) && minute.map_or(true, |minute| minute as u32 == m))
+            .filter_map(|(h, m)| date.and_hms_opt(h, m, 0))
+            .find(|&time| time >= start);
+        if next.is_some() {
+            return next;
+        }
+    }
+    None
+}
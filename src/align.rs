@@ -0,0 +1,48 @@
+//! Wake-ups aligned to wall-clock boundaries, so that a fleet of devices samples at the same times.
+
+use chrono::{Duration, NaiveDateTime, Timelike};
+
+use crate::{Error, IntoRtcDuration, Rx8900, Transport};
+
+/// Returns the first multiple of `period` since midnight that is strictly after `now`.
+///
+/// `period` must be a whole number of seconds dividing a day.
+fn next_boundary(now: NaiveDateTime, period: i64) -> Option<NaiveDateTime> {
+    let since_midnight = now.num_seconds_from_midnight() as i64;
+    let midnight = now.date().and_hms_opt(0, 0, 0)?;
+    midnight.checked_add_signed(Duration::try_seconds((since_midnight / period + 1) * period)?)
+}
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Arms the alarm or the wake-up timer to fire at the next wall-clock boundary of `period`.
+    ///
+    /// Boundaries are the multiples of `period` since midnight (UTC), e.g. every full minute, every 5 minutes at
+    /// :00, :05, :10 and so on, or every full hour. Periods of whole minutes arm the alarm; shorter periods start
+    /// the wake-up timer with the delay to the boundary, counted on the second source which is synchronized with
+    /// the clock update. In both cases, call `align_to` again after each wake-up to arm the next boundary.
+    ///
+    /// # Arguments
+    /// * `period` - The spacing of the boundaries, a whole number of seconds dividing a day.
+    ///
+    /// # Returns
+    /// * `Result<NaiveDateTime, Error<E>>` - The boundary at which the alarm or timer fires, `Error::InvalidInput` if
+    ///   the period is not a whole number of seconds dividing a day, or an error if the operation fails.
+    pub fn align_to(&mut self, period: impl IntoRtcDuration) -> Result<NaiveDateTime, Error<E>> {
+        let period = period.into_rtc_duration().ok_or(Error::InvalidInput)?;
+        let seconds = period.num_seconds();
+        if seconds <= 0 || period.subsec_nanos() != 0 || 86_400 % seconds != 0 {
+            return Err(Error::InvalidInput);
+        }
+        let now = self.datetime()?;
+        let next = next_boundary(now, seconds).ok_or(Error::InvalidInput)?;
+        if seconds % 60 == 0 {
+            self.sleep_until(next)?;
+        } else {
+            self.start_countdown(next - now)?;
+        }
+        Ok(next)
+    }
+}
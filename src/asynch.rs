@@ -0,0 +1,194 @@
+//! Async waits on the INT pin, for executors that keep running while the MCU clock is stopped, and `Rx8900Async`
+//! for async I2C buses.
+//!
+//! The waits of `Rx8900` keep the I2C transfers blocking; only the wait for the RTC is asynchronous. The INT pin is
+//! shared by every interrupt source, so sources that are not being waited for must not keep it asserted, or the
+//! waits below poll the flags continuously.
+//!
+//! `Rx8900Async` awaits the transfers too. Its accessors are not a copy of the blocking ones: both are expanded
+//! from `register_access!`, so they cannot drift apart.
+
+use chrono::{Datelike, Timelike};
+use core::convert::Infallible;
+use embedded_hal_async::digital::Wait;
+
+use crate::{AsyncTransport, DecodeMode, Error, Event, IntoRtcDuration, RegisterTable, Rx8900, Shadow, Transport};
+
+/// The RX8900 driver over an async I2C bus, for executors that must not block on bus transfers.
+///
+/// It offers the accessors of `Rx8900` that only move bytes and decode them: the flags, the temperature and the
+/// date and time, from the same implementation. Configure the chip with the blocking driver, e.g. at boot before
+/// the executor starts.
+pub struct Rx8900Async<I2C> {
+    i2c: I2C,
+    shadow: Shadow,
+    decode_mode: DecodeMode,
+    burst_limit: Option<u8>,
+}
+
+impl<I2C> Rx8900Async<I2C> {
+    /// Creates a new instance of the async RX8900 driver.
+    ///
+    /// # Arguments
+    /// * `i2c` - An async I2C bus, or any other `AsyncTransport`, connected to the RX8900.
+    pub fn new(i2c: I2C) -> Self {
+        Self { i2c, shadow: Shadow::default(), decode_mode: DecodeMode::default(), burst_limit: None }
+    }
+
+    /// Destroys the driver and returns the I2C peripheral.
+    ///
+    /// # Returns
+    /// * `I2C` - The I2C peripheral passed to `new`.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    /// Sets how `datetime` and `datetime_fast` handle registers holding an invalid value; see
+    /// `Rx8900::set_decode_mode`.
+    ///
+    /// # Arguments
+    /// * `mode` - The decode mode.
+    pub fn set_decode_mode(&mut self, mode: DecodeMode) {
+        self.decode_mode = mode;
+    }
+
+    /// Caps the number of registers read or written in one I2C transaction; see `Rx8900::set_burst_limit`.
+    ///
+    /// # Arguments
+    /// * `limit` - The maximum number of registers per transaction, or None for no limit.
+    pub fn set_burst_limit(&mut self, limit: Option<u8>) {
+        self.burst_limit = limit;
+    }
+
+    /// Returns the number of registers to transfer per transaction for a burst of `len` registers.
+    fn chunk_len(&self, len: usize) -> usize {
+        self.burst_limit.map_or(len, usize::from).max(1)
+    }
+}
+
+impl<I2C, E> Rx8900Async<I2C>
+where
+    I2C: AsyncTransport<Error = E>,
+{
+    register_access!(async await);
+
+    /// Lets every write through: the async driver takes no part in the ownership of the chip.
+    fn check_writable(&self) -> Result<(), Error<E>> {
+        Ok(())
+    }
+}
+
+/// Stops the countdown timer when dropped, so a cancelled `after` future does not leave it running.
+struct Countdown<'a, I2C, E>
+where
+    I2C: Transport<Error = E>,
+{
+    rx8900: &'a mut Rx8900<I2C>,
+}
+
+impl<'a, I2C, E> Countdown<'a, I2C, E>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Stops the timer, reporting bus errors.
+    fn finish(mut self) -> Result<(), Error<E>> {
+        let result = self.stop();
+        core::mem::forget(self);
+        result
+    }
+
+    fn stop(&mut self) -> Result<(), Error<E>> {
+        self.rx8900.set_te(false)?;
+        self.rx8900.set_tie(false)?;
+        // TF
+        self.rx8900.clear_flags(0b00010000)
+    }
+}
+
+impl<'a, I2C, E> Drop for Countdown<'a, I2C, E>
+where
+    I2C: Transport<Error = E>,
+{
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Waits for `duration` using the wake-up timer instead of an MCU timer.
+    ///
+    /// The countdown timer is started with `start_countdown` and its interrupt enabled, then the future waits for
+    /// the INT pin to go low and the timer flag to be set. The timer is stopped, its interrupt disabled and the
+    /// flag cleared once the wait completes, and also when the future is dropped before completion.
+    ///
+    /// # Arguments
+    /// * `duration` - The time to wait; see `start_countdown` for the supported values.
+    /// * `int` - The MCU input connected to the INT pin of the RX8900.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok once the duration has elapsed, `Error::InvalidInput` if the duration cannot be
+    ///   counted down by the timer, or an error if a bus operation fails.
+    pub async fn after<P>(&mut self, duration: impl IntoRtcDuration, int: &mut P) -> Result<(), Error<E>>
+    where
+        P: Wait<Error = Infallible>,
+    {
+        self.start_countdown(duration)?;
+        let countdown = Countdown { rx8900: self };
+        countdown.rx8900.set_tie(true)?;
+        while !countdown.rx8900.tf()? {
+            let _ = int.wait_for_low().await;
+        }
+        countdown.finish()?;
+        Ok(())
+    }
+
+    /// Waits for whichever enabled interrupt source fires first.
+    ///
+    /// Only sources whose interrupt is enabled (AIE, TIE, UIE) are considered. The flag of the returned source is
+    /// cleared; when several sources are pending, the others are returned by the following calls, in the order
+    /// alarm, timer, update. The future holds no state across an await point besides the pin, so it can be
+    /// dropped at any time, which makes it suitable for `select!` with other async sources.
+    ///
+    /// # Arguments
+    /// * `int` - The MCU input connected to the INT pin of the RX8900.
+    ///
+    /// # Returns
+    /// * `Result<Event, Error<E>>` - The source that fired, or an error if a bus operation fails.
+    pub async fn next_event<P>(&mut self, int: &mut P) -> Result<Event, Error<E>>
+    where
+        P: Wait<Error = Infallible>,
+    {
+        loop {
+            if let Some(event) = self.take_event()? {
+                return Ok(event);
+            }
+            let _ = int.wait_for_low().await;
+        }
+    }
+
+    /// Returns and clears the first pending source whose interrupt is enabled.
+    fn take_event(&mut self) -> Result<Option<Event>, Error<E>> {
+        let enabled = self.read_register_cached(RegisterTable::CompatibleControlRegister)?;
+        let flags = self.read_register(RegisterTable::CompatibleFlagRegister)?;
+        // AF/AIE, TF/TIE and UF/UIE share the same bit positions.
+        let pending = flags & enabled & 0b00111000;
+        let event = [
+            #[cfg(feature = "alarm")]
+            (0b00001000, Event::Alarm),
+            (0b00010000, Event::Timer),
+            (0b00100000, Event::Update),
+        ]
+        .into_iter()
+        .find(|(flag, _)| pending & flag != 0);
+        match event {
+            Some((flag, event)) => {
+                self.clear_flags(flag)?;
+                Ok(Some(event))
+            }
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,68 @@
+//! Backup function register settings for the usual backup supply topologies.
+
+use crate::{Error, InitConfig, RegisterTable, Rx8900, Transport};
+
+/// The backup supply connected to VBAT, selecting the VDETOFF, SWOFF and BKSMP settings recommended for it.
+///
+/// | Profile | VDETOFF | SWOFF | BKSMP |
+/// |---|---|---|---|
+/// | `CoinCell` | 0, detector on | 1, no charging | 128 ms |
+/// | `Supercap` | 0, detector on | 0, charged from VDD | 128 ms |
+/// | `None` | 1, detector off | 1, no charging | 128 ms |
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BackupPowerProfile {
+    /// A primary lithium coin cell, which must never be charged.
+    CoinCell,
+    /// A supercapacitor or rechargeable cell, kept charged from VDD through the internal switch.
+    Supercap,
+    /// No backup supply: the voltage detector is turned off to save its current.
+    None,
+}
+
+impl BackupPowerProfile {
+    /// Returns the VDETOFF and SWOFF bits of the profile.
+    fn bits(self) -> (bool, bool) {
+        match self {
+            BackupPowerProfile::CoinCell => (false, true),
+            BackupPowerProfile::Supercap => (false, false),
+            BackupPowerProfile::None => (true, true),
+        }
+    }
+
+    /// Returns the value of the backup function register for the profile.
+    fn backup_function_register(self) -> u8 {
+        let (voltage_detector_off, switch_off) = self.bits();
+        // VDETOFF, SWOFF and BKSMP=10, the 128 ms sampling time of power-on
+        (voltage_detector_off as u8) << 3 | (switch_off as u8) << 2 | 0b10
+    }
+}
+
+impl InitConfig {
+    /// Sets VDETOFF, SWOFF and BKSMP for a backup supply topology.
+    pub fn backup(self, profile: BackupPowerProfile) -> Self {
+        let (voltage_detector_off, switch_off) = profile.bits();
+        self.voltage_detector_off(voltage_detector_off)
+            .switch_off(switch_off)
+            .backup_mode_sampling_time(0b10)
+    }
+}
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Programs the backup function register for a backup supply topology, as listed on `BackupPowerProfile`.
+    ///
+    /// With SWOFF=0 the chip charges VBAT from VDD, which a coin cell must not see: boards with one should apply
+    /// their profile, or `init`, before anything else.
+    ///
+    /// # Arguments
+    /// * `profile` - The backup supply connected to VBAT.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the register was successfully updated, or an error if the operation fails.
+    pub fn configure_backup(&mut self, profile: BackupPowerProfile) -> Result<(), Error<E>> {
+        // VDETOFF, SWOFF and BKSMP
+        self.update_register(RegisterTable::ExtendedBackupFunction, 0b00001111, profile.backup_function_register())
+    }
+}
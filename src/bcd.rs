@@ -0,0 +1,77 @@
+//! Binary-coded decimal conversions, as used by the time, calendar and alarm registers.
+//!
+//! The conversions are `const fn`, so they can also build register values at compile time.
+
+use core::fmt;
+
+/// An error returned by the checked BCD conversions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BcdError {
+    /// The decimal value is above 99 and does not fit in two BCD digits.
+    OutOfRange(u8),
+    /// A nibble of the BCD byte is above 9.
+    InvalidDigit(u8),
+}
+
+impl fmt::Display for BcdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BcdError::OutOfRange(value) => write!(f, "{} does not fit in two BCD digits", value),
+            BcdError::InvalidDigit(value) => write!(f, "0x{:02X} is not a valid BCD byte", value),
+        }
+    }
+}
+
+/// Converts a BCD-encoded byte to a regular decimal byte.
+///
+/// Nibbles above 9 are not checked; use `checked_from_bcd` for bytes read from an untrusted source.
+///
+/// # Arguments
+/// * `data` - The BCD-encoded byte.
+///
+/// # Returns
+/// * `u8` - The decimal value.
+pub const fn from_bcd(data: u8) -> u8 {
+    (data >> 4) * 10 + (data & 0x0F)
+}
+
+/// Converts a regular decimal byte to a BCD-encoded byte.
+///
+/// Values above 99 are not checked; use `checked_to_bcd` for values from an untrusted source.
+///
+/// # Arguments
+/// * `data` - The decimal value (0-99).
+///
+/// # Returns
+/// * `u8` - The BCD-encoded byte.
+pub const fn to_bcd(data: u8) -> u8 {
+    ((data / 10) << 4) | (data % 10)
+}
+
+/// Converts a BCD-encoded byte to a regular decimal byte, checking both nibbles.
+///
+/// # Arguments
+/// * `data` - The BCD-encoded byte.
+///
+/// # Returns
+/// * `Result<u8, BcdError>` - The decimal value, or `BcdError::InvalidDigit` if a nibble is above 9.
+pub const fn checked_from_bcd(data: u8) -> Result<u8, BcdError> {
+    if data >> 4 > 9 || data & 0x0F > 9 {
+        return Err(BcdError::InvalidDigit(data));
+    }
+    Ok(from_bcd(data))
+}
+
+/// Converts a regular decimal byte to a BCD-encoded byte, checking the range.
+///
+/// # Arguments
+/// * `data` - The decimal value.
+///
+/// # Returns
+/// * `Result<u8, BcdError>` - The BCD-encoded byte, or `BcdError::OutOfRange` if the value is above 99.
+pub const fn checked_to_bcd(data: u8) -> Result<u8, BcdError> {
+    if data > 99 {
+        return Err(BcdError::OutOfRange(data));
+    }
+    Ok(to_bcd(data))
+}
@@ -0,0 +1,71 @@
+//! Burst reads and writes through caller-provided buffers, for HALs whose DMA-backed I2C needs the transfer memory
+//! to come from a DMA-safe pool rather than the driver's stack.
+//!
+//! Only reads are zero-copy: the register address has to precede the data in the same write, so `Transport` copies
+//! every write into a buffer on its stack, and a caller buffer never reaches the bus on writes.
+
+use crate::{Error, RegisterTable, Rx8900, Transport};
+
+/// The number of register addresses, 0x00-0x1F.
+const REGISTER_COUNT: usize = 0x20;
+
+/// Returns whether a burst of `len` registers starting at `address` stays within the register file.
+fn in_register_file(address: u8, len: usize) -> bool {
+    usize::from(address) < REGISTER_COUNT && usize::from(address) + len <= REGISTER_COUNT
+}
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Reads consecutive registers directly into a caller-provided buffer.
+    ///
+    /// The buffer is handed to the bus as is, so it can live in DMA-capable memory. Bursts are split according to
+    /// `set_burst_limit`.
+    ///
+    /// # Arguments
+    /// * `address` - The first register address (0x00-0x1F).
+    /// * `buffer` - The buffer receiving the register values, one byte per register.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the read was successful, `Error::InvalidInput` if the burst runs past
+    ///   register 0x1F, or an error if the read fails.
+    pub fn read_registers_into(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Error<E>> {
+        if !in_register_file(address, buffer.len()) {
+            return Err(Error::InvalidInput);
+        }
+        Ok(self.read_burst(address, buffer)?)
+    }
+
+    /// Writes consecutive registers from a caller-provided buffer.
+    ///
+    /// Unlike `read_registers_into`, this is not zero-copy: the transport copies the buffer behind the register
+    /// address on its stack, and the bus writes from that copy. The bytes are written as they are: writing the flag
+    /// register clears every flag whose bit is 0, and writing the control register can stop interrupts the driver
+    /// relies on. Bursts longer than 16 registers are split in two transactions.
+    ///
+    /// # Arguments
+    /// * `address` - The first register address (0x00-0x1F).
+    /// * `buffer` - The register values, one byte per register.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the write was successful, `Error::InvalidInput` if the burst runs past
+    ///   register 0x1F, or an error if the write fails.
+    pub fn write_registers_from(&mut self, address: u8, buffer: &[u8]) -> Result<(), Error<E>> {
+        if !in_register_file(address, buffer.len()) {
+            return Err(Error::InvalidInput);
+        }
+        self.write_burst(address, buffer)
+    }
+
+    /// Reads the SEC to YEAR registers in one burst into a caller-provided buffer, like `read_time_raw`.
+    ///
+    /// # Arguments
+    /// * `buffer` - The buffer receiving the SEC, MIN, HOUR, WEEK, DAY, MONTH and YEAR registers.
+    ///
+    /// # Returns
+    /// * `Result<(), E>` - Ok if the read was successful, or an error if the read fails.
+    pub fn read_time_raw_into(&mut self, buffer: &mut [u8; 7]) -> Result<(), E> {
+        self.read_registers(RegisterTable::CompatibleSEC, buffer)
+    }
+}
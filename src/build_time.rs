@@ -0,0 +1,45 @@
+//! The firmware build timestamp, embedded at compile time.
+
+use chrono::{DateTime, NaiveDateTime};
+
+/// Seconds since the Unix epoch at which this crate was compiled, set by `build.rs`.
+const BUILD_TIME: i64 = parse_seconds(env!("RX8900_BUILD_TIME"));
+
+/// Parses a decimal number of seconds at compile time.
+const fn parse_seconds(text: &str) -> i64 {
+    let bytes = text.as_bytes();
+    let mut seconds = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        assert!(bytes[i].is_ascii_digit(), "RX8900_BUILD_TIME must be a number of seconds");
+        seconds = seconds * 10 + (bytes[i] - b'0') as i64;
+        i += 1;
+    }
+    seconds
+}
+
+/// Returns the UTC date and time at which the driver was compiled.
+///
+/// The value honors `SOURCE_DATE_EPOCH` for reproducible builds. Since it is captured when this
+/// crate is compiled, an incremental build of the firmware may keep an older value; it is always a
+/// lower bound of the actual firmware build time.
+pub fn build_time() -> NaiveDateTime {
+    DateTime::from_timestamp(BUILD_TIME, 0).unwrap_or_default().naive_utc()
+}
+
+/// Programs the firmware build time into the RTC if the voltage low flag shows it lost its time.
+///
+/// Expands to `rx8900.set_if_invalid(rx8900::build_time())` and evaluates to its
+/// `Result<bool, E>`.
+///
+/// ```ignore
+/// let mut rx8900 = Rx8900::new_unchecked(i2c);
+/// rx8900.init()?;
+/// rx8900::set_datetime_from_build_time!(rx8900)?;
+/// ```
+#[macro_export]
+macro_rules! set_datetime_from_build_time {
+    ($rx8900:expr) => {
+        $rx8900.set_if_invalid($crate::build_time())
+    };
+}
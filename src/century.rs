@@ -0,0 +1,72 @@
+//! A driver whose century is fixed at compile time, for products where the year mapping must not be configurable.
+
+use chrono::NaiveDateTime;
+
+use crate::{Error, IntoNaiveUtc, Rx8900, Transport};
+
+/// An `Rx8900` reading and writing dates in the century starting at `BASE`.
+///
+/// The RX8900 only stores a two-digit year. `Rx8900` maps it to 2000-2099; `Century<I2C, 2100>` maps the same
+/// registers to 2100-2199 instead, and a `BASE` that is not a multiple of 100 is rejected at compile time. The
+/// chip takes every year divisible by 4 as a leap year, which is only right for the century starting at 2000:
+/// with another base, the chip counts a February 29 in the base year if it is not a leap year.
+///
+/// ```ignore
+/// type Clock = Century<I2c, 2000>;
+/// let mut clock = Clock::new(Rx8900::new_unchecked(i2c));
+/// let now = clock.datetime()?;
+/// ```
+pub struct Century<I2C, const BASE: i32> {
+    rx8900: Rx8900<I2C>,
+}
+
+impl<I2C, const BASE: i32> Century<I2C, BASE> {
+    /// Fails the build for a base that is not the first year of a century.
+    const VALID_BASE: () = assert!(BASE % 100 == 0, "the year base must be a multiple of 100");
+
+    /// Wraps a driver.
+    ///
+    /// # Arguments
+    /// * `rx8900` - The driver to read and write the date and time with.
+    pub fn new(rx8900: Rx8900<I2C>) -> Self {
+        let () = Self::VALID_BASE;
+        Self { rx8900 }
+    }
+
+    /// Returns the wrapped driver, for the features that do not involve the year.
+    pub fn rx8900(&mut self) -> &mut Rx8900<I2C> {
+        &mut self.rx8900
+    }
+
+    /// Unwraps the driver.
+    pub fn release(self) -> Rx8900<I2C> {
+        self.rx8900
+    }
+}
+
+impl<I2C, E, const BASE: i32> Century<I2C, BASE>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Reads the current date and time, with the year counted from `BASE`.
+    ///
+    /// # Returns
+    /// * `Result<NaiveDateTime, Error<E>>` - The current date and time, `Error::InvalidTime` with the raw registers
+    ///   if they do not hold a valid date and time in the decode mode of the driver, or an error if the read fails.
+    pub fn datetime(&mut self) -> Result<NaiveDateTime, Error<E>> {
+        let raw = self.rx8900.read_time_raw()?;
+        self.rx8900.decode_mode().decode_time_in(&raw, BASE).ok_or(Error::InvalidTime(raw))
+    }
+
+    /// Sets the date and time.
+    ///
+    /// # Arguments
+    /// * `data` - The `NaiveDateTime` or `DateTime<Tz>` to be set, within `BASE` to `BASE + 99`.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the date and time were set, `Error::InvalidInput` if the year is outside the
+    ///   century, or an error if the write fails.
+    pub fn set_datetime(&mut self, data: impl IntoNaiveUtc) -> Result<(), Error<E>> {
+        self.rx8900.set_datetime_in(data.into_naive_utc(), BASE)
+    }
+}
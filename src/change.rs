@@ -0,0 +1,78 @@
+//! Detection of changes made to the chip behind the driver's back, by other bus masters or debug tools.
+
+use crate::{Error, Rx8900, Snapshot, Transport};
+
+/// The logical fields found changed by `ChangeDetector::poll`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Changes {
+    /// The clock moved by this many seconds more than the elapsed time given to `poll`, beyond the tolerance.
+    pub time_stepped: Option<i64>,
+    /// The configuration differs from the previous poll.
+    pub config_altered: bool,
+    /// The voltage low flag (VLF) was raised since the previous poll.
+    pub voltage_low_raised: bool,
+    /// The voltage detection flag (VDET) was raised since the previous poll.
+    pub voltage_detect_raised: bool,
+}
+
+impl Changes {
+    /// Returns whether nothing changed.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Compares each `Snapshot` of the chip with the previous one.
+///
+/// Poll it periodically with the time elapsed on an MCU clock since the previous poll; a clock running at
+/// another pace than that time was set by someone else, while a changed configuration or a raised supply flag
+/// is reported as is.
+#[derive(Clone, Copy, Debug)]
+pub struct ChangeDetector {
+    last: Option<Snapshot>,
+    tolerance: i64,
+}
+
+impl ChangeDetector {
+    /// Creates a detector without a previous snapshot.
+    ///
+    /// # Arguments
+    /// * `tolerance` - The difference in seconds between the clock and the elapsed time that is not reported as a
+    ///   step, covering the one-second resolution of the clock and the jitter of the polls.
+    pub fn new(tolerance: i64) -> Self {
+        Self { last: None, tolerance: tolerance.max(0) }
+    }
+
+    /// Returns the snapshot taken by the last poll.
+    pub fn last(&self) -> Option<&Snapshot> {
+        self.last.as_ref()
+    }
+
+    /// Takes a snapshot and compares it with the previous one.
+    ///
+    /// The first poll only stores the snapshot and reports no change.
+    ///
+    /// # Arguments
+    /// * `rx8900` - The RTC to watch.
+    /// * `elapsed` - The seconds elapsed on an MCU clock since the previous poll.
+    ///
+    /// # Returns
+    /// * `Result<Changes, Error<E>>` - The fields that changed, `Error::InvalidTime` if the time registers hold no
+    ///   valid date and time, or an error if the read fails.
+    pub fn poll<I2C, E>(&mut self, rx8900: &mut Rx8900<I2C>, elapsed: i64) -> Result<Changes, Error<E>>
+    where
+        I2C: Transport<Error = E>,
+    {
+        let snapshot = rx8900.snapshot()?;
+        let Some(last) = self.last.replace(snapshot) else {
+            return Ok(Changes::default());
+        };
+        let step = snapshot.timestamp - last.timestamp - elapsed;
+        Ok(Changes {
+            time_stepped: (step.abs() > self.tolerance).then_some(step),
+            config_altered: snapshot.config != last.config,
+            voltage_low_raised: snapshot.voltage_low && !last.voltage_low,
+            voltage_detect_raised: snapshot.voltage_detected && !last.voltage_detected,
+        })
+    }
+}
@@ -0,0 +1,88 @@
+//! Ready-made command handlers for the `menu` CLI crate.
+//!
+//! The handlers answer like the `console` protocol. The menu context gives access to the RTC and
+//! the output through `CliContext`:
+//!
+//! ```ignore
+//! struct Context { rtc: Rx8900<I2c>, uart: Uart }
+//!
+//! impl CliContext for Context {
+//!     type Bus = I2c;
+//!     type Error = I2cError;
+//!     fn split(&mut self) -> (&mut Rx8900<I2c>, &mut dyn core::fmt::Write) {
+//!         (&mut self.rtc, &mut self.uart)
+//!     }
+//! }
+//!
+//! static TIME: Item<Context> = rx8900::cli::time_item();
+//! static ALARM: Item<Context> = rx8900::cli::alarm_item();
+//! static DUMP: Item<Context> = rx8900::cli::dump_item();
+//! static ROOT: Menu<Context> = Menu { label: "root", items: &[&TIME, &ALARM, &DUMP], entry: None, exit: None };
+//! ```
+
+use core::fmt;
+use menu::{Item, ItemType, Menu};
+
+use crate::{commands, Rx8900, Transport};
+
+/// The menu context of an application using the handlers of this module.
+pub trait CliContext {
+    /// The I2C bus of the RTC.
+    type Bus: Transport<Error = Self::Error>;
+    /// The error of the I2C bus.
+    type Error;
+
+    /// Returns the RTC and the output of the CLI.
+    fn split(&mut self) -> (&mut Rx8900<Self::Bus>, &mut dyn fmt::Write);
+}
+
+/// Returns the arguments of a command line, skipping the command itself.
+fn arguments(line: &str) -> core::str::SplitAsciiWhitespace<'_> {
+    let mut words = line.split_ascii_whitespace();
+    words.next();
+    words
+}
+
+/// `time [YYYY-MM-DDTHH:MM:SS]`: prints the date and time, or sets it (UTC) and clears VLF.
+pub fn time<T: CliContext>(_menu: &Menu<T>, _item: &Item<T>, line: &str, context: &mut T) {
+    let (rtc, out) = context.split();
+    let mut arguments = arguments(line);
+    let _ = match (arguments.next(), arguments.next()) {
+        (None, _) => commands::get_time(rtc, out),
+        (Some(value), None) => commands::set_time(rtc, value, out),
+        (Some(_), Some(_)) => write!(out, "ERR too many arguments\r\n"),
+    };
+}
+
+/// `alarm [HH:MM|OFF]`: prints the alarm state, arms a daily alarm, or disarms it.
+#[cfg(feature = "alarm")]
+pub fn alarm<T: CliContext>(_menu: &Menu<T>, _item: &Item<T>, line: &str, context: &mut T) {
+    let (rtc, out) = context.split();
+    let mut arguments = arguments(line);
+    let _ = match (arguments.next(), arguments.next()) {
+        (argument, None) => commands::alarm(rtc, argument, out),
+        (_, Some(_)) => write!(out, "ERR too many arguments\r\n"),
+    };
+}
+
+/// `dump`: prints the annotated register table.
+pub fn dump<T: CliContext>(_menu: &Menu<T>, _item: &Item<T>, _line: &str, context: &mut T) {
+    let (rtc, out) = context.split();
+    let _ = commands::dump(rtc, out);
+}
+
+/// Returns the `time` menu item.
+pub const fn time_item<T: CliContext>() -> Item<'static, T> {
+    Item { command: "time", help: Some("print the RTC time, or set it (UTC) with YYYY-MM-DDTHH:MM:SS"), item_type: ItemType::Callback(time::<T>) }
+}
+
+/// Returns the `alarm` menu item.
+#[cfg(feature = "alarm")]
+pub const fn alarm_item<T: CliContext>() -> Item<'static, T> {
+    Item { command: "alarm", help: Some("print the alarm state, arm a daily alarm with HH:MM, or disarm it with OFF"), item_type: ItemType::Callback(alarm::<T>) }
+}
+
+/// Returns the `dump` menu item.
+pub const fn dump_item<T: CliContext>() -> Item<'static, T> {
+    Item { command: "dump", help: Some("print the RTC registers"), item_type: ItemType::Callback(dump::<T>) }
+}
@@ -0,0 +1,108 @@
+//! Text commands shared by the serial console and the CLI integrations.
+//!
+//! Every command writes a single response line: `OK`, possibly followed by a value, or
+//! `ERR <reason>`. Bus errors are reported as `ERR bus` rather than returned.
+
+use core::fmt;
+use chrono::{Datelike, NaiveDateTime, Timelike};
+
+#[cfg(feature = "alarm")]
+use crate::AlarmConfig;
+use crate::{Error, Rx8900, Transport};
+
+/// Executes a command line such as `GET TIME` or `ALARM 06:30`.
+#[cfg(feature = "console")]
+pub(crate) fn execute<I2C, E>(rx8900: &mut Rx8900<I2C>, command: &str, out: &mut dyn fmt::Write) -> fmt::Result
+where
+    I2C: Transport<Error = E>,
+{
+    let mut words = command.split_ascii_whitespace();
+    match (words.next(), words.next(), words.next()) {
+        (Some("GET"), Some("TIME"), None) => get_time(rx8900, out),
+        (Some("SET"), Some("TIME"), Some(value)) if words.next().is_none() => set_time(rx8900, value, out),
+        (Some("DUMP"), None, None) => dump(rx8900, out),
+        #[cfg(feature = "alarm")]
+        (Some("ALARM"), argument, None) => alarm(rx8900, argument, out),
+        _ => write!(out, "ERR unknown command\r\n"),
+    }
+}
+
+/// Prints the date and time, e.g. `OK 2024-01-31T12:34:56`.
+pub(crate) fn get_time<I2C, E>(rx8900: &mut Rx8900<I2C>, out: &mut dyn fmt::Write) -> fmt::Result
+where
+    I2C: Transport<Error = E>,
+{
+    match rx8900.datetime() {
+        Ok(now) => write!(
+            out,
+            "OK {:04}-{:02}-{:02}T{:02}:{:02}:{:02}\r\n",
+            now.year(),
+            now.month(),
+            now.day(),
+            now.hour(),
+            now.minute(),
+            now.second()
+        ),
+        Err(Error::InvalidTime(_)) => write!(out, "ERR invalid time\r\n"),
+        Err(_) => write!(out, "ERR bus\r\n"),
+    }
+}
+
+/// Sets the date and time from `YYYY-MM-DDTHH:MM:SS` (UTC) and clears VLF.
+pub(crate) fn set_time<I2C, E>(rx8900: &mut Rx8900<I2C>, value: &str, out: &mut dyn fmt::Write) -> fmt::Result
+where
+    I2C: Transport<Error = E>,
+{
+    let now = match NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S") {
+        Ok(now) if (2000..=2099).contains(&now.year()) => now,
+        _ => return write!(out, "ERR invalid time\r\n"),
+    };
+    // VLF
+    match rx8900.set_datetime(now).and_then(|_| rx8900.clear_flags(0b00000010)) {
+        Ok(()) => write!(out, "OK\r\n"),
+        Err(_) => write!(out, "ERR bus\r\n"),
+    }
+}
+
+/// Prints the annotated register table, then `OK`.
+pub(crate) fn dump<I2C, E>(rx8900: &mut Rx8900<I2C>, out: &mut dyn fmt::Write) -> fmt::Result
+where
+    I2C: Transport<Error = E>,
+{
+    match rx8900.register_dump() {
+        Ok(dump) => write!(out, "{}OK\r\n", dump),
+        Err(_) => write!(out, "ERR bus\r\n"),
+    }
+}
+
+/// Prints the alarm state without argument, arms a daily alarm with `HH:MM`, or disarms it with `OFF`.
+#[cfg(feature = "alarm")]
+pub(crate) fn alarm<I2C, E>(rx8900: &mut Rx8900<I2C>, argument: Option<&str>, out: &mut dyn fmt::Write) -> fmt::Result
+where
+    I2C: Transport<Error = E>,
+{
+    let result = match argument {
+        None => {
+            return match rx8900.aie().and_then(|aie| Ok((aie, rx8900.af()?))) {
+                Ok((aie, af)) => write!(out, "OK AIE={} AF={}\r\n", aie as u8, af as u8),
+                Err(_) => write!(out, "ERR bus\r\n"),
+            }
+        }
+        Some("OFF") => crate::RtcAlarm::cancel_alarm(rx8900),
+        Some(time) => {
+            let config = time
+                .split_once(':')
+                .and_then(|(hour, minute)| Some((hour.parse().ok()?, minute.parse().ok()?)))
+                .map(|(hour, minute)| AlarmConfig::default().hour(hour).minute(minute))
+                .filter(|config| config.is_valid() && rx8900.alarm_interval_allowed(config));
+            match config {
+                Some(config) => rx8900.write_alarm(&config).and_then(|_| rx8900.set_aie(true)),
+                None => return write!(out, "ERR invalid time\r\n"),
+            }
+        }
+    };
+    match result {
+        Ok(()) => write!(out, "OK\r\n"),
+        Err(_) => write!(out, "ERR bus\r\n"),
+    }
+}
@@ -0,0 +1,449 @@
+//! Device configuration that can be applied to the RX8900 in one go.
+
+#[cfg(feature = "alarm")]
+use crate::AlarmType;
+#[cfg(feature = "timer")]
+use crate::SourceClock;
+use crate::{CompensationIntervalType, FoutFrequency, UpdateInterruptType};
+
+/// The configuration held in the extension, control and backup function registers.
+///
+/// Apply it with `Rx8900::apply_config`, which writes each affected register exactly once.
+/// The default value matches the state `Rx8900::init` leaves the chip in, with the
+/// compensation interval and backup sampling time at their power-on values.
+///
+/// Start from the default and chain the setters, so new fields can be added without breaking call sites:
+///
+/// ```ignore
+/// let config = Config::default()
+///     .fout(FoutFrequency::FoutFrequency1Hz)
+///     .compensation(CompensationIntervalType::CompensationInterval2_0s);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct Config {
+    /// Frequency of the FOUT pin (FSEL).
+    pub fout_frequency: FoutFrequency,
+    /// Period of the update interrupt (USEL).
+    pub update_interrupt_type: UpdateInterruptType,
+    /// Update interrupt enable (UIE).
+    pub update_interrupt_enable: bool,
+    /// Temperature compensation interval (CSEL).
+    pub compensation_interval_type: CompensationIntervalType,
+    /// Day or week alarm (WADA).
+    #[cfg(feature = "alarm")]
+    pub alarm_type: AlarmType,
+    /// Alarm interrupt enable (AIE).
+    #[cfg(feature = "alarm")]
+    pub alarm_interrupt_enable: bool,
+    /// Source clock of the wake-up timer (TSEL).
+    #[cfg(feature = "timer")]
+    pub source_clock: SourceClock,
+    /// Wake-up timer enable (TE).
+    #[cfg(feature = "timer")]
+    pub timer_enable: bool,
+    /// Timer interrupt enable (TIE).
+    #[cfg(feature = "timer")]
+    pub timer_interrupt_enable: bool,
+    /// Voltage detector off (VDETOFF).
+    pub voltage_detector_off: bool,
+    /// Switch off (SWOFF).
+    pub switch_off: bool,
+    /// Backup mode sampling time (BKSMP, 0-3); other values are rejected with `Error::InvalidInput`.
+    pub backup_mode_sampling_time: u8,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            fout_frequency: FoutFrequency::FoutFrequency32_768kHz,
+            update_interrupt_type: UpdateInterruptType::EverySecond,
+            update_interrupt_enable: false,
+            compensation_interval_type: CompensationIntervalType::CompensationInterval2_0s,
+            #[cfg(feature = "alarm")]
+            alarm_type: AlarmType::WeekAlarm,
+            #[cfg(feature = "alarm")]
+            alarm_interrupt_enable: false,
+            #[cfg(feature = "timer")]
+            source_clock: SourceClock::SourceClock4096Hz,
+            #[cfg(feature = "timer")]
+            timer_enable: false,
+            #[cfg(feature = "timer")]
+            timer_interrupt_enable: false,
+            voltage_detector_off: false,
+            switch_off: true,
+            backup_mode_sampling_time: 0b10,
+        }
+    }
+}
+
+impl Config {
+    /// Sets the frequency of the FOUT pin (FSEL).
+    pub fn fout(mut self, value: FoutFrequency) -> Self {
+        self.fout_frequency = value;
+        self
+    }
+
+    /// Sets the period of the update interrupt (USEL).
+    pub fn update_interrupt(mut self, value: UpdateInterruptType) -> Self {
+        self.update_interrupt_type = value;
+        self
+    }
+
+    /// Sets the update interrupt enable (UIE).
+    pub fn update_interrupt_enable(mut self, value: bool) -> Self {
+        self.update_interrupt_enable = value;
+        self
+    }
+
+    /// Sets the temperature compensation interval (CSEL).
+    pub fn compensation(mut self, value: CompensationIntervalType) -> Self {
+        self.compensation_interval_type = value;
+        self
+    }
+
+    /// Sets the day or week alarm (WADA).
+    #[cfg(feature = "alarm")]
+    pub fn alarm_type(mut self, value: AlarmType) -> Self {
+        self.alarm_type = value;
+        self
+    }
+
+    /// Sets the alarm interrupt enable (AIE).
+    #[cfg(feature = "alarm")]
+    pub fn alarm_interrupt_enable(mut self, value: bool) -> Self {
+        self.alarm_interrupt_enable = value;
+        self
+    }
+
+    /// Sets the source clock of the wake-up timer (TSEL).
+    #[cfg(feature = "timer")]
+    pub fn source_clock(mut self, value: SourceClock) -> Self {
+        self.source_clock = value;
+        self
+    }
+
+    /// Sets the wake-up timer enable (TE).
+    #[cfg(feature = "timer")]
+    pub fn timer_enable(mut self, value: bool) -> Self {
+        self.timer_enable = value;
+        self
+    }
+
+    /// Sets the timer interrupt enable (TIE).
+    #[cfg(feature = "timer")]
+    pub fn timer_interrupt_enable(mut self, value: bool) -> Self {
+        self.timer_interrupt_enable = value;
+        self
+    }
+
+    /// Sets the voltage detector off bit (VDETOFF).
+    pub fn voltage_detector_off(mut self, value: bool) -> Self {
+        self.voltage_detector_off = value;
+        self
+    }
+
+    /// Sets the switch off bit (SWOFF).
+    pub fn switch_off(mut self, value: bool) -> Self {
+        self.switch_off = value;
+        self
+    }
+
+    /// Sets the backup mode sampling time (BKSMP, 0-3).
+    pub fn backup_mode_sampling_time(mut self, value: u8) -> Self {
+        self.backup_mode_sampling_time = value;
+        self
+    }
+
+    /// Serializes the configuration to JSON.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> std::string::String {
+        // Serializing plain fields and unit enum variants cannot fail.
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Parses a configuration serialized with `to_json`.
+    ///
+    /// # Arguments
+    /// * `json` - The JSON text.
+    ///
+    /// # Returns
+    /// * `Result<Config, serde_json::Error>` - The configuration, or an error if the text is not a valid configuration.
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Returns the bits of the extension register owned by the configuration and their values.
+    pub(crate) fn extension_register(&self) -> (u8, u8) {
+        // TEST and FSEL
+        let mut mask = 0b10001100;
+        let mut data = (self.fout_frequency as u8) << 2;
+        // USEL
+        mask |= 0b00100000;
+        data |= (self.update_interrupt_type as u8) << 5;
+        #[cfg(feature = "alarm")]
+        {
+            // WADA
+            mask |= 0b01000000;
+            data |= (self.alarm_type as u8) << 6;
+        }
+        #[cfg(feature = "timer")]
+        {
+            // TE and TSEL
+            mask |= 0b00010011;
+            data |= (self.timer_enable as u8) << 4 | self.source_clock as u8;
+        }
+        (mask, data)
+    }
+
+    /// Returns the bits of the control register owned by the configuration and their values.
+    #[allow(unused_mut)] // Only AIE and TIE are optional.
+    pub(crate) fn control_register(&self) -> (u8, u8) {
+        // CSEL and UIE
+        let mut mask = 0b11100000;
+        let mut data = (self.compensation_interval_type as u8) << 6 | (self.update_interrupt_enable as u8) << 5;
+        #[cfg(feature = "alarm")]
+        {
+            // AIE
+            mask |= 0b00001000;
+            data |= (self.alarm_interrupt_enable as u8) << 3;
+        }
+        #[cfg(feature = "timer")]
+        {
+            // TIE
+            mask |= 0b00010000;
+            data |= (self.timer_interrupt_enable as u8) << 4;
+        }
+        (mask, data)
+    }
+
+    /// Decodes the configuration from the extension, control and backup function registers.
+    pub(crate) fn from_registers(extension: u8, control: u8, backup_function: u8) -> Self {
+        Self {
+            fout_frequency: match (extension >> 2) & 0b11 {
+                0b01 => FoutFrequency::FoutFrequency1024Hz,
+                0b10 => FoutFrequency::FoutFrequency1Hz,
+                _ => FoutFrequency::FoutFrequency32_768kHz,
+            },
+            update_interrupt_type: if extension & 0b00100000 != 0 {
+                UpdateInterruptType::EveryMinute
+            } else {
+                UpdateInterruptType::EverySecond
+            },
+            update_interrupt_enable: control & 0b00100000 != 0,
+            compensation_interval_type: match control >> 6 {
+                0b00 => CompensationIntervalType::CompensationInterval0_5s,
+                0b01 => CompensationIntervalType::CompensationInterval2_0s,
+                0b10 => CompensationIntervalType::CompensationInterval10s,
+                _ => CompensationIntervalType::CompensationInterval30s,
+            },
+            #[cfg(feature = "alarm")]
+            alarm_type: if extension & 0b01000000 != 0 { AlarmType::DayAlarm } else { AlarmType::WeekAlarm },
+            #[cfg(feature = "alarm")]
+            alarm_interrupt_enable: control & 0b00001000 != 0,
+            #[cfg(feature = "timer")]
+            source_clock: match extension & 0b11 {
+                0b00 => SourceClock::SourceClock4096Hz,
+                0b01 => SourceClock::SourceClock64Hz,
+                0b10 => SourceClock::SourceClockSecond,
+                _ => SourceClock::SourceClockMinute,
+            },
+            #[cfg(feature = "timer")]
+            timer_enable: extension & 0b00010000 != 0,
+            #[cfg(feature = "timer")]
+            timer_interrupt_enable: control & 0b00010000 != 0,
+            voltage_detector_off: backup_function & 0b00001000 != 0,
+            switch_off: backup_function & 0b00000100 != 0,
+            backup_mode_sampling_time: backup_function & 0b00000011,
+        }
+    }
+
+    /// Returns whether every field fits its bits.
+    pub(crate) fn is_valid(&self) -> bool {
+        self.backup_mode_sampling_time <= 0b11
+    }
+
+    /// Returns the bits of the backup function register owned by the configuration and their values.
+    pub(crate) fn backup_function_register(&self) -> (u8, u8) {
+        // VDETOFF, SWOFF and BKSMP
+        let mask = 0b00001111;
+        let data = (self.voltage_detector_off as u8) << 3
+            | (self.switch_off as u8) << 2
+            | (self.backup_mode_sampling_time & 0b00000011);
+        (mask, data)
+    }
+}
+
+/// The settings `Rx8900::init_with` programs while initializing the chip.
+///
+/// The default value reproduces `Rx8900::init`: FOUT at 32.768 kHz, all interrupts disabled, VDETOFF=0 and
+/// SWOFF=1, with the compensation interval and backup sampling time left as they are. Like `Config`, start from the
+/// default and chain the setters:
+///
+/// ```ignore
+/// let init = InitConfig::default()
+///     .fout(FoutFrequency::FoutFrequency1Hz)
+///     .compensation(CompensationIntervalType::CompensationInterval30s)
+///     .update_interrupt_enable(true);
+/// rx8900.init_with(&init)?;
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct InitConfig {
+    /// Frequency of the FOUT pin (FSEL).
+    pub fout_frequency: FoutFrequency,
+    /// Temperature compensation interval (CSEL), or None to leave it unchanged.
+    pub compensation_interval_type: Option<CompensationIntervalType>,
+    /// Update interrupt enable (UIE).
+    pub update_interrupt_enable: bool,
+    /// Alarm interrupt enable (AIE).
+    #[cfg(feature = "alarm")]
+    pub alarm_interrupt_enable: bool,
+    /// Timer interrupt enable (TIE).
+    #[cfg(feature = "timer")]
+    pub timer_interrupt_enable: bool,
+    /// Voltage detector off (VDETOFF).
+    pub voltage_detector_off: bool,
+    /// Switch off (SWOFF).
+    pub switch_off: bool,
+    /// Backup mode sampling time (BKSMP, 0-3), or None to leave it unchanged; other values are rejected with
+    /// `Error::InvalidInput`.
+    pub backup_mode_sampling_time: Option<u8>,
+}
+
+impl Default for InitConfig {
+    fn default() -> Self {
+        Self {
+            fout_frequency: FoutFrequency::FoutFrequency32_768kHz,
+            compensation_interval_type: None,
+            update_interrupt_enable: false,
+            #[cfg(feature = "alarm")]
+            alarm_interrupt_enable: false,
+            #[cfg(feature = "timer")]
+            timer_interrupt_enable: false,
+            voltage_detector_off: false,
+            switch_off: true,
+            backup_mode_sampling_time: None,
+        }
+    }
+}
+
+impl InitConfig {
+    /// Sets the frequency of the FOUT pin (FSEL).
+    pub fn fout(mut self, value: FoutFrequency) -> Self {
+        self.fout_frequency = value;
+        self
+    }
+
+    /// Sets the temperature compensation interval (CSEL).
+    pub fn compensation(mut self, value: CompensationIntervalType) -> Self {
+        self.compensation_interval_type = Some(value);
+        self
+    }
+
+    /// Sets the update interrupt enable (UIE).
+    pub fn update_interrupt_enable(mut self, value: bool) -> Self {
+        self.update_interrupt_enable = value;
+        self
+    }
+
+    /// Sets the alarm interrupt enable (AIE).
+    #[cfg(feature = "alarm")]
+    pub fn alarm_interrupt_enable(mut self, value: bool) -> Self {
+        self.alarm_interrupt_enable = value;
+        self
+    }
+
+    /// Sets the timer interrupt enable (TIE).
+    #[cfg(feature = "timer")]
+    pub fn timer_interrupt_enable(mut self, value: bool) -> Self {
+        self.timer_interrupt_enable = value;
+        self
+    }
+
+    /// Sets the voltage detector off bit (VDETOFF).
+    pub fn voltage_detector_off(mut self, value: bool) -> Self {
+        self.voltage_detector_off = value;
+        self
+    }
+
+    /// Sets the switch off bit (SWOFF).
+    pub fn switch_off(mut self, value: bool) -> Self {
+        self.switch_off = value;
+        self
+    }
+
+    /// Sets the backup mode sampling time (BKSMP, 0-3).
+    pub fn backup_mode_sampling_time(mut self, value: u8) -> Self {
+        self.backup_mode_sampling_time = Some(value);
+        self
+    }
+
+    /// Returns whether every field fits its bits.
+    pub(crate) fn is_valid(&self) -> bool {
+        self.backup_mode_sampling_time.map_or(true, |sampling_time| sampling_time <= 0b11)
+    }
+
+    /// Returns the bits of the extension register to write and their values.
+    pub(crate) fn extension_register(&self) -> (u8, u8) {
+        // TEST=0, TE=0 and FSEL
+        (0b10011100, (self.fout_frequency as u8) << 2)
+    }
+
+    /// Returns the bits of the control register to write and their values.
+    pub(crate) fn control_register(&self) -> (u8, u8) {
+        // UIE, TIE and AIE; the enables of disabled features stay cleared.
+        let mut mask = 0b00111000;
+        let mut data = (self.update_interrupt_enable as u8) << 5;
+        #[cfg(feature = "alarm")]
+        {
+            data |= (self.alarm_interrupt_enable as u8) << 3;
+        }
+        #[cfg(feature = "timer")]
+        {
+            data |= (self.timer_interrupt_enable as u8) << 4;
+        }
+        if let Some(interval) = self.compensation_interval_type {
+            // CSEL
+            mask |= 0b11000000;
+            data |= (interval as u8) << 6;
+        }
+        (mask, data)
+    }
+
+    /// Returns the bits of the backup function register to write and their values.
+    pub(crate) fn backup_function_register(&self) -> (u8, u8) {
+        // VDETOFF and SWOFF
+        let mut mask = 0b00001100;
+        let mut data = (self.voltage_detector_off as u8) << 3 | (self.switch_off as u8) << 2;
+        if let Some(sampling_time) = self.backup_mode_sampling_time {
+            // BKSMP
+            mask |= 0b00000011;
+            data |= sampling_time & 0b00000011;
+        }
+        (mask, data)
+    }
+}
+
+/// The configuration bits that differed between the chip and a desired `Config`.
+///
+/// Returned by `Rx8900::diff_config`. Each field holds the bits of one register that had to be changed;
+/// a register whose field is 0 was left untouched.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConfigDelta {
+    /// Changed bits of the extension register.
+    pub extension: u8,
+    /// Changed bits of the control register.
+    pub control: u8,
+    /// Changed bits of the backup function register.
+    pub backup_function: u8,
+}
+
+impl ConfigDelta {
+    /// Returns whether the chip already matched the configuration.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
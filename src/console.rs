@@ -0,0 +1,143 @@
+//! A line protocol for provisioning the RTC over a serial port.
+//!
+//! Commands are case-sensitive, one per line, answered by `OK` (possibly followed by a value)
+//! or `ERR <reason>`:
+//!
+//! * `GET TIME` - prints the date and time, e.g. `OK 2024-01-31T12:34:56`.
+//! * `SET TIME <YYYY-MM-DDTHH:MM:SS>` - sets the date and time (UTC) and clears VLF.
+//! * `DUMP` - prints the annotated register table, then `OK`.
+//! * `ALARM` - prints the alarm state, e.g. `OK AIE=1 AF=0`.
+//! * `ALARM <HH:MM>` - arms the alarm every day at the given time.
+//! * `ALARM OFF` - disarms the alarm and clears AF.
+//!
+//! The `ALARM` commands require the `alarm` feature.
+
+use core::fmt;
+
+use crate::{commands, Rx8900, Transport};
+
+/// Longest command line accepted, in bytes.
+pub const LINE_LEN: usize = 64;
+
+/// Serves the provisioning line protocol on an `embedded_io` stream.
+///
+/// ```ignore
+/// let mut console = Console::new(uart);
+/// loop {
+///     console.poll(&mut rx8900)?;
+/// }
+/// ```
+pub struct Console<IO> {
+    io: IO,
+    line: [u8; LINE_LEN],
+    len: usize,
+    overflow: bool,
+}
+
+/// Adapts an `embedded_io` writer to `core::fmt::Write`, keeping the I/O error.
+struct Output<'a, IO: embedded_io::Write> {
+    io: &'a mut IO,
+    error: Option<IO::Error>,
+}
+
+impl<IO: embedded_io::Write> fmt::Write for Output<'_, IO> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.io.write_all(s.as_bytes()).map_err(|error| {
+            self.error = Some(error);
+            fmt::Error
+        })
+    }
+}
+
+impl<IO: embedded_io::Write> Output<'_, IO> {
+    /// Returns the I/O error that made formatting fail, if any.
+    fn finish(self, result: fmt::Result) -> Result<(), IO::Error> {
+        match (result, self.error) {
+            (Err(_), Some(error)) => Err(error),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<IO> Console<IO> {
+    /// Creates a console on a serial stream.
+    pub fn new(io: IO) -> Self {
+        Self { io, line: [0; LINE_LEN], len: 0, overflow: false }
+    }
+
+    /// Returns the serial stream.
+    pub fn into_inner(self) -> IO {
+        self.io
+    }
+}
+
+impl<IO> Console<IO>
+where
+    IO: embedded_io::Read + embedded_io::Write,
+{
+    /// Reads from the stream once and executes the commands completed by the received bytes.
+    ///
+    /// # Arguments
+    /// * `rx8900` - The RTC the commands act on.
+    ///
+    /// # Returns
+    /// * `Result<usize, IO::Error>` - The number of commands executed, or an error if the stream fails. Bus errors
+    ///   are reported to the peer as `ERR bus`.
+    pub fn poll<I2C, E>(&mut self, rx8900: &mut Rx8900<I2C>) -> Result<usize, IO::Error>
+    where
+        I2C: Transport<Error = E>,
+    {
+        let mut buffer = [0; 16];
+        let count = self.io.read(&mut buffer)?;
+        let mut executed = 0;
+        for &byte in &buffer[..count] {
+            match byte {
+                b'\r' | b'\n' => {
+                    if self.overflow {
+                        self.respond(format_args!("ERR line too long"))?;
+                    } else if self.len > 0 {
+                        let mut line = [0; LINE_LEN];
+                        line[..self.len].copy_from_slice(&self.line[..self.len]);
+                        match core::str::from_utf8(&line[..self.len]) {
+                            Ok(command) => self.execute(rx8900, command)?,
+                            Err(_) => self.respond(format_args!("ERR invalid text"))?,
+                        }
+                        executed += 1;
+                    }
+                    self.len = 0;
+                    self.overflow = false;
+                }
+                _ if self.len == LINE_LEN => self.overflow = true,
+                _ => {
+                    self.line[self.len] = byte;
+                    self.len += 1;
+                }
+            }
+        }
+        Ok(executed)
+    }
+
+    /// Executes one command line and writes the response.
+    ///
+    /// # Arguments
+    /// * `rx8900` - The RTC the command acts on.
+    /// * `command` - The command line, without line terminator.
+    ///
+    /// # Returns
+    /// * `Result<(), IO::Error>` - Ok once the response was written, or an error if the stream fails.
+    pub fn execute<I2C, E>(&mut self, rx8900: &mut Rx8900<I2C>, command: &str) -> Result<(), IO::Error>
+    where
+        I2C: Transport<Error = E>,
+    {
+        let mut output = Output { io: &mut self.io, error: None };
+        let result = commands::execute(rx8900, command, &mut output);
+        output.finish(result)
+    }
+
+    /// Writes a response line.
+    fn respond(&mut self, response: fmt::Arguments<'_>) -> Result<(), IO::Error> {
+        let mut output = Output { io: &mut self.io, error: None };
+        let result = fmt::Write::write_fmt(&mut output, format_args!("{}\r\n", response));
+        output.finish(result)
+    }
+}
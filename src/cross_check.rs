@@ -0,0 +1,84 @@
+//! A watchdog comparing the RX8900 against an independent clock of the MCU.
+
+use crate::{Error, Rx8900, Transport};
+
+/// Reported by `CrossCheck::check` when the two clocks drifted apart by more than the bound.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    /// Time elapsed on the RX8900 since the reference point, in seconds.
+    pub rtc_elapsed: i64,
+    /// Time elapsed on the MCU clock since the reference point, in milliseconds.
+    pub reference_elapsed_ms: u64,
+    /// RX8900 minus MCU clock, in milliseconds; positive when the RX8900 runs fast.
+    pub drift_ms: i64,
+}
+
+/// Compares the RX8900 with an MCU-internal RTC or monotonic timer to catch either clock failing silently.
+///
+/// The first call to `check` takes a reference point on both clocks; each later call compares the
+/// time elapsed on both sides. The RX8900 has a resolution of one second, so one second is tolerated
+/// on top of the bound. Call `reset` after setting the RX8900, otherwise the step is reported as a
+/// divergence.
+///
+/// ```ignore
+/// let mut cross_check = CrossCheck::new(2_000);
+/// loop {
+///     if let Some(divergence) = cross_check.check(&mut rx8900, mcu_millis())? {
+///         report(divergence);
+///         cross_check.reset();
+///     }
+///     sleep_a_while();
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct CrossCheck {
+    bound_ms: u32,
+    reference: Option<(i64, u64)>,
+}
+
+impl CrossCheck {
+    /// Creates a watchdog without a reference point.
+    ///
+    /// # Arguments
+    /// * `bound_ms` - The largest drift, in milliseconds, considered normal.
+    pub fn new(bound_ms: u32) -> Self {
+        Self { bound_ms, reference: None }
+    }
+
+    /// Drops the reference point; the next `check` takes a new one.
+    pub fn reset(&mut self) {
+        self.reference = None;
+    }
+
+    /// Compares the time elapsed on the RX8900 and on the MCU clock since the reference point.
+    ///
+    /// # Arguments
+    /// * `rx8900` - The RTC to check.
+    /// * `now_ms` - The current value of the MCU clock, in milliseconds.
+    ///
+    /// # Returns
+    /// * `Result<Option<Divergence>, Error<E>>` - The divergence if it exceeds the bound, None otherwise or when the
+    ///   reference point was just taken, `Error::InvalidTime` if the time registers hold no valid date and time, or an
+    ///   error if the read fails.
+    pub fn check<I2C, E>(&mut self, rx8900: &mut Rx8900<I2C>, now_ms: u64) -> Result<Option<Divergence>, Error<E>>
+    where
+        I2C: Transport<Error = E>,
+    {
+        let rtc = rx8900.datetime()?.and_utc().timestamp();
+        let (rtc_reference, mcu_reference) = match self.reference {
+            Some(reference) => reference,
+            None => {
+                self.reference = Some((rtc, now_ms));
+                return Ok(None);
+            }
+        };
+        let rtc_elapsed = rtc - rtc_reference;
+        let reference_elapsed_ms = now_ms.wrapping_sub(mcu_reference);
+        let drift_ms = (rtc_elapsed * 1000).saturating_sub(reference_elapsed_ms.min(i64::MAX as u64) as i64);
+        if drift_ms.unsigned_abs() > self.bound_ms as u64 + 1000 {
+            Ok(Some(Divergence { rtc_elapsed, reference_elapsed_ms, drift_ms }))
+        } else {
+            Ok(None)
+        }
+    }
+}
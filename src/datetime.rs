@@ -0,0 +1,222 @@
+//! Conversions of the date and time types accepted by the driver.
+
+use crate::bcd::checked_from_bcd;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+
+/// A date and time that can be converted to the naive UTC value stored in the RTC.
+///
+/// The RTC keeps UTC. A `NaiveDateTime` is taken as UTC already, while a `DateTime` in any time
+/// zone is converted, so a local time can never be written by mistake.
+pub trait IntoNaiveUtc {
+    /// Converts the value to a naive UTC date and time.
+    fn into_naive_utc(self) -> NaiveDateTime;
+}
+
+impl IntoNaiveUtc for NaiveDateTime {
+    fn into_naive_utc(self) -> NaiveDateTime {
+        self
+    }
+}
+
+impl<Tz: TimeZone> IntoNaiveUtc for DateTime<Tz> {
+    fn into_naive_utc(self) -> NaiveDateTime {
+        self.naive_utc()
+    }
+}
+
+/// How register contents that are not a valid date and time are decoded.
+///
+/// Set with `Rx8900::set_decode_mode` and applied by `Rx8900::datetime`, `datetime_coherent`, `datetime_fast`,
+/// `datetime_decoded`, the date reads such as `ordinal`, and `Century::datetime`. Only `datetime_decoded` reports
+/// whether a field was clamped.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DecodeMode {
+    /// Any invalid BCD digit or out-of-range field is an error.
+    #[default]
+    Strict,
+    /// Invalid BCD digits are limited to 9 and out-of-range fields are clamped to the nearest valid value; the
+    /// result is flagged as clamped.
+    Lenient,
+}
+
+impl DecodeMode {
+    /// Decodes the SEC to YEAR registers read in one burst, with the two-digit year counted from `base`.
+    ///
+    /// # Arguments
+    /// * `raw` - The SEC, MIN, HOUR, WEEK, DAY, MONTH and YEAR registers.
+    /// * `base` - The first year of the century held by the clock, e.g. 2000.
+    ///
+    /// # Returns
+    /// * `Option<NaiveDateTime>` - The date and time, or None if a field is invalid in `DecodeMode::Strict`.
+    pub(crate) fn decode_time_in(self, raw: &[u8; 7], base: i32) -> Option<NaiveDateTime> {
+        match self {
+            DecodeMode::Strict => decode_time_in(raw, base),
+            DecodeMode::Lenient => Some(decode_time_lenient_in(raw, base).datetime),
+        }
+    }
+
+    /// Decodes the SEC, MIN and HOUR registers read in one burst.
+    ///
+    /// # Arguments
+    /// * `raw` - The SEC, MIN and HOUR registers.
+    ///
+    /// # Returns
+    /// * `Option<NaiveTime>` - The time of day, or None if a field is invalid in `DecodeMode::Strict`.
+    pub(crate) fn decode_time_of_day(self, raw: &[u8; 3]) -> Option<NaiveTime> {
+        match self {
+            DecodeMode::Strict => decode_time_of_day(raw),
+            DecodeMode::Lenient => {
+                let mut clamped = false;
+                let (hour, minute, second) = (
+                    lenient_field(raw[2], 0x3F, 0, 23, &mut clamped),
+                    lenient_field(raw[1], 0x7F, 0, 59, &mut clamped),
+                    lenient_field(raw[0], 0x7F, 0, 59, &mut clamped),
+                );
+                NaiveTime::from_hms_opt(hour, minute, second)
+            }
+        }
+    }
+}
+
+/// A date and time decoded from the registers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecodedTime {
+    /// The decoded date and time.
+    pub datetime: NaiveDateTime,
+    /// True if a field was clamped by `DecodeMode::Lenient`; the value is then a best effort only.
+    pub clamped: bool,
+}
+
+/// Decodes a BCD field, limiting invalid digits to 9 and clamping the value to `min..=max`.
+///
+/// # Arguments
+/// * `data` - The register value.
+/// * `mask` - The bits of the field.
+/// * `min` - The lowest valid value.
+/// * `max` - The highest valid value.
+/// * `clamped` - Set to true if the field had to be fixed.
+///
+/// # Returns
+/// * `u32` - The decoded value.
+fn lenient_field(data: u8, mask: u8, min: u32, max: u32, clamped: &mut bool) -> u32 {
+    let data = data & mask;
+    let (tens, ones) = (data >> 4, data & 0x0F);
+    let value = tens.min(9) as u32 * 10 + ones.min(9) as u32;
+    let value_clamped = value.clamp(min, max);
+    *clamped |= tens > 9 || ones > 9 || value_clamped != value;
+    value_clamped
+}
+
+/// Decodes the SEC to YEAR registers read in one burst, clamping invalid fields.
+///
+/// # Arguments
+/// * `raw` - The SEC, MIN, HOUR, WEEK, DAY, MONTH and YEAR registers.
+///
+/// # Returns
+/// * `DecodedTime` - The date and time, flagged as clamped if a field was not valid BCD or out of range.
+pub(crate) fn decode_time_lenient(raw: &[u8; 7]) -> DecodedTime {
+    decode_time_lenient_in(raw, 2000)
+}
+
+/// Decodes the SEC to YEAR registers read in one burst, clamping invalid fields, with the two-digit year counted
+/// from `base`.
+///
+/// # Arguments
+/// * `raw` - The SEC, MIN, HOUR, WEEK, DAY, MONTH and YEAR registers.
+/// * `base` - The first year of the century held by the clock, e.g. 2000.
+///
+/// # Returns
+/// * `DecodedTime` - The date and time, flagged as clamped if a field was not valid BCD or out of range.
+fn decode_time_lenient_in(raw: &[u8; 7], base: i32) -> DecodedTime {
+    let mut clamped = false;
+    let mut field = |data: u8, mask: u8, min: u32, max: u32| lenient_field(data, mask, min, max, &mut clamped);
+    let year = base + field(raw[6], 0xFF, 0, 99) as i32;
+    let month = field(raw[5], 0x1F, 1, 12);
+    let day = field(raw[4], 0x3F, 1, 31);
+    let (hour, minute, second) = (field(raw[2], 0x3F, 0, 23), field(raw[1], 0x7F, 0, 59), field(raw[0], 0x7F, 0, 59));
+    // Walk down to the last day of the month if the day is past it.
+    let date = (1..=day).rev().find_map(|day| NaiveDate::from_ymd_opt(year, month, day)).unwrap_or_default();
+    DecodedTime {
+        datetime: date.and_hms_opt(hour, minute, second).unwrap_or_default(),
+        clamped: clamped || date.day() != day,
+    }
+}
+
+/// Decodes the SEC to YEAR registers read in one burst.
+///
+/// # Arguments
+/// * `raw` - The SEC, MIN, HOUR, WEEK, DAY, MONTH and YEAR registers.
+///
+/// # Returns
+/// * `Option<NaiveDateTime>` - The date and time, or None if a field is not valid BCD or out of range.
+pub(crate) fn decode_time(raw: &[u8; 7]) -> Option<NaiveDateTime> {
+    decode_time_in(raw, 2000)
+}
+
+/// Decodes the SEC to YEAR registers read in one burst, with the two-digit year counted from `base`.
+///
+/// # Arguments
+/// * `raw` - The SEC, MIN, HOUR, WEEK, DAY, MONTH and YEAR registers.
+/// * `base` - The first year of the century held by the clock, e.g. 2000.
+///
+/// # Returns
+/// * `Option<NaiveDateTime>` - The date and time, or None if a field is not valid BCD or out of range.
+pub(crate) fn decode_time_in(raw: &[u8; 7], base: i32) -> Option<NaiveDateTime> {
+    let bcd = |data: u8, mask: u8| checked_from_bcd(data & mask).ok().map(u32::from);
+    NaiveDate::from_ymd_opt(base + bcd(raw[6], 0xFF)? as i32, bcd(raw[5], 0x1F)?, bcd(raw[4], 0x3F)?)?
+        .and_hms_opt(bcd(raw[2], 0x3F)?, bcd(raw[1], 0x7F)?, bcd(raw[0], 0x7F)?)
+}
+
+/// Decodes the SEC, MIN and HOUR registers read in one burst.
+///
+/// # Arguments
+/// * `raw` - The SEC, MIN and HOUR registers.
+///
+/// # Returns
+/// * `Option<NaiveTime>` - The time of day, or None if a field is not valid BCD or out of range.
+pub(crate) fn decode_time_of_day(raw: &[u8; 3]) -> Option<NaiveTime> {
+    let bcd = |data: u8, mask: u8| checked_from_bcd(data & mask).ok().map(u32::from);
+    NaiveTime::from_hms_opt(bcd(raw[2], 0x3F)?, bcd(raw[1], 0x7F)?, bcd(raw[0], 0x7F)?)
+}
+
+/// A duration accepted by the APIs that adjust the clock or program the timer.
+///
+/// Implemented for `chrono::Duration`, `core::time::Duration` and, with the `fugit` feature,
+/// `fugit::Duration`, so callers don't need to convert between duration types themselves.
+pub trait IntoRtcDuration {
+    /// Converts the value to a `chrono::Duration`, or returns None if it is out of range.
+    fn into_rtc_duration(self) -> Option<Duration>;
+}
+
+impl IntoRtcDuration for Duration {
+    fn into_rtc_duration(self) -> Option<Duration> {
+        Some(self)
+    }
+}
+
+impl IntoRtcDuration for core::time::Duration {
+    fn into_rtc_duration(self) -> Option<Duration> {
+        Duration::from_std(self).ok()
+    }
+}
+
+#[cfg(feature = "fugit")]
+impl<const NOM: u32, const DENOM: u32> IntoRtcDuration for fugit::Duration<u32, NOM, DENOM> {
+    fn into_rtc_duration(self) -> Option<Duration> {
+        from_ticks(self.ticks() as u64, NOM, DENOM)
+    }
+}
+
+#[cfg(feature = "fugit")]
+impl<const NOM: u32, const DENOM: u32> IntoRtcDuration for fugit::Duration<u64, NOM, DENOM> {
+    fn into_rtc_duration(self) -> Option<Duration> {
+        from_ticks(self.ticks(), NOM, DENOM)
+    }
+}
+
+/// Converts a number of ticks of `nom / denom` seconds to a `chrono::Duration`.
+#[cfg(feature = "fugit")]
+fn from_ticks(ticks: u64, nom: u32, denom: u32) -> Option<Duration> {
+    let nanos = ticks as u128 * nom as u128 * 1_000_000_000 / denom as u128;
+    Some(Duration::nanoseconds(i64::try_from(nanos).ok()?))
+}
@@ -0,0 +1,49 @@
+//! A backstop wake-up on the wake-up timer, armed before long sleeps in case the expected wake-up never comes.
+
+use chrono::Duration;
+
+use crate::{Error, IntoRtcDuration, Rx8900, Transport};
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Arms the wake-up timer and its interrupt as a deadman wake-up, firing if nothing else woke the MCU first.
+    ///
+    /// The deadman uses the wake-up timer, so the alarm stays free for the normal schedule and both can be armed
+    /// together: after a wake-up, TF set means the deadman fired and AF set means the alarm did. It cannot coexist
+    /// with the users of the timer (`start_countdown`, `every`, and the `Logger` or `align_to` with periods shorter
+    /// than a minute), which it reprograms. The timeout is rounded up to whole seconds, or to whole minutes above
+    /// 4095 seconds; the first count may be up to one second or minute short, since the timer starts on the next
+    /// clock update.
+    ///
+    /// # Arguments
+    /// * `timeout` - The time after which the MCU is woken up, as any `IntoRtcDuration`, at most 4095 minutes.
+    ///
+    /// # Returns
+    /// * `Result<Duration, Error<E>>` - The timeout actually armed, `Error::InvalidInput` if the timeout is not
+    ///   positive or longer than 4095 minutes, or an error if the operation fails.
+    pub fn arm_deadman(&mut self, timeout: impl IntoRtcDuration) -> Result<Duration, Error<E>> {
+        let timeout = timeout.into_rtc_duration().ok_or(Error::InvalidInput)?;
+        let ms = timeout.num_milliseconds();
+        let armed = match ms {
+            1..=4_095_000 => Duration::seconds((ms as u64).div_ceil(1000) as i64),
+            4_095_001..=245_700_000 => Duration::minutes((ms as u64).div_ceil(60_000) as i64),
+            _ => return Err(Error::InvalidInput),
+        };
+        self.start_countdown(armed)?;
+        self.set_tie(true)?;
+        Ok(armed)
+    }
+
+    /// Stops the deadman wake-up armed by `arm_deadman` and clears the timer flag.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the deadman was disarmed, or an error if the operation fails.
+    pub fn disarm_deadman(&mut self) -> Result<(), Error<E>> {
+        self.set_te(false)?;
+        self.set_tie(false)?;
+        // TF
+        self.clear_flags(0b00010000)
+    }
+}
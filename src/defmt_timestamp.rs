@@ -0,0 +1,68 @@
+//! A `defmt` timestamp source fed by the RX8900, so RTT logs carry wall-clock time.
+//!
+//! Enabling the `defmt-timestamp` feature installs `defmt::timestamp!` for the whole binary, printing the
+//! seconds since the Unix epoch. Formatting a log frame must not touch the bus, so the timestamp is a cached
+//! value: call `sync` once after `init`, then `on_update_interrupt` whenever the INT pin is asserted by the
+//! update interrupt (or simply poll it from the main loop). Frames logged before the first `sync` carry 0.
+//!
+//! ```ignore
+//! rx8900.init()?;
+//! rx8900.set_update_interrupt_enable(true)?;
+//! rx8900::defmt_timestamp::sync(&mut rx8900)?;
+//! loop {
+//!     rx8900::defmt_timestamp::on_update_interrupt(&mut rx8900)?;
+//!     defmt::info!("tick");
+//! }
+//! ```
+
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use crate::{Error, Rx8900, Transport};
+
+/// Seconds since the Unix epoch as of the last update.
+static EPOCH: AtomicU32 = AtomicU32::new(0);
+
+defmt::timestamp!("{=u32:ts}", epoch());
+
+/// Returns the cached number of seconds since the Unix epoch, as printed in the log frames.
+pub fn epoch() -> u32 {
+    EPOCH.load(Ordering::Relaxed)
+}
+
+/// Reads the clock and refreshes the cached timestamp.
+///
+/// # Arguments
+/// * `rx8900` - The RTC to read the time from.
+///
+/// # Returns
+/// * `Result<(), Error<E>>` - Ok if the timestamp was refreshed, `Error::InvalidTime` if the time registers hold no
+///   valid date and time, or an error if the read fails.
+pub fn sync<I2C, E>(rx8900: &mut Rx8900<I2C>) -> Result<(), Error<E>>
+where
+    I2C: Transport<Error = E>,
+{
+    // The RTC only holds 2000-2099, which always fits.
+    EPOCH.store(rx8900.timestamp()? as u32, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Checks the update flag of the RTC and, if it is set, clears it and refreshes the cached timestamp.
+///
+/// # Arguments
+/// * `rx8900` - The RTC generating the update interrupts.
+///
+/// # Returns
+/// * `Result<bool, Error<E>>` - True if the timestamp was refreshed, `Error::InvalidTime` if the time registers hold no
+///   valid date and time, or an error if the bus operation fails.
+pub fn on_update_interrupt<I2C, E>(rx8900: &mut Rx8900<I2C>) -> Result<bool, Error<E>>
+where
+    I2C: Transport<Error = E>,
+{
+    if !rx8900.uf()? {
+        return Ok(false);
+    }
+    // UF
+    rx8900.clear_flags(0b00100000)?;
+    sync(rx8900)?;
+    Ok(true)
+}
@@ -0,0 +1,86 @@
+//! Chip-independent clock traits, so application code can be tested against a fake clock.
+
+use chrono::NaiveDateTime;
+
+use crate::fallback::{FallbackClock, OptionalRtc};
+use crate::{Error, Rx8900, Transport};
+
+/// A real-time clock keeping UTC.
+///
+/// Implemented by `Rx8900` and `OptionalRtc`. Business logic written against this trait can run
+/// against a fake clock in tests and against another RTC chip later.
+pub trait RtcDevice {
+    /// The error reported by the clock.
+    type Error;
+
+    /// Returns the current date and time.
+    fn now(&mut self) -> Result<NaiveDateTime, Self::Error>;
+
+    /// Sets the current date and time.
+    fn set_now(&mut self, now: NaiveDateTime) -> Result<(), Self::Error>;
+}
+
+/// A real-time clock with an alarm able to wake up the system.
+pub trait RtcAlarm: RtcDevice {
+    /// Arms the alarm to fire at the given date and time.
+    ///
+    /// Clocks may round the time to their alarm resolution; the RX8900 ignores the seconds.
+    fn set_alarm(&mut self, at: NaiveDateTime) -> Result<(), Self::Error>;
+
+    /// Disarms the alarm and forgets whether it fired.
+    fn cancel_alarm(&mut self) -> Result<(), Self::Error>;
+
+    /// Returns whether the alarm fired since it was armed.
+    fn alarm_fired(&mut self) -> Result<bool, Self::Error>;
+}
+
+impl<I2C, E> RtcDevice for Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    type Error = Error<E>;
+
+    fn now(&mut self) -> Result<NaiveDateTime, Error<E>> {
+        self.datetime()
+    }
+
+    fn set_now(&mut self, now: NaiveDateTime) -> Result<(), Error<E>> {
+        self.set_datetime(now)
+    }
+}
+
+#[cfg(feature = "alarm")]
+impl<I2C, E> RtcAlarm for Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    fn set_alarm(&mut self, at: NaiveDateTime) -> Result<(), Error<E>> {
+        self.sleep_until(at)
+    }
+
+    fn cancel_alarm(&mut self) -> Result<(), Error<E>> {
+        self.set_aie(false)?;
+        // AF
+        self.clear_flags(0b00001000)
+    }
+
+    fn alarm_fired(&mut self) -> Result<bool, Error<E>> {
+        Ok(self.af()?)
+    }
+}
+
+impl<I2C, F, E> RtcDevice for OptionalRtc<I2C, F>
+where
+    I2C: Transport<Error = E>,
+    F: FallbackClock,
+{
+    type Error = Error<E>;
+
+    fn now(&mut self) -> Result<NaiveDateTime, Error<E>> {
+        Ok(OptionalRtc::now(self))
+    }
+
+    fn set_now(&mut self, now: NaiveDateTime) -> Result<(), Error<E>> {
+        OptionalRtc::set_now(self, now)
+    }
+}
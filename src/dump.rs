@@ -0,0 +1,140 @@
+//! An annotated snapshot of the register file, for support bundles and bug reports.
+
+use core::fmt;
+
+use crate::bcd::from_bcd;
+use crate::{RegisterTable, Rx8900, Transport};
+
+/// Register names and addresses shown by a `RegisterDump`, in display order.
+const ROWS: [(u8, &str); 18] = [
+    (0x00, "SEC"),
+    (0x01, "MIN"),
+    (0x02, "HOUR"),
+    (0x03, "WEEK"),
+    (0x04, "DAY"),
+    (0x05, "MONTH"),
+    (0x06, "YEAR"),
+    (0x07, "RAM"),
+    (0x08, "MIN Alarm"),
+    (0x09, "HOUR Alarm"),
+    (0x0A, "WEEK/DAY Alarm"),
+    (0x0B, "Timer Counter 0"),
+    (0x0C, "Timer Counter 1"),
+    (0x0D, "Extension"),
+    (0x0E, "Flag"),
+    (0x0F, "Control"),
+    (0x17, "TEMP"),
+    (0x18, "Backup Function"),
+];
+
+/// The contents of the registers 0x00-0x0F, TEMP and Backup Function, captured by `Rx8900::register_dump`.
+///
+/// The `Display` implementation prints one line per register with its address, name, raw value
+/// and decoded fields; with the `std` feature, `render` returns the same text as a `String`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegisterDump {
+    compatible: [u8; 16],
+    temp: u8,
+    backup_function: u8,
+}
+
+impl RegisterDump {
+    /// Returns the value of a captured register.
+    ///
+    /// # Arguments
+    /// * `address` - The register address; 0x00-0x0F, 0x17 or 0x18.
+    ///
+    /// # Returns
+    /// * `Option<u8>` - The captured value, or None if the register is not part of the dump.
+    pub fn register(&self, address: u8) -> Option<u8> {
+        match address {
+            0x00..=0x0F => Some(self.compatible[address as usize]),
+            0x17 => Some(self.temp),
+            0x18 => Some(self.backup_function),
+            _ => None,
+        }
+    }
+
+    /// Returns the annotated register table as text.
+    #[cfg(feature = "std")]
+    pub fn render(&self) -> std::string::String {
+        std::string::ToString::to_string(self)
+    }
+
+    /// Writes the decoded fields of a register.
+    fn fields(&self, f: &mut fmt::Formatter<'_>, address: u8, value: u8) -> fmt::Result {
+        let bit = |n: u8| (value >> n) & 1;
+        match address {
+            0x00 | 0x01 => write!(f, "{:02}", from_bcd(value & 0x7F)),
+            0x02 => write!(f, "{:02}", from_bcd(value & 0x3F)),
+            0x03 => {
+                const DAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+                let mut first = true;
+                for (n, day) in DAYS.iter().enumerate() {
+                    if value & (1 << n) != 0 {
+                        write!(f, "{}{}", if first { "" } else { "," }, day)?;
+                        first = false;
+                    }
+                }
+                Ok(())
+            }
+            0x04 => write!(f, "{:02}", from_bcd(value & 0x3F)),
+            0x05 => write!(f, "{:02}", from_bcd(value & 0x1F)),
+            0x06 => write!(f, "20{:02}", from_bcd(value)),
+            0x07 => write!(f, "{}", value),
+            0x08 => write!(f, "AE={} {:02}", bit(7), from_bcd(value & 0x7F)),
+            0x09 => write!(f, "AE={} {:02}", bit(7), from_bcd(value & 0x3F)),
+            0x0A => write!(f, "AE={} 0x{:02X}", bit(7), value & 0x7F),
+            0x0B => {
+                let counter = u16::from(self.compatible[0x0C] & 0x0F) << 8 | u16::from(value);
+                write!(f, "counter={}", counter)
+            }
+            0x0C => write!(f, "counter bits 11-8={}", value & 0x0F),
+            0x0D => write!(
+                f,
+                "TEST={} WADA={} USEL={} TE={} FSEL={} TSEL={}",
+                bit(7),
+                bit(6),
+                bit(5),
+                bit(4),
+                (value >> 2) & 0b11,
+                value & 0b11
+            ),
+            0x0E => write!(f, "UF={} TF={} AF={} VLF={} VDET={}", bit(5), bit(4), bit(3), bit(1), bit(0)),
+            0x0F => write!(f, "CSEL={} UIE={} TIE={} AIE={} RESET={}", value >> 6, bit(5), bit(4), bit(3), bit(0)),
+            0x17 => write!(f, "raw={}", value),
+            0x18 => write!(f, "VDETOFF={} SWOFF={} BKSMP={}", bit(3), bit(2), value & 0b11),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for RegisterDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Addr Register         Value Fields")?;
+        for (address, name) in ROWS {
+            let value = self.register(address).unwrap_or(0);
+            write!(f, "0x{:02X} {:<16} 0x{:02X}  ", address, name, value)?;
+            self.fields(f, address, value)?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Captures the registers 0x00-0x0F, TEMP and Backup Function for diagnostics.
+    ///
+    /// # Returns
+    /// * `Result<RegisterDump, E>` - The captured registers, or an error if the read fails.
+    pub fn register_dump(&mut self) -> Result<RegisterDump, E> {
+        let mut compatible = [0; 16];
+        self.read_registers(RegisterTable::CompatibleSEC, &mut compatible)?;
+        let mut extended = [0; 2];
+        self.read_registers(RegisterTable::ExtendedTEMP, &mut extended)?;
+        Ok(RegisterDump { compatible, temp: extended[0], backup_function: extended[1] })
+    }
+}
@@ -0,0 +1,111 @@
+//! A wake-up schedule derived from an energy budget, for battery-powered devices sleeping between alarms.
+
+use chrono::{Duration, NaiveDateTime, Timelike};
+
+use crate::{Error, Rx8900, Transport};
+
+/// Longest interval between two wake-ups, in minutes; the alarm matches the day of the month.
+const MAX_INTERVAL_MINUTES: u64 = 28 * 24 * 60;
+
+/// Schedules alarm wake-ups so that the MCU stays awake at most a given time per hour.
+///
+/// The interval between two wake-ups is the estimated awake time of one wake-up divided by the budget, rounded
+/// up to whole minutes (the resolution of the alarm) and limited to 1 minute - 28 days. The estimate starts
+/// from the value given to `new` and follows the awake times reported to `on_wake`, so the schedule stretches
+/// when wake-ups take longer than planned:
+///
+/// ```ignore
+/// let mut duty = DutyCycle::new(2_000, 150).unwrap(); // 2 s per hour, 150 ms per wake-up
+/// duty.start(&mut rx8900)?;
+/// loop {
+///     sleep_until_interrupt();
+///     let started = now_ms();
+///     do_work();
+///     duty.on_wake(&mut rx8900, now_ms() - started)?;
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DutyCycle {
+    budget_ms_per_hour: u32,
+    awake_ms: u32,
+    next: Option<NaiveDateTime>,
+}
+
+impl DutyCycle {
+    /// Creates a schedule.
+    ///
+    /// # Arguments
+    /// * `budget_ms_per_hour` - The time the MCU may stay awake per hour, in milliseconds.
+    /// * `awake_ms` - The expected awake time of one wake-up, in milliseconds.
+    ///
+    /// # Returns
+    /// * `Option<DutyCycle>` - The schedule, or None if the budget is 0.
+    pub fn new(budget_ms_per_hour: u32, awake_ms: u32) -> Option<Self> {
+        (budget_ms_per_hour > 0).then_some(Self { budget_ms_per_hour, awake_ms, next: None })
+    }
+
+    /// Returns the interval between two wake-ups for the current estimate of the awake time.
+    pub fn interval(&self) -> Duration {
+        let interval_ms = self.awake_ms as u64 * 3_600_000 / self.budget_ms_per_hour as u64;
+        let minutes = interval_ms.div_ceil(60_000).clamp(1, MAX_INTERVAL_MINUTES);
+        Duration::minutes(minutes as i64)
+    }
+
+    /// Returns the estimated awake time of one wake-up, in milliseconds.
+    pub fn awake_ms(&self) -> u32 {
+        self.awake_ms
+    }
+
+    /// Returns the time of the next wake-up, or None before `start`.
+    pub fn next_wake(&self) -> Option<NaiveDateTime> {
+        self.next
+    }
+
+    /// Arms the alarm for the first wake-up, one interval after the start of the current minute.
+    ///
+    /// # Arguments
+    /// * `rx8900` - The RTC waking the MCU up.
+    ///
+    /// # Returns
+    /// * `Result<NaiveDateTime, Error<E>>` - The time of the first wake-up, `Error::InvalidTime` if the time registers
+    ///   hold no valid date and time, or an error if the operation fails.
+    pub fn start<I2C, E>(&mut self, rx8900: &mut Rx8900<I2C>) -> Result<NaiveDateTime, Error<E>>
+    where
+        I2C: Transport<Error = E>,
+    {
+        self.arm(rx8900)
+    }
+
+    /// Records the awake time of a wake-up and arms the alarm for the next one.
+    ///
+    /// Call it once the work of a wake-up is done, right before going back to sleep. The estimate moves a quarter
+    /// of the way towards `awake_ms`, and the next wake-up is one interval after the start of the current minute.
+    ///
+    /// # Arguments
+    /// * `rx8900` - The RTC passed to `start`.
+    /// * `awake_ms` - The time the MCU stayed awake for this wake-up, in milliseconds.
+    ///
+    /// # Returns
+    /// * `Result<NaiveDateTime, Error<E>>` - The time of the next wake-up, `Error::InvalidTime` if the time registers
+    ///   hold no valid date and time, or an error if the operation fails.
+    pub fn on_wake<I2C, E>(&mut self, rx8900: &mut Rx8900<I2C>, awake_ms: u32) -> Result<NaiveDateTime, Error<E>>
+    where
+        I2C: Transport<Error = E>,
+    {
+        self.awake_ms = ((self.awake_ms as u64 * 3 + awake_ms as u64) / 4) as u32;
+        self.arm(rx8900)
+    }
+
+    /// Arms the alarm one interval after the start of the current minute.
+    fn arm<I2C, E>(&mut self, rx8900: &mut Rx8900<I2C>) -> Result<NaiveDateTime, Error<E>>
+    where
+        I2C: Transport<Error = E>,
+    {
+        let now = rx8900.datetime()?;
+        let minute = now.with_second(0).and_then(|minute| minute.with_nanosecond(0)).unwrap_or(now);
+        let next = minute + self.interval();
+        rx8900.sleep_until(next)?;
+        self.next = Some(next);
+        Ok(next)
+    }
+}
@@ -0,0 +1,41 @@
+//! A low-entropy seed derived from the clock, for chips without a TRNG.
+
+use crate::{RegisterTable, Rx8900, Transport};
+
+/// Mixes a 64-bit value with the SplitMix64 finalizer.
+fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Derives a 64-bit seed from the date and time, a sub-second phase and the temperature LSBs.
+    ///
+    /// The RX8900 cannot report where it is within the current second, so the phase comes from a free-running
+    /// counter of the MCU, such as the cycle counter or a SysTick value; its offset from the RTC second boundary
+    /// differs from boot to boot. The result holds only a few bits of entropy and is predictable by anyone who
+    /// knows the time: use it to seed hash maps or backoff jitter, never for cryptography.
+    ///
+    /// # Arguments
+    /// * `phase` - The current value of a free-running MCU counter.
+    ///
+    /// # Returns
+    /// * `Result<u64, E>` - The seed, or an error if the read fails.
+    pub fn entropy_seed(&mut self, phase: u32) -> Result<u64, E> {
+        let mut data = [0; 7];
+        self.read_registers(RegisterTable::CompatibleSEC, &mut data)?;
+        let seconds = data.iter().fold(0, |seed, &byte| seed << 8 | byte as u64);
+        let mut seed = mix(seconds) ^ mix(phase as u64);
+        #[cfg(feature = "temperature")]
+        {
+            seed = mix(seed ^ (self.temp()? & 0b00001111) as u64);
+        }
+        seed = mix(seed);
+        Ok(seed)
+    }
+}
@@ -0,0 +1,89 @@
+//! Errors reported by the driver.
+
+use core::convert::Infallible;
+use core::fmt;
+
+/// An error returned by the driver.
+///
+/// Methods that write the chip, validate their arguments or decode register contents return `Error<E>`, with
+/// `NotOwner` for every write of an observer and `InvalidInput` for arguments out of range instead of masking them.
+/// Plain register reads return the bus error `E` itself, which `?` converts into `Error<E>`.
+///
+/// New variants may be added in minor releases, so matches need a wildcard arm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error<E> {
+    /// The I2C bus reported an error.
+    Bus(E),
+    /// An argument is out of the range supported by the device.
+    InvalidInput,
+    /// The device returned register contents that cannot be decoded.
+    InvalidDeviceData,
+    /// The time registers hold no valid date and time, typically after the backup supply was lost. Carries the SEC,
+    /// MIN, HOUR, WEEK, DAY, MONTH and YEAR registers as read, so the application can log them and set the clock;
+    /// `datetime_fast` only reads the first three and leaves the others zero, and the date reads such as `ordinal`
+    /// only read the last three and leave the others zero.
+    InvalidTime([u8; 7]),
+    /// The clock is earlier than the required floor, typically because it was reset.
+    ClockTooEarly,
+    /// Another bus master owns the chip, so this one may only read it and every write is refused; see
+    /// `Rx8900::claim`.
+    NotOwner,
+    /// Handling the interrupts of a periodic mode would take too much of the bus time at the hinted frequency; see
+    /// `Rx8900::set_bus_frequency_hint`.
+    BusSaturated,
+}
+
+impl<E> From<E> for Error<E> {
+    fn from(error: E) -> Self {
+        Error::Bus(error)
+    }
+}
+
+impl<E> Error<E> {
+    /// Returns the bus error, for callers still using the plain bus error type of the older methods.
+    ///
+    /// # Returns
+    /// * `Option<E>` - The bus error, or None if the error was reported by the driver itself.
+    pub fn bus(self) -> Option<E> {
+        match self {
+            Error::Bus(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl<E: fmt::Debug> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Bus(error) => write!(f, "bus error: {:?}", error),
+            Error::InvalidInput => f.write_str("argument out of the range supported by the RX8900"),
+            Error::InvalidDeviceData => f.write_str("the RX8900 registers hold values that cannot be decoded"),
+            Error::InvalidTime(raw) => write!(f, "the RX8900 time registers hold no valid date and time: {:02X?}", raw),
+            Error::ClockTooEarly => f.write_str("the clock is earlier than the required floor"),
+            Error::NotOwner => f.write_str("another bus master owns the RX8900"),
+            Error::BusSaturated => f.write_str("the periodic interrupts would saturate the I2C bus"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Debug> std::error::Error for Error<E> {}
+
+/// Unwraps the results of buses that cannot fail without a panic path.
+///
+/// The driver never requires `E: Debug`, so every API works with `E = Infallible`; `unwrap_infallible` then takes
+/// the value out of the `Result` in a way the compiler proves cannot fail, where `unwrap` would keep a panic.
+pub trait UnwrapInfallible<T> {
+    /// Returns the value of a result whose error type cannot be constructed.
+    fn unwrap_infallible(self) -> T;
+}
+
+impl<T> UnwrapInfallible<T> for Result<T, Infallible> {
+    fn unwrap_infallible(self) -> T {
+        match self {
+            Ok(value) => value,
+            Err(error) => match error {},
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! A fixed-size ring buffer of timestamped interrupt events, for blackbox-style diagnostics.
+
+use chrono::NaiveDateTime;
+
+use crate::{Error, Event, RegisterTable, Rx8900, Transport};
+
+/// Keeps the last `N` events with the time they were seen, overwriting the oldest when full.
+///
+/// The log needs no allocator and `new` is const, so it can live in a `static` behind a critical-section mutex
+/// and be pushed to from an interrupt handler, then iterated and cleared when the entries are uploaded.
+#[derive(Clone, Debug)]
+pub struct EventLog<const N: usize> {
+    entries: [Option<(Event, NaiveDateTime)>; N],
+    next: usize,
+    overwritten: u32,
+}
+
+impl<const N: usize> Default for EventLog<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> EventLog<N> {
+    /// Creates an empty log.
+    pub const fn new() -> Self {
+        Self { entries: [None; N], next: 0, overwritten: 0 }
+    }
+
+    /// Records an event, overwriting the oldest entry if the log is full.
+    ///
+    /// # Arguments
+    /// * `event` - The event to record.
+    /// * `at` - The time the event was seen.
+    pub fn push(&mut self, event: Event, at: NaiveDateTime) {
+        if N == 0 {
+            self.overwritten = self.overwritten.saturating_add(1);
+            return;
+        }
+        if self.entries[self.next].replace((event, at)).is_some() {
+            self.overwritten = self.overwritten.saturating_add(1);
+        }
+        self.next = (self.next + 1) % N;
+    }
+
+    /// Returns the number of entries in the log.
+    pub fn len(&self) -> usize {
+        self.entries.iter().flatten().count()
+    }
+
+    /// Returns whether the log holds no entry.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of entries lost because the log was full since it was created or cleared.
+    pub fn overwritten(&self) -> u32 {
+        self.overwritten
+    }
+
+    /// Returns the entries from the oldest to the newest.
+    pub fn iter(&self) -> impl Iterator<Item = &(Event, NaiveDateTime)> {
+        let (newer, older) = self.entries.split_at(self.next);
+        older.iter().chain(newer).flatten()
+    }
+
+    /// Removes every entry and resets the count of lost entries, e.g. once they were uploaded.
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Reads and clears the interrupt flags, and records the sources that were set in a log with the current time.
+    ///
+    /// The time is read before the flags are cleared, so events whose time could not be read stay pending and are
+    /// recorded by the next call.
+    ///
+    /// # Arguments
+    /// * `log` - The log the events are recorded in.
+    ///
+    /// # Returns
+    /// * `Result<usize, Error<E>>` - The number of events recorded, or an error if a bus operation fails or the
+    ///   registers do not hold a valid date and time.
+    pub fn log_events<const N: usize>(&mut self, log: &mut EventLog<N>) -> Result<usize, Error<E>> {
+        // UF, TF and AF
+        let flags = self.read_register(RegisterTable::CompatibleFlagRegister)? & 0b00111000;
+        if flags == 0 {
+            return Ok(0);
+        }
+        let now = self.datetime()?;
+        self.clear_flags(flags)?;
+        let sources = [
+            #[cfg(feature = "alarm")]
+            (0b00001000, Event::Alarm),
+            #[cfg(feature = "timer")]
+            (0b00010000, Event::Timer),
+            (0b00100000, Event::Update),
+        ];
+        let mut count = 0;
+        for (_, event) in sources.into_iter().filter(|(flag, _)| flags & flag != 0) {
+            log.push(event, now);
+            count += 1;
+        }
+        Ok(count)
+    }
+}
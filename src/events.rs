@@ -0,0 +1,60 @@
+//! Polling of the flag register, with an optional repair of the configuration after a brown-out.
+
+use crate::{ConfigDelta, Error, InterruptEvents, RegisterTable, Rx8900, Transport};
+
+/// An interrupt source, as reported by `Rx8900::next_event` and recorded in an `EventLog`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Event {
+    /// The alarm fired (AF).
+    #[cfg(feature = "alarm")]
+    Alarm,
+    /// The countdown timer expired (TF).
+    #[cfg(feature = "timer")]
+    Timer,
+    /// The clock reached the next second or minute (UF).
+    Update,
+}
+
+/// The flags found set by `Rx8900::poll_events`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PolledEvents {
+    /// The interrupt sources (UF, TF, AF).
+    pub interrupts: InterruptEvents,
+    /// Voltage detect flag (VDET): the supply dropped low enough to stop the temperature compensation.
+    pub voltage_detect: bool,
+    /// The configuration bits repaired after VDET, all zero unless `Rx8900::set_reapply_on_vdet` was given a
+    /// configuration.
+    pub repaired: ConfigDelta,
+}
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Reads and clears the interrupt flags and the voltage detect flag.
+    ///
+    /// When VDET is set, the shadow cache is invalidated, and if a configuration was given to
+    /// `set_reapply_on_vdet`, it is compared with the registers of the chip and the differing registers are
+    /// rewritten, as with `diff_config`.
+    ///
+    /// # Returns
+    /// * `Result<PolledEvents, Error<E>>` - The flags that were set and have been cleared, with the repaired configuration
+    ///   bits, or an error if the operation fails.
+    pub fn poll_events(&mut self) -> Result<PolledEvents, Error<E>> {
+        // UF, TF, AF and VDET
+        let flags = self.read_register(RegisterTable::CompatibleFlagRegister)? & 0b00111001;
+        if flags != 0 {
+            self.clear_flags(flags)?;
+        }
+        let voltage_detect = flags & 0b00000001 != 0;
+        let mut repaired = ConfigDelta::default();
+        if voltage_detect {
+            self.shadow.invalidate();
+            if let Some(config) = self.vdet_config {
+                repaired = self.repair_config(&config)?;
+            }
+        }
+        Ok(PolledEvents { interrupts: InterruptEvents::from_flags(flags), voltage_detect, repaired })
+    }
+}
@@ -0,0 +1,152 @@
+//! Presence detection and graceful degradation for products where the RTC is optional.
+
+use chrono::NaiveDateTime;
+
+use crate::master::OWNER_MASK;
+use crate::{Error, RegisterTable, Rx8900, Transport};
+
+/// A clock used in place of the RX8900 when it is not populated or not responding.
+///
+/// Typically implemented on top of an MCU-internal RTC or a monotonic timer set from the network.
+pub trait FallbackClock {
+    /// Returns the current date and time.
+    fn now(&mut self) -> NaiveDateTime;
+
+    /// Sets the current date and time.
+    fn set_now(&mut self, now: NaiveDateTime);
+}
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Checks that an RX8900 answers at its address.
+    ///
+    /// The user RAM register is written with two test patterns and read back, then restored, and the registers
+    /// are checked as by `is_present`. The patterns only cover the lower six bits, so the ownership marker of
+    /// `claim` stays in place for other masters reading it meanwhile. The date and time are not touched.
+    ///
+    /// # Returns
+    /// * `Result<bool, Error<E>>` - True if the device behaves like an RX8900, false if it does not, or an error if a bus
+    ///   operation fails, which usually means nothing is populated.
+    pub fn probe(&mut self) -> Result<bool, Error<E>> {
+        let saved = self.read_register(RegisterTable::CompatibleRAM)?;
+        let mut ram_ok = true;
+        for pattern in [0b00011010, 0b00100101] {
+            let data = saved & OWNER_MASK | pattern;
+            self.write_register(RegisterTable::CompatibleRAM, data)?;
+            ram_ok &= self.read_register(RegisterTable::CompatibleRAM)? == data;
+        }
+        self.write_register(RegisterTable::CompatibleRAM, saved)?;
+        Ok(ram_ok && self.is_present()?)
+    }
+
+    /// Checks that an RX8900 answers at its address, without writing any register.
+    ///
+    /// The extended timer counter, extension and control registers are checked to mirror the compatible ones,
+    /// which another device answering at the same address is unlikely to do. Use it where `probe` may not write
+    /// the user RAM, e.g. from a read-only view or while another firmware owns the chip.
+    ///
+    /// # Returns
+    /// * `Result<bool, E>` - True if a device acknowledged and behaves like an RX8900, false if a device
+    ///   acknowledged but is something else, or an error if a bus operation fails, which usually means nothing is
+    ///   populated or the chip is dead.
+    pub fn is_present(&mut self) -> Result<bool, E> {
+        let mut compatible = [0; 5];
+        let mut extended = [0; 5];
+        self.read_registers(RegisterTable::CompatibleTimerCounter0, &mut compatible)?;
+        self.read_registers(RegisterTable::ExtendedTimerCounter0, &mut extended)?;
+        // The flag register (index 2) may change between the two reads.
+        Ok([0, 1, 3, 4].iter().all(|&index| compatible[index] == extended[index]))
+    }
+
+    /// Creates a driver if an RX8900 is present, returning the bus otherwise.
+    ///
+    /// Products with an optional RTC can keep the result as an `Option<Rx8900<I2C>>` via `.ok()`, or wrap it in
+    /// an `OptionalRtc` to fall back to another clock without checking for presence at each call.
+    ///
+    /// # Arguments
+    /// * `i2c` - The I2C bus the RX8900 may be connected to.
+    ///
+    /// # Returns
+    /// * `Result<Rx8900<I2C>, I2C>` - The driver, or the bus if `probe` did not find the device.
+    pub fn detect(i2c: I2C) -> Result<Self, I2C> {
+        let mut rx8900 = Self::new_unchecked(i2c);
+        match rx8900.probe() {
+            Ok(true) => Ok(rx8900),
+            _ => Err(rx8900.i2c),
+        }
+    }
+}
+
+/// An RX8900 that may be absent, backed by a `FallbackClock`.
+///
+/// Reads come from the RX8900 when it is present and answering, and from the fallback clock
+/// otherwise; writes go to both, so the fallback clock stays close to the RTC and takes over
+/// seamlessly.
+///
+/// ```ignore
+/// let mut clock = OptionalRtc::new(Rx8900::detect(i2c).ok(), mcu_rtc);
+/// let now = clock.now();
+/// ```
+pub struct OptionalRtc<I2C, F> {
+    rtc: Option<Rx8900<I2C>>,
+    fallback: F,
+}
+
+impl<I2C, F> OptionalRtc<I2C, F> {
+    /// Creates a clock from an optional RX8900 and a fallback clock.
+    ///
+    /// # Arguments
+    /// * `rtc` - The RX8900, or None if it is not populated.
+    /// * `fallback` - The clock used when the RX8900 is absent or not answering.
+    pub fn new(rtc: Option<Rx8900<I2C>>, fallback: F) -> Self {
+        Self { rtc, fallback }
+    }
+
+    /// Returns the RX8900, if present.
+    pub fn rtc(&mut self) -> Option<&mut Rx8900<I2C>> {
+        self.rtc.as_mut()
+    }
+
+    /// Returns the fallback clock.
+    pub fn fallback(&mut self) -> &mut F {
+        &mut self.fallback
+    }
+
+    /// Returns the optional RX8900 and the fallback clock.
+    pub fn into_inner(self) -> (Option<Rx8900<I2C>>, F) {
+        (self.rtc, self.fallback)
+    }
+}
+
+impl<I2C, F, E> OptionalRtc<I2C, F>
+where
+    I2C: Transport<Error = E>,
+    F: FallbackClock,
+{
+    /// Returns the current date and time from the RX8900, or from the fallback clock if the RX8900 is absent or
+    /// the read fails.
+    pub fn now(&mut self) -> NaiveDateTime {
+        self.rtc
+            .as_mut()
+            .and_then(|rtc| rtc.datetime().ok())
+            .unwrap_or_else(|| self.fallback.now())
+    }
+
+    /// Sets the date and time of the fallback clock and, if present, the RX8900.
+    ///
+    /// # Arguments
+    /// * `now` - The current date and time.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the clocks were set, `Error::InvalidInput` if the year is outside 2000-2099,
+    ///   or an error if writing the RX8900 fails; the fallback clock is set in any case.
+    pub fn set_now(&mut self, now: NaiveDateTime) -> Result<(), Error<E>> {
+        self.fallback.set_now(now);
+        match self.rtc.as_mut() {
+            Some(rtc) => rtc.set_datetime(now),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! Validated year, month and day values for the field-level getters and setters.
+//!
+//! The range is checked once when a value is constructed, so a value held by the application is always valid and
+//! the typed setters write it without checking it again.
+
+use crate::{bcd, Error, RegisterTable, Rx8900, Transport};
+
+macro_rules! field {
+    ($(#[$doc:meta])* $name:ident, $min:literal..=$max:literal) => {
+        $(#[$doc])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(u8);
+
+        impl $name {
+            /// The smallest valid value.
+            pub const MIN: Self = Self($min);
+            /// The largest valid value.
+            pub const MAX: Self = Self($max);
+
+            /// Checks a value and wraps it.
+            ///
+            /// # Arguments
+            #[doc = concat!("* `value` - The value (", stringify!($min), "-", stringify!($max), ").")]
+            ///
+            /// # Returns
+            #[doc = concat!("* `Option<", stringify!($name), ">` - The wrapped value, or None if it is out of range.")]
+            pub const fn new(value: u8) -> Option<Self> {
+                match value {
+                    $min..=$max => Some(Self(value)),
+                    _ => None,
+                }
+            }
+
+            /// Returns the wrapped value.
+            pub const fn get(self) -> u8 {
+                self.0
+            }
+        }
+
+        impl TryFrom<u8> for $name {
+            type Error = u8;
+
+            /// Checks a value, returning it back as the error if it is out of range.
+            fn try_from(value: u8) -> Result<Self, u8> {
+                Self::new(value).ok_or(value)
+            }
+        }
+
+        impl From<$name> for u8 {
+            fn from(value: $name) -> u8 {
+                value.0
+            }
+        }
+    };
+}
+
+field!(
+    /// A year of the century (0-99), as kept in the YEAR register.
+    Year, 0..=99
+);
+field!(
+    /// A month (1-12), as kept in the MONTH register.
+    Month, 1..=12
+);
+field!(
+    /// A day of the month (1-31), as kept in the DAY register. Whether the day exists in a given month is not
+    /// checked.
+    Day, 1..=31
+);
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Reads a register holding a BCD field and checks its range.
+    fn read_field<T>(&mut self, register: RegisterTable, mask: u8, new: fn(u8) -> Option<T>) -> Result<T, Error<E>> {
+        let data = self.read_register(register)?;
+        let value = bcd::checked_from_bcd(data & mask).map_err(|_| Error::InvalidDeviceData)?;
+        new(value).ok_or(Error::InvalidDeviceData)
+    }
+
+    /// Reads the year.
+    ///
+    /// # Returns
+    /// * `Result<Year, Error<E>>` - The year of the century, `Error::InvalidDeviceData` if the register does not hold
+    ///   a valid year, or an error if the read fails.
+    pub fn year_typed(&mut self) -> Result<Year, Error<E>> {
+        self.read_field(RegisterTable::CompatibleYEAR, 0b11111111, Year::new)
+    }
+
+    /// Reads the month.
+    ///
+    /// # Returns
+    /// * `Result<Month, Error<E>>` - The month, `Error::InvalidDeviceData` if the register does not hold a valid
+    ///   month, or an error if the read fails.
+    pub fn month_typed(&mut self) -> Result<Month, Error<E>> {
+        self.read_field(RegisterTable::CompatibleMONTH, 0b00011111, Month::new)
+    }
+
+    /// Reads the day of the month.
+    ///
+    /// # Returns
+    /// * `Result<Day, Error<E>>` - The day, `Error::InvalidDeviceData` if the register does not hold a valid day, or
+    ///   an error if the read fails.
+    pub fn day_typed(&mut self) -> Result<Day, Error<E>> {
+        self.read_field(RegisterTable::CompatibleDAY, 0b00111111, Day::new)
+    }
+
+    /// Sets the year.
+    ///
+    /// # Arguments
+    /// * `year` - The year of the century.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the year was successfully set, or an error if the write fails.
+    pub fn set_year_typed(&mut self, year: Year) -> Result<(), Error<E>> {
+        self.write_register(RegisterTable::CompatibleYEAR, bcd::to_bcd(year.get()))
+    }
+
+    /// Sets the month.
+    ///
+    /// # Arguments
+    /// * `month` - The month.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the month was successfully set, or an error if the write fails.
+    pub fn set_month_typed(&mut self, month: Month) -> Result<(), Error<E>> {
+        self.write_register(RegisterTable::CompatibleMONTH, bcd::to_bcd(month.get()))
+    }
+
+    /// Sets the day of the month.
+    ///
+    /// # Arguments
+    /// * `day` - The day of the month.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the day was successfully set, or an error if the write fails.
+    pub fn set_day_typed(&mut self, day: Day) -> Result<(), Error<E>> {
+        self.write_register(RegisterTable::CompatibleDAY, bcd::to_bcd(day.get()))
+    }
+}
@@ -0,0 +1,57 @@
+//! A small record of the most recent time adjustments, for diagnosing clock jumps in the field.
+
+use chrono::NaiveDateTime;
+
+/// Number of adjustments kept by `Rx8900::adjustment_history`.
+pub const ADJUSTMENT_HISTORY_LEN: usize = 4;
+
+/// Where a time adjustment came from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AdjustmentSource {
+    /// Set by the application, e.g. `set_timestamp` or `adjust_seconds`.
+    Manual,
+    /// Synchronized with `sync_from_ntp`.
+    Ntp,
+    /// Synchronized from a GNSS receiver.
+    Gps,
+    /// Any other time source.
+    Other,
+}
+
+/// A time adjustment applied to the clock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Adjustment {
+    /// The time read just before the adjustment.
+    pub old: NaiveDateTime,
+    /// The time written by the adjustment.
+    pub new: NaiveDateTime,
+    /// Where the adjustment came from.
+    pub source: AdjustmentSource,
+}
+
+/// A ring buffer of the last `ADJUSTMENT_HISTORY_LEN` adjustments.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct History {
+    entries: [Option<Adjustment>; ADJUSTMENT_HISTORY_LEN],
+    next: usize,
+}
+
+impl History {
+    /// Records an adjustment, overwriting the oldest one when the buffer is full.
+    pub(crate) fn record(&mut self, adjustment: Adjustment) {
+        self.entries[self.next] = Some(adjustment);
+        self.next = (self.next + 1) % ADJUSTMENT_HISTORY_LEN;
+    }
+
+    /// Drops all recorded adjustments.
+    pub(crate) fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Returns the recorded adjustments, most recent first.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = Adjustment> + '_ {
+        (1..=ADJUSTMENT_HISTORY_LEN)
+            .map(move |age| (self.next + ADJUSTMENT_HISTORY_LEN - age) % ADJUSTMENT_HISTORY_LEN)
+            .map_while(move |index| self.entries[index])
+    }
+}
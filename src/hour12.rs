@@ -0,0 +1,84 @@
+//! 12-hour clock conversions for display; the RX8900 itself only counts in 24-hour mode.
+
+use crate::{bcd, Error, RegisterTable, Rx8900, Transport};
+
+/// The half of the day on a 12-hour clock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AmPm {
+    /// Midnight to 11:59 (12 AM to 11:59 AM).
+    Am,
+    /// Noon to 23:59 (12 PM to 11:59 PM).
+    Pm,
+}
+
+impl AmPm {
+    /// Converts a 24-hour clock hour into a 12-hour clock hour.
+    ///
+    /// # Arguments
+    /// * `hour` - The hour (0-23).
+    ///
+    /// # Returns
+    /// * `Option<(u8, AmPm)>` - The hour (1-12) with AM or PM, 0 being 12 AM and 12 being 12 PM, or None if the
+    ///   hour is above 23.
+    pub const fn from_24h(hour: u8) -> Option<(u8, AmPm)> {
+        match hour {
+            0 => Some((12, AmPm::Am)),
+            1..=11 => Some((hour, AmPm::Am)),
+            12 => Some((12, AmPm::Pm)),
+            13..=23 => Some((hour - 12, AmPm::Pm)),
+            _ => None,
+        }
+    }
+
+    /// Converts a 12-hour clock hour into a 24-hour clock hour.
+    ///
+    /// # Arguments
+    /// * `hour` - The hour (1-12).
+    /// * `am_pm` - AM or PM.
+    ///
+    /// # Returns
+    /// * `Option<u8>` - The hour (0-23), or None if the hour is not within 1-12.
+    pub const fn to_24h(hour: u8, am_pm: AmPm) -> Option<u8> {
+        match (hour, am_pm) {
+            (12, AmPm::Am) => Some(0),
+            (1..=11, AmPm::Am) => Some(hour),
+            (12, AmPm::Pm) => Some(12),
+            (1..=11, AmPm::Pm) => Some(hour + 12),
+            _ => None,
+        }
+    }
+}
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Returns the current hour on a 12-hour clock.
+    ///
+    /// # Returns
+    /// * `Result<(u8, AmPm), Error<E>>` - The hour (1-12) with AM or PM, `Error::InvalidDeviceData` if the hour
+    ///   register is above 23, or an error if the read fails.
+    pub fn hour12(&mut self) -> Result<(u8, AmPm), Error<E>> {
+        AmPm::from_24h(self.hour()?).ok_or(Error::InvalidDeviceData)
+    }
+
+    /// Sets the time of day from a 12-hour clock, leaving the date unchanged.
+    ///
+    /// # Arguments
+    /// * `hour` - The hour (1-12).
+    /// * `am_pm` - AM or PM.
+    /// * `minute` - The minute (0-59).
+    /// * `second` - The second (0-59).
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the time was set, `Error::InvalidInput` if a field is out of range, or an
+    ///   error if the write fails.
+    pub fn set_time_12h(&mut self, hour: u8, am_pm: AmPm, minute: u8, second: u8) -> Result<(), Error<E>> {
+        let hour = AmPm::to_24h(hour, am_pm).ok_or(Error::InvalidInput)?;
+        if minute > 59 || second > 59 {
+            return Err(Error::InvalidInput);
+        }
+        let data = [bcd::to_bcd(second), bcd::to_bcd(minute), bcd::to_bcd(hour)];
+        self.write_registers(RegisterTable::CompatibleSEC, &data)
+    }
+}
@@ -0,0 +1,189 @@
+//! Blocking iterators driven by the RX8900.
+
+#[cfg(feature = "timer")]
+use chrono::Duration;
+use chrono::NaiveDateTime;
+use embedded_hal::blocking::delay::DelayMs;
+
+use crate::{Error, Rx8900, Transport};
+
+/// Interval between two polls of the update flag, in milliseconds.
+const POLL_INTERVAL_MS: u32 = 10;
+
+/// An iterator yielding the current date and time once per second.
+///
+/// Created by `Rx8900::seconds_iter`. Each call to `next` blocks until the update flag signals the
+/// next second, clears it, and reads the date and time. The iterator never ends.
+pub struct SecondsIter<'a, I2C, D> {
+    rx8900: &'a mut Rx8900<I2C>,
+    delay: &'a mut D,
+}
+
+impl<'a, I2C, D> SecondsIter<'a, I2C, D> {
+    pub(crate) fn new(rx8900: &'a mut Rx8900<I2C>, delay: &'a mut D) -> Self {
+        Self { rx8900, delay }
+    }
+}
+
+impl<'a, I2C, D, E> SecondsIter<'a, I2C, D>
+where
+    I2C: Transport<Error = E>,
+    D: DelayMs<u32>,
+{
+    /// Waits for the update flag, clears it and reads the date and time.
+    fn wait_next(&mut self) -> Result<NaiveDateTime, Error<E>> {
+        while !self.rx8900.uf()? {
+            self.delay.delay_ms(POLL_INTERVAL_MS);
+        }
+        // UF
+        self.rx8900.clear_flags(0b00100000)?;
+        self.rx8900.datetime()
+    }
+}
+
+impl<'a, I2C, D, E> Iterator for SecondsIter<'a, I2C, D>
+where
+    I2C: Transport<Error = E>,
+    D: DelayMs<u32>,
+{
+    type Item = Result<NaiveDateTime, Error<E>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.wait_next())
+    }
+}
+
+/// An iterator yielding timestamps spaced by a fixed interval, paced by the wake-up timer.
+///
+/// Created by `Rx8900::every`. Each call to `next` blocks until the timer flag is raised, clears
+/// it, and yields the next timestamp. The iterator never ends; the timer keeps running after it is
+/// dropped until it is disabled with `Rx8900::reset_timer_enable`.
+#[cfg(feature = "timer")]
+pub struct Every<'a, I2C, D> {
+    rx8900: &'a mut Rx8900<I2C>,
+    delay: &'a mut D,
+    next: NaiveDateTime,
+    interval: Duration,
+    poll_interval_ms: u32,
+    clock: Option<fn() -> u32>,
+    last_instant: Option<u32>,
+    jitter_count: u32,
+    jitter_max: u32,
+    jitter_total: u64,
+}
+
+/// Deviation of the measured intervals of an `Every` iterator from the nominal one, in microseconds.
+#[cfg(feature = "timer")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct JitterStats {
+    /// Number of measured intervals.
+    pub count: u32,
+    /// Largest deviation measured.
+    pub max: u32,
+    /// Average deviation, rounded down.
+    pub average: u32,
+}
+
+#[cfg(feature = "timer")]
+impl<'a, I2C, D> Every<'a, I2C, D> {
+    pub(crate) fn new(rx8900: &'a mut Rx8900<I2C>, delay: &'a mut D, start: NaiveDateTime, interval: Duration) -> Self {
+        // Poll often enough not to miss an expiry of short intervals.
+        let poll_interval_ms = (interval.num_milliseconds() / 4).clamp(1, POLL_INTERVAL_MS as i64) as u32;
+        Self {
+            rx8900,
+            delay,
+            next: start,
+            interval,
+            poll_interval_ms,
+            clock: None,
+            last_instant: None,
+            jitter_count: 0,
+            jitter_max: 0,
+            jitter_total: 0,
+        }
+    }
+
+    /// Timestamps each expiry with a microsecond clock of the MCU and measures the jitter of the intervals.
+    ///
+    /// The flag is then polled every millisecond, which bounds the resolution of the measurement; the deviations
+    /// include the polling delay and the bus transactions, as seen by a control loop driven by the iterator.
+    ///
+    /// # Arguments
+    /// * `clock` - Returns a free-running microsecond counter, e.g. derived from a hardware timer. Wrapping is
+    ///   handled, but the interval must be shorter than the wrapping period, about 71 minutes.
+    pub fn with_jitter_clock(mut self, clock: fn() -> u32) -> Self {
+        self.clock = Some(clock);
+        self.poll_interval_ms = 1;
+        self
+    }
+
+    /// Returns the jitter measured since the iterator was created or the statistics were reset.
+    ///
+    /// # Returns
+    /// * `JitterStats` - The statistics, all zero without `with_jitter_clock`.
+    pub fn jitter(&self) -> JitterStats {
+        JitterStats {
+            count: self.jitter_count,
+            max: self.jitter_max,
+            average: self.jitter_total.checked_div(u64::from(self.jitter_count)).unwrap_or(0) as u32,
+        }
+    }
+
+    /// Clears the jitter measured so far.
+    pub fn reset_jitter(&mut self) {
+        self.jitter_count = 0;
+        self.jitter_max = 0;
+        self.jitter_total = 0;
+    }
+
+    /// Returns the value of the clock given to `with_jitter_clock` when the last expiry was seen.
+    pub fn last_instant(&self) -> Option<u32> {
+        self.last_instant
+    }
+
+    /// Records the instant of an expiry and the deviation of the interval since the previous one.
+    fn record(&mut self, instant: u32) {
+        if let (Some(last), Some(nominal)) = (self.last_instant, self.interval.num_microseconds()) {
+            let deviation = (i64::from(instant.wrapping_sub(last)) - nominal).unsigned_abs();
+            let deviation = u32::try_from(deviation).unwrap_or(u32::MAX);
+            self.jitter_count = self.jitter_count.saturating_add(1);
+            self.jitter_max = self.jitter_max.max(deviation);
+            self.jitter_total = self.jitter_total.saturating_add(u64::from(deviation));
+        }
+        self.last_instant = Some(instant);
+    }
+}
+
+#[cfg(feature = "timer")]
+impl<'a, I2C, D, E> Every<'a, I2C, D>
+where
+    I2C: Transport<Error = E>,
+    D: DelayMs<u32>,
+{
+    /// Waits for the timer flag, clears it and advances the timestamp.
+    fn wait_next(&mut self) -> Result<NaiveDateTime, Error<E>> {
+        while !self.rx8900.tf()? {
+            self.delay.delay_ms(self.poll_interval_ms);
+        }
+        if let Some(clock) = self.clock {
+            self.record(clock());
+        }
+        // TF
+        self.rx8900.clear_flags(0b00010000)?;
+        self.next += self.interval;
+        Ok(self.next)
+    }
+}
+
+#[cfg(feature = "timer")]
+impl<'a, I2C, D, E> Iterator for Every<'a, I2C, D>
+where
+    I2C: Transport<Error = E>,
+    D: DelayMs<u32>,
+{
+    type Item = Result<NaiveDateTime, Error<E>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.wait_next())
+    }
+}
@@ -0,0 +1,57 @@
+//! Optional leap second handling for the Unix timestamp APIs.
+
+/// Decides whether `timestamp` and `set_timestamp` count leap seconds.
+///
+/// The RTC itself keeps civil UTC and cannot represent 23:59:60. POSIX timestamps ignore leap
+/// seconds, which is what most applications want and the default. Applications that need a
+/// continuous count (power metering, telecom) can supply the announced leap seconds, and the
+/// timestamps then include every leap second inserted since the epoch; add the TAI-UTC offset
+/// of 1970 (10 s) to obtain TAI.
+///
+/// `sync_from_ntp` is not affected since NTP timestamps do not count leap seconds either.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum LeapSeconds {
+    /// Timestamps are POSIX timestamps; this is the default.
+    #[default]
+    Ignore,
+    /// POSIX timestamps at which a positive leap second has been inserted, sorted in ascending order.
+    ///
+    /// Each entry is the first second after the leap second, i.e. 00:00:00 of the following day.
+    Table(&'static [i64]),
+    /// Returns the number of leap seconds inserted before a POSIX timestamp; negative leap seconds are
+    /// reported as a decreasing count.
+    Callback(fn(i64) -> i64),
+}
+
+impl LeapSeconds {
+    /// Returns the number of leap seconds inserted before a POSIX timestamp.
+    ///
+    /// # Arguments
+    /// * `posix` - Seconds since the Unix epoch, not counting leap seconds.
+    pub fn offset(&self, posix: i64) -> i64 {
+        match self {
+            LeapSeconds::Ignore => 0,
+            LeapSeconds::Table(table) => table.partition_point(|&inserted| inserted <= posix) as i64,
+            LeapSeconds::Callback(callback) => callback(posix),
+        }
+    }
+
+    /// Converts a POSIX timestamp to a timestamp counting leap seconds.
+    ///
+    /// # Arguments
+    /// * `posix` - Seconds since the Unix epoch, not counting leap seconds.
+    pub fn to_counting(&self, posix: i64) -> i64 {
+        posix + self.offset(posix)
+    }
+
+    /// Converts a timestamp counting leap seconds to a POSIX timestamp.
+    ///
+    /// A leap second itself has no POSIX representation and maps to the first second of the next day.
+    ///
+    /// # Arguments
+    /// * `seconds` - Seconds since the Unix epoch, counting leap seconds.
+    pub fn to_posix(&self, seconds: i64) -> i64 {
+        let estimate = seconds - self.offset(seconds);
+        seconds - self.offset(estimate)
+    }
+}
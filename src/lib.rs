@@ -1,11 +1,164 @@
-#![no_std]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![deny(clippy::float_arithmetic)]
 //! A `no_std` compatible driver for the RX8900 real-time clock (RTC) chip, intended for use in embedded systems where no standard library is available.
 
+#[cfg(feature = "alarm")]
 use heapless::Vec; // Provides a fixed-size vector data structure.
-use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Weekday}; // Provides date and time utility types.
-use embedded_hal::blocking::i2c::{Read, Write, WriteRead}; // Traits for blocking I2C communication.
+use chrono::{DateTime, Datelike, IsoWeek, NaiveDate, NaiveDateTime, Timelike, Utc, Weekday}; // Provides date and time utility types.
+use chrono::Duration; // Signed time spans.
+use core::marker::PhantomData; // Zero-sized initialization state of the driver.
+use core::ops::RangeInclusive; // Valid ranges of the BCD register fields.
+use embedded_hal::blocking::delay::DelayMs; // Trait for blocking delays.
 
 const RX8900_ADDR: u8 = 0x32; // I2C address for the RX8900 RTC device.
+const FLAG_BITS: u8 = 0b00111011; // UF, TF, AF, VLF and VDET; writing 1 leaves a flag unchanged, writing 0 clears it.
+const MAX_WRITE_LEN: usize = 16; // Registers per `Transport::write_registers` call.
+const COHERENT_READ_ATTEMPTS: u8 = 4; // Reads of the time registers by `datetime_coherent` before giving up.
+
+#[macro_use]
+mod access;
+#[cfg(feature = "alarm")]
+mod alarm;
+#[cfg(all(feature = "alarm", feature = "timer"))]
+mod align;
+#[cfg(feature = "async")]
+mod asynch;
+mod backup;
+mod buffers;
+#[cfg(feature = "build-time")]
+mod build_time;
+mod century;
+mod change;
+#[cfg(feature = "menu")]
+pub mod cli;
+#[cfg(any(feature = "console", feature = "menu"))]
+mod commands;
+mod config;
+#[cfg(feature = "console")]
+pub mod console;
+mod cross_check;
+mod datetime;
+#[cfg(feature = "timer")]
+mod deadman;
+mod device;
+mod dump;
+#[cfg(feature = "alarm")]
+mod duty;
+mod entropy;
+mod error;
+mod event_log;
+mod events;
+mod fallback;
+mod fields;
+mod history;
+mod hour12;
+mod iter;
+mod leap;
+#[cfg(all(feature = "alarm", feature = "timer"))]
+mod logger;
+#[cfg(feature = "timer")]
+mod long_countdown;
+mod master;
+mod monotonic;
+#[cfg(feature = "objects")]
+mod objects;
+mod pending;
+#[cfg(feature = "timer")]
+mod periodic;
+mod pins;
+mod quality;
+mod read_only;
+mod redundant;
+mod rollover;
+mod scheduler;
+mod shadow;
+mod slow_bus;
+mod snapshot;
+#[cfg(feature = "alarm")]
+mod solar;
+mod state;
+mod stop_start;
+mod stress;
+mod time_set;
+mod timing;
+mod transport;
+mod typestate;
+mod wire;
+mod zone;
+#[cfg(feature = "alarm")]
+pub use alarm::{AlarmConfig, AlarmDay, ArmedAlarm};
+#[cfg(feature = "async")]
+pub use asynch::Rx8900Async;
+pub use backup::BackupPowerProfile;
+#[cfg(feature = "build-time")]
+pub use build_time::build_time;
+pub use century::Century;
+pub use change::{ChangeDetector, Changes};
+pub use config::{Config, ConfigDelta, InitConfig};
+pub use cross_check::{CrossCheck, Divergence};
+pub use datetime::{DecodeMode, DecodedTime, IntoNaiveUtc, IntoRtcDuration};
+pub use device::{RtcAlarm, RtcDevice};
+pub use dump::RegisterDump;
+#[cfg(feature = "alarm")]
+pub use duty::DutyCycle;
+pub use error::{Error, UnwrapInfallible};
+pub use event_log::EventLog;
+pub use events::{Event, PolledEvents};
+pub use fallback::{FallbackClock, OptionalRtc};
+pub use fields::{Day, Month, Year};
+pub use history::{Adjustment, AdjustmentSource, ADJUSTMENT_HISTORY_LEN};
+pub use hour12::AmPm;
+#[cfg(feature = "timer")]
+pub use iter::{Every, JitterStats};
+pub use iter::SecondsIter;
+pub use leap::LeapSeconds;
+#[cfg(feature = "timer")]
+pub use long_countdown::{CountdownStorage, LongCountdown};
+pub use master::Role;
+#[cfg(all(feature = "alarm", feature = "timer"))]
+pub use logger::Logger;
+#[cfg(feature = "objects")]
+pub use objects::RtcObject;
+pub use pending::{InterruptEvents, LatencyStats, PendingInterrupt};
+#[cfg(feature = "timer")]
+pub use periodic::{PeriodicStats, PeriodicTimer};
+pub use pins::{ClkoutConsumer, FoePin, IntPin, TiedHigh};
+pub use quality::TimeQuality;
+pub use read_only::Rx8900ReadOnly;
+pub use redundant::{Health, RedundantRtc, Side};
+pub use rollover::Rollover;
+pub use scheduler::{TaskId, TickScheduler};
+pub use slow_bus::{SlowBus, DEFAULT_BUS_FREE_TIME_US};
+pub use snapshot::Snapshot;
+#[cfg(feature = "alarm")]
+pub use solar::{SolarCalculator, SolarEvent, SolarRule, SolarSchedule, SunTimes};
+pub use state::StateBlob;
+pub use stop_start::StopStart;
+pub use stress::StressReport;
+pub use time_set::TimeSetPolicy;
+pub use timing::{BusOperation, DEFAULT_BUS_FREQUENCY_HZ};
+pub use transport::Transport;
+#[cfg(feature = "async")]
+pub use transport::AsyncTransport;
+pub use typestate::{Initialized, Ready, TimeInvalid, TimeNotSet, Uninitialized};
+pub use weekday::WeekStart;
+pub use wire::TimeMessage;
+pub use zone::NamedOffset;
+use history::History;
+use monotonic::Monotonic;
+use shadow::Shadow;
+
+pub mod bcd;
+#[cfg(feature = "defmt-timestamp")]
+pub mod defmt_timestamp;
+#[cfg(feature = "modbus")]
+pub mod modbus;
+pub mod prelude;
+#[cfg(feature = "sim")]
+pub mod sim;
+#[cfg(feature = "testsupport")]
+pub mod testsupport;
+pub mod weekday;
 
 /// Defines the register map for the RX8900 RTC device. Includes both standard and extended registers.
 #[allow(dead_code)]
@@ -47,7 +200,9 @@ enum RegisterTable {
 }
 
 /// Represents the possible clock sources for triggering events.
+#[cfg(feature = "timer")]
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SourceClock {
     SourceClock4096Hz = 0b00,
     SourceClock64Hz = 0b01,
@@ -55,8 +210,59 @@ pub enum SourceClock {
     SourceClockMinute = 0b11,
 }
 
+#[cfg(feature = "timer")]
+impl SourceClock {
+    /// Returns the frequency of the source clock as ticks per number of seconds.
+    fn frequency(self) -> (i64, i64) {
+        match self {
+            SourceClock::SourceClock4096Hz => (4096, 1),
+            SourceClock::SourceClock64Hz => (64, 1),
+            SourceClock::SourceClockSecond => (1, 1),
+            SourceClock::SourceClockMinute => (1, 60),
+        }
+    }
+
+    /// Finds the source clock and 12-bit counter value that count down exactly the given duration.
+    ///
+    /// The minute and second sources are preferred since they are synchronized with the clock update.
+    fn countdown(duration: Duration) -> Option<(SourceClock, u16)> {
+        let ns = duration.num_nanoseconds()?;
+        [
+            SourceClock::SourceClockMinute,
+            SourceClock::SourceClockSecond,
+            SourceClock::SourceClock64Hz,
+            SourceClock::SourceClock4096Hz,
+        ]
+        .into_iter()
+        .find_map(|source| {
+            let (ticks, seconds) = source.frequency();
+            let numerator = ns.checked_mul(ticks)?;
+            let denominator = 1_000_000_000 * seconds;
+            let count = numerator / denominator;
+            (numerator % denominator == 0 && (1..=4095).contains(&count)).then_some((source, count as u16))
+        })
+    }
+}
+
+/// A status flag of the flag register, as cleared by `Rx8900::clear_flag`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Flag {
+    /// Update flag (UF).
+    Update = 0b00100000,
+    /// Timer flag (TF).
+    Timer = 0b00010000,
+    /// Alarm flag (AF).
+    Alarm = 0b00001000,
+    /// Voltage low flag (VLF).
+    VoltageLow = 0b00000010,
+    /// Voltage detect flag (VDET).
+    VoltageDetect = 0b00000001,
+}
+
 /// Represents different types of alarms that can be configured.
+#[cfg(feature = "alarm")]
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AlarmType {
     WeekAlarm = 0b00,
     DayAlarm = 0b01,
@@ -64,6 +270,7 @@ pub enum AlarmType {
 
 /// Represents the frequency at which update interrupts are generated.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UpdateInterruptType {
     EverySecond = 0b00,
     EveryMinute = 0b01,
@@ -71,6 +278,7 @@ pub enum UpdateInterruptType {
 
 /// Represents the frequency of the fout (frequency out) pin output.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FoutFrequency {
     FoutFrequency32_768kHz = 0b00,
     FoutFrequency1024Hz = 0b01,
@@ -80,6 +288,7 @@ pub enum FoutFrequency {
 
 /// Represents the intervals at which temperature compensation is applied.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CompensationIntervalType {
     CompensationInterval0_5s = 0b00,
     CompensationInterval2_0s = 0b01,
@@ -87,9 +296,67 @@ pub enum CompensationIntervalType {
     CompensationInterval30s = 0b11,
 }
 
+impl CompensationIntervalType {
+    /// Returns the interval between two temperature compensations.
+    pub fn as_duration(self) -> Duration {
+        match self {
+            CompensationIntervalType::CompensationInterval0_5s => Duration::milliseconds(500),
+            CompensationIntervalType::CompensationInterval2_0s => Duration::seconds(2),
+            CompensationIntervalType::CompensationInterval10s => Duration::seconds(10),
+            CompensationIntervalType::CompensationInterval30s => Duration::seconds(30),
+        }
+    }
+
+    /// Returns the supported compensation interval closest to the given duration.
+    ///
+    /// # Arguments
+    /// * `duration` - The requested interval; ties are resolved towards the shorter interval.
+    ///
+    /// # Returns
+    /// * `Option<CompensationIntervalType>` - The closest interval, or None if the distance to an interval
+    ///   overflows `Duration`.
+    pub fn from_duration(duration: Duration) -> Option<Self> {
+        [
+            CompensationIntervalType::CompensationInterval0_5s,
+            CompensationIntervalType::CompensationInterval2_0s,
+            CompensationIntervalType::CompensationInterval10s,
+            CompensationIntervalType::CompensationInterval30s,
+        ]
+        .into_iter()
+        .try_fold(None, |nearest: Option<(Duration, Self)>, interval| {
+            let distance = interval.as_duration().checked_sub(&duration)?.abs();
+            Some(match nearest {
+                Some(nearest) if nearest.0 <= distance => Some(nearest),
+                _ => Some((distance, interval)),
+            })
+        })?
+        .map(|(_, interval)| interval)
+    }
+}
+
 /// A struct representing the RX8900 RTC device interfaced over I2C.
-pub struct Rx8900<I2C> {
+///
+/// `S` is the initialization state. Drivers created with `uninitialized` only become `Ready`, and gain their getters,
+/// through `init`, and through `set_datetime` if the time was lost; `new_unchecked` skips the check.
+pub struct Rx8900<I2C, S = Ready> {
     i2c: I2C,
+    shadow: Shadow,
+    time_set_policy: TimeSetPolicy,
+    leap_seconds: LeapSeconds,
+    monotonic: Monotonic,
+    history: History,
+    quality: TimeQuality,
+    vdet_config: Option<Config>,
+    decode_mode: DecodeMode,
+    week_start: WeekStart,
+    role: Role,
+    burst_limit: Option<u8>,
+    bus_frequency_hz: u32,
+    #[cfg(feature = "alarm")]
+    min_alarm_interval: Duration,
+    #[cfg(feature = "alarm")]
+    last_alarm: Option<NaiveDateTime>,
+    state: PhantomData<S>,
 }
 
 impl<I2C> Rx8900<I2C> {
@@ -97,139 +364,383 @@ impl<I2C> Rx8900<I2C> {
     ///
     /// # Arguments
     /// * `i2c` - An instance of the I2C peripheral to communicate with the RX8900.
+    #[deprecated(
+        since = "0.2.0",
+        note = "use `Rx8900::uninitialized(i2c).init()`, or `new_unchecked` if the chip is known to be initialized"
+    )]
     pub fn new(i2c: I2C) -> Self {
-        Self { i2c, }
+        Self::new_unchecked(i2c)
     }
 
-    /// Converts a BCD-encoded byte to a regular decimal byte.
-    fn from_bcd(data: u8) -> u8 {
-        (data >> 4) * 10 + (data & 0x0F)
+    /// Creates a `Ready` driver without checking the chip, e.g. after a wake-up from a low-power state when an
+    /// earlier boot initialized it and set the time.
+    ///
+    /// Nothing guarantees that the time registers hold a valid time; use `uninitialized` on a cold boot.
+    ///
+    /// # Arguments
+    /// * `i2c` - An instance of the I2C peripheral to communicate with the RX8900.
+    pub fn new_unchecked(i2c: I2C) -> Self {
+        Self {
+            i2c,
+            shadow: Shadow::default(),
+            time_set_policy: TimeSetPolicy::default(),
+            leap_seconds: LeapSeconds::default(),
+            monotonic: Monotonic::default(),
+            history: History::default(),
+            quality: TimeQuality::default(),
+            vdet_config: None,
+            decode_mode: DecodeMode::default(),
+            week_start: WeekStart::default(),
+            role: Role::default(),
+            burst_limit: None,
+            bus_frequency_hz: DEFAULT_BUS_FREQUENCY_HZ,
+            #[cfg(feature = "alarm")]
+            min_alarm_interval: Duration::zero(),
+            #[cfg(feature = "alarm")]
+            last_alarm: None,
+            state: PhantomData,
+        }
     }
 
-    /// Converts a regular decimal byte to a BCD-encoded byte.
-    fn to_bcd(data: u8) -> u8 {
-        ((data / 10) << 4) | (data % 10)
+    /// Destroys the driver and returns the I2C peripheral, e.g. to hand the bus to another driver or reconfigure it.
+    ///
+    /// The chip keeps running with its current settings; a driver created later on the same bus picks them up.
+    ///
+    /// # Returns
+    /// * `I2C` - The I2C peripheral passed to `new_unchecked`.
+    pub fn release(self) -> I2C {
+        self.i2c
     }
 
-    /// Converts a numerical representation of a weekday into a `Weekday` enum.
-    fn from_week(data: u8) -> Weekday {
-        match data {
-            0b00000001 => Weekday::Sun,
-            0b00000010 => Weekday::Mon,
-            0b00000100 => Weekday::Tue,
-            0b00001000 => Weekday::Wed,
-            0b00010000 => Weekday::Thu,
-            0b00100000 => Weekday::Fri,
-            0b01000000 => Weekday::Sat,
-            _ => todo!(), // placeholder for handling invalid data
-        }
+    /// Sets the policy applied by `set_timestamp`, `sync_from_ntp` and `adjust_seconds`.
+    ///
+    /// # Arguments
+    /// * `policy` - The correction policy; `TimeSetPolicy::STEP` by default.
+    pub fn set_time_set_policy(&mut self, policy: TimeSetPolicy) {
+        self.time_set_policy = policy;
+    }
+
+    /// Returns the policy applied by `set_timestamp`, `sync_from_ntp` and `adjust_seconds`.
+    ///
+    /// # Returns
+    /// * `TimeSetPolicy` - The current correction policy.
+    pub fn time_set_policy(&self) -> TimeSetPolicy {
+        self.time_set_policy
+    }
+
+    /// Returns the configuration that `poll_events` restores after a brown-out.
+    ///
+    /// # Returns
+    /// * `Option<Config>` - The configuration, or None if the automatic repair is disabled.
+    pub fn reapply_on_vdet(&self) -> Option<Config> {
+        self.vdet_config
+    }
+
+    /// Sets how every date and time read handles register contents that are not a valid date and time.
+    ///
+    /// The mode applies to `datetime`, `datetime_coherent`, `datetime_fast`, `datetime_decoded`, the date reads of
+    /// `ordinal`, `iso_week`, `days_until` and `is_today`, and `Century::datetime`, and to everything built on them.
+    ///
+    /// # Arguments
+    /// * `mode` - The decode mode; `DecodeMode::Strict` by default.
+    pub fn set_decode_mode(&mut self, mode: DecodeMode) {
+        self.decode_mode = mode;
+    }
+
+    /// Returns how the date and time reads handle register contents that are not a valid date and time.
+    ///
+    /// # Returns
+    /// * `DecodeMode` - The current decode mode.
+    pub fn decode_mode(&self) -> DecodeMode {
+        self.decode_mode
+    }
+
+    /// Sets the first day of the week, which orders the days returned by `week_alarm`.
+    ///
+    /// # Arguments
+    /// * `start` - The first day of the week; `WeekStart::Sunday` by default, the order of the register bits.
+    pub fn set_week_start(&mut self, start: WeekStart) {
+        self.week_start = start;
     }
 
-    /// Converts a `Weekday` enum into its numerical representation.
-    fn to_week(data: Weekday) -> u8 {
-        match data {
-            Weekday::Sun => 0b00000001,
-            Weekday::Mon => 0b00000010,
-            Weekday::Tue => 0b00000100,
-            Weekday::Wed => 0b00001000,
-            Weekday::Thu => 0b00010000,
-            Weekday::Fri => 0b00100000,
-            Weekday::Sat => 0b01000000,
+    /// Returns the first day of the week.
+    ///
+    /// # Returns
+    /// * `WeekStart` - The first day of the week.
+    pub fn week_start(&self) -> WeekStart {
+        self.week_start
+    }
+
+    /// Caps the number of registers read or written in one I2C transaction, for masters that cannot handle long
+    /// combined transfers, e.g. because of a small FIFO or a clock-stretching timeout.
+    ///
+    /// Longer bursts are split into several transactions of at most `limit` registers. The chip only latches the
+    /// time registers during a single burst, so a time read split this way can tear at a carry; use
+    /// `datetime_coherent` where that matters, or a limit of at least 7.
+    ///
+    /// # Arguments
+    /// * `limit` - The maximum number of registers per transaction, or None, the default, for no limit. A limit of 0
+    ///   is handled as 1.
+    pub fn set_burst_limit(&mut self, limit: Option<u8>) {
+        self.burst_limit = limit.map(|limit| limit.max(1));
+    }
+
+    /// Returns the maximum number of registers read or written in one I2C transaction.
+    ///
+    /// # Returns
+    /// * `Option<u8>` - The limit, or None if bursts are not split.
+    pub fn burst_limit(&self) -> Option<u8> {
+        self.burst_limit
+    }
+
+    /// Sets the I2C clock frequency the bus runs at, used by `estimated_transaction_time` and to reject periodic
+    /// modes that would saturate a slow bus.
+    ///
+    /// The driver does not configure the bus; the hint only has to match what the HAL was set up with.
+    ///
+    /// # Arguments
+    /// * `hz` - The SCL frequency in Hz; `DEFAULT_BUS_FREQUENCY_HZ` by default. A frequency of 0 is handled as 1 Hz.
+    pub fn set_bus_frequency_hint(&mut self, hz: u32) {
+        self.bus_frequency_hz = hz.max(1);
+    }
+
+    /// Returns the I2C clock frequency the bus is assumed to run at.
+    ///
+    /// # Returns
+    /// * `u32` - The SCL frequency in Hz.
+    pub fn bus_frequency_hint(&self) -> u32 {
+        self.bus_frequency_hz
+    }
+
+    /// Returns the number of registers to transfer per transaction for a burst of `len` registers.
+    fn chunk_len(&self, len: usize) -> usize {
+        self.burst_limit.map_or(len, usize::from).max(1)
+    }
+
+    /// Sets the minimum interval between two alarm firings, protecting against schedules that would wake the MCU
+    /// far more often than intended.
+    ///
+    /// `arm_alarm` rejects configurations firing more often, such as a wildcard minute, and `sleep_until`, used by
+    /// the re-arming schedulers, defers a time closer than the interval to the previous one. Registers written
+    /// directly, e.g. with `set_min_alarm`, are not checked.
+    ///
+    /// # Arguments
+    /// * `interval` - The minimum interval; zero, the default, disables the protection.
+    #[cfg(feature = "alarm")]
+    pub fn set_min_alarm_interval(&mut self, interval: Duration) {
+        self.min_alarm_interval = interval;
+    }
+
+    /// Returns the minimum interval between two alarm firings.
+    ///
+    /// # Returns
+    /// * `Duration` - The minimum interval, zero if the protection is disabled.
+    #[cfg(feature = "alarm")]
+    pub fn min_alarm_interval(&self) -> Duration {
+        self.min_alarm_interval
+    }
+
+    /// Returns whether an alarm configuration respects the minimum interval between two alarm firings.
+    #[cfg(feature = "alarm")]
+    pub(crate) fn alarm_interval_allowed(&self, config: &AlarmConfig) -> bool {
+        config.min_period() >= self.min_alarm_interval
+    }
+
+    /// Defers an alarm time closer than the minimum interval to the previously armed one, to the first whole minute
+    /// respecting it. A time before the previous one means the clock was set back and is kept as is.
+    #[cfg(feature = "alarm")]
+    fn alarm_not_before(&self, data: NaiveDateTime) -> NaiveDateTime {
+        let earliest = match self.last_alarm {
+            Some(last) if self.min_alarm_interval > Duration::zero() && data >= last => last + self.min_alarm_interval,
+            _ => return data,
+        };
+        if data >= earliest {
+            return data;
         }
+        let minute = earliest.with_second(0).and_then(|minute| minute.with_nanosecond(0)).unwrap_or(earliest);
+        if minute < earliest {
+            minute + Duration::minutes(1)
+        } else {
+            minute
+        }
+    }
+
+    /// Sets whether `timestamp` and `set_timestamp` count leap seconds.
+    ///
+    /// # Arguments
+    /// * `leap_seconds` - The leap second handling; `LeapSeconds::Ignore` (POSIX timestamps) by default.
+    pub fn set_leap_seconds(&mut self, leap_seconds: LeapSeconds) {
+        self.leap_seconds = leap_seconds;
+    }
+
+    /// Returns whether `timestamp` and `set_timestamp` count leap seconds.
+    ///
+    /// # Returns
+    /// * `LeapSeconds` - The current leap second handling.
+    pub fn leap_seconds(&self) -> LeapSeconds {
+        self.leap_seconds
+    }
+
+    /// Returns the last time adjustments made through the time set policy, most recent first.
+    ///
+    /// Adjustments made by `set_timestamp`, `set_timestamp_from`, `sync_from_ntp`, `adjust_seconds`, `adjust` and
+    /// `apply_time_message` are recorded with the time before and after the adjustment; `set_datetime` writes the clock unconditionally
+    /// and is not recorded. At most `ADJUSTMENT_HISTORY_LEN` adjustments are kept.
+    ///
+    /// # Returns
+    /// * `impl Iterator<Item = Adjustment>` - The recorded adjustments.
+    pub fn adjustment_history(&self) -> impl Iterator<Item = Adjustment> + '_ {
+        self.history.iter()
+    }
+
+    /// Drops all recorded time adjustments.
+    pub fn clear_adjustment_history(&mut self) {
+        self.history.clear();
+    }
+
+    /// Returns the source, time and estimated drift of the last synchronization.
+    ///
+    /// # Returns
+    /// * `TimeQuality` - The time quality metadata.
+    pub fn time_quality(&self) -> TimeQuality {
+        self.quality
+    }
+
+    /// Enables or disables the shadow cache of the extension, control and backup function registers.
+    ///
+    /// When enabled, read-modify-write operations on these registers reuse the value of the last completed read or
+    /// write instead of reading the register again, roughly halving the bus traffic of configuration-heavy code.
+    /// The cache is only valid as long as nothing else writes these registers, so it is disabled by default; call
+    /// `invalidate_shadow_cache` whenever another bus master or a power loss may have changed them.
+    ///
+    /// # Arguments
+    /// * `enabled` - True to enable the cache, false to disable it. Either way the cached values are dropped.
+    pub fn set_shadow_cache(&mut self, enabled: bool) {
+        self.shadow.set_enabled(enabled);
+    }
+
+    /// Checks if the shadow cache is enabled.
+    ///
+    /// # Returns
+    /// * `bool` - True if the shadow cache is enabled.
+    pub fn shadow_cache(&self) -> bool {
+        self.shadow.enabled()
+    }
+
+    /// Drops the cached register values, so the next read-modify-write operation reads from the device again.
+    pub fn invalidate_shadow_cache(&mut self) {
+        self.shadow.invalidate();
+    }
+
+    /// Converts a numerical representation of a weekday into a `Weekday` enum.
+    ///
+    /// # Returns
+    /// * `Option<Weekday>` - The weekday, or None if not exactly one weekday bit is set.
+    fn from_week(data: u8) -> Option<Weekday> {
+        weekday::bit_to_weekday(data)
     }
 }
 
 impl<I2C, E> Rx8900<I2C>
 where
-    I2C: Read<Error = E> + WriteRead<Error = E>,
+    I2C: Transport<Error = E>,
 {
-    /// Reads a single byte from a specified register.
+    register_access!();
+
+    /// Reads a single byte from a specified register, or returns its cached value when the shadow cache holds one.
     ///
     /// # Arguments
     /// * `register` - The register from which to read.
     ///
     /// # Returns
-    /// * `Result<u8, E>` - The read byte on success or an error if the read fails.
-    fn read_register(&mut self, register: RegisterTable) -> Result<u8, E> {
-        let mut data = [0];
-        self.i2c.write_read(RX8900_ADDR, &[register as u8], &mut data)?;
-        Ok(data[0])
+    /// * `Result<u8, E>` - The register value on success or an error if the read fails.
+    fn read_register_cached(&mut self, register: RegisterTable) -> Result<u8, E> {
+        match self.shadow.get(register) {
+            Some(data) => Ok(data),
+            None => self.read_register(register),
+        }
     }
 
-    /// Reads a specific bit from a register and returns it as a boolean value.
+    /// Reads a BCD-encoded field from a register, checking its digits and range.
     ///
     /// # Arguments
     /// * `register` - The register from which to read.
-    /// * `bit` - The bit position to read (0-7).
+    /// * `mask` - The bits of the register holding the field.
+    /// * `range` - The valid values of the field.
     ///
     /// # Returns
-    /// * `Result<bool, E>` - True if the bit is set, false otherwise, or an error if the read fails.
-    fn read_register_1bit(&mut self, register: RegisterTable, bit: u8) -> Result<bool, E> {
+    /// * `Result<u8, Error<E>>` - The decoded field, `Error::InvalidDeviceData` if it is not valid BCD or out of
+    ///   range, or an error if the read fails.
+    fn read_bcd(&mut self, register: RegisterTable, mask: u8, range: RangeInclusive<u8>) -> Result<u8, Error<E>> {
         let data = self.read_register(register)?;
-        Ok((data & (1 << bit)) == (1 << bit))
+        bcd::checked_from_bcd(data & mask)
+            .ok()
+            .filter(|value| range.contains(value))
+            .ok_or(Error::InvalidDeviceData)
     }
 
     /// Returns the current second value from the RTC.
     ///
     /// # Returns
-    /// * `Result<u8, E>` - The current second, or an error if the read fails.
-    pub fn sec(&mut self) -> Result<u8, E> {
-        let data = self.read_register(RegisterTable::CompatibleSEC)?;
-        Ok(Self::from_bcd(data & 0b01111111))
+    /// * `Result<u8, Error<E>>` - The current second, `Error::InvalidDeviceData` if the register does not hold a valid
+    ///   second, or an error if the read fails.
+    pub fn sec(&mut self) -> Result<u8, Error<E>> {
+        self.read_bcd(RegisterTable::CompatibleSEC, 0b01111111, 0..=59)
     }
 
     /// Returns the current minute value from the RTC.
     ///
     /// # Returns
-    /// * `Result<u8, E>` - The current minute, or an error if the read fails.
-    pub fn min(&mut self) -> Result<u8, E> {
-        let data = self.read_register(RegisterTable::CompatibleMIN)?;
-        Ok(Self::from_bcd(data & 0b01111111))
+    /// * `Result<u8, Error<E>>` - The current minute, `Error::InvalidDeviceData` if the register does not hold a valid
+    ///   minute, or an error if the read fails.
+    pub fn min(&mut self) -> Result<u8, Error<E>> {
+        self.read_bcd(RegisterTable::CompatibleMIN, 0b01111111, 0..=59)
     }
 
     /// Returns the current hour value from the RTC.
     ///
     /// # Returns
-    /// * `Result<u8, E>` - The current hour, or an error if the read fails.
-    pub fn hour(&mut self) -> Result<u8, E> {
-        let data = self.read_register(RegisterTable::CompatibleHOUR)?;
-        Ok(Self::from_bcd(data & 0b00111111))
+    /// * `Result<u8, Error<E>>` - The current hour, `Error::InvalidDeviceData` if the register does not hold a valid
+    ///   hour, or an error if the read fails.
+    pub fn hour(&mut self) -> Result<u8, Error<E>> {
+        self.read_bcd(RegisterTable::CompatibleHOUR, 0b00111111, 0..=23)
     }
 
     /// Returns the current day of the week from the RTC.
     ///
     /// # Returns
-    /// * `Result<Weekday, E>` - The current weekday, or an error if the read fails.
-    pub fn week(&mut self) -> Result<Weekday, E> {
+    /// * `Result<Weekday, Error<E>>` - The current weekday, `Error::InvalidDeviceData` if the register does not
+    ///   hold exactly one weekday bit, e.g. on a freshly powered chip, or an error if the read fails.
+    pub fn week(&mut self) -> Result<Weekday, Error<E>> {
         let data = self.read_register(RegisterTable::CompatibleWEEK)?;
-        Ok(Self::from_week(data))
+        Self::from_week(data).ok_or(Error::InvalidDeviceData)
     }
 
     /// Returns the current day of the month from the RTC.
     ///
     /// # Returns
-    /// * `Result<u8, E>` - The current day, or an error if the read fails.
-    pub fn day(&mut self) -> Result<u8, E> {
-        let data = self.read_register(RegisterTable::CompatibleDAY)?;
-        Ok(Self::from_bcd(data & 0b00111111))
+    /// * `Result<u8, Error<E>>` - The current day, `Error::InvalidDeviceData` if the register does not hold a valid
+    ///   day, or an error if the read fails.
+    pub fn day(&mut self) -> Result<u8, Error<E>> {
+        self.read_bcd(RegisterTable::CompatibleDAY, 0b00111111, 1..=31)
     }
 
     /// Returns the current month from the RTC.
     ///
     /// # Returns
-    /// * `Result<u8, E>` - The current month, or an error if the read fails.
-    pub fn month(&mut self) -> Result<u8, E> {
-        let data = self.read_register(RegisterTable::CompatibleMONTH)?;
-        Ok(Self::from_bcd(data & 0b00011111))
+    /// * `Result<u8, Error<E>>` - The current month, `Error::InvalidDeviceData` if the register does not hold a valid
+    ///   month, or an error if the read fails.
+    pub fn month(&mut self) -> Result<u8, Error<E>> {
+        self.read_bcd(RegisterTable::CompatibleMONTH, 0b00011111, 1..=12)
     }
 
     /// Returns the current year from the RTC.
     ///
     /// # Returns
-    /// * `Result<u8, E>` - The current year, or an error if the read fails.
-    pub fn year(&mut self) -> Result<u8, E> {
-        let data = self.read_register(RegisterTable::CompatibleYEAR)?;
-        Ok(Self::from_bcd(data))
+    /// * `Result<u8, Error<E>>` - The current year, `Error::InvalidDeviceData` if the register does not hold a valid
+    ///   year, or an error if the read fails.
+    pub fn year(&mut self) -> Result<u8, Error<E>> {
+        self.read_bcd(RegisterTable::CompatibleYEAR, 0xFF, 0..=99)
     }
 
     /// Reads the general-purpose RAM value from the RTC.
@@ -243,16 +754,18 @@ where
     /// Reads the minute alarm value and checks if the alarm is enabled.
     ///
     /// # Returns
-    /// * `Result<u8, E>` - The minute for the alarm, or an error if the read fails.
-    pub fn min_alarm(&mut self) -> Result<u8, E> {
-        let data = self.read_register(RegisterTable::CompatibleMinAlarm)?;
-        Ok(Self::from_bcd(data & 0b01111111))
+    /// * `Result<u8, Error<E>>` - The minute for the alarm, `Error::InvalidDeviceData` if the register does not hold a
+    ///   valid minute, or an error if the read fails.
+    #[cfg(feature = "alarm")]
+    pub fn min_alarm(&mut self) -> Result<u8, Error<E>> {
+        self.read_bcd(RegisterTable::CompatibleMinAlarm, 0b01111111, 0..=59)
     }
 
     /// Checks if the minute alarm is currently enabled.
     ///
     /// # Returns
     /// * `Result<bool, E>` - True if the minute alarm is enabled, or an error if the read fails.
+    #[cfg(feature = "alarm")]
     pub fn min_alarm_enabled(&mut self) -> Result<bool, E> {
         self.read_register_1bit(RegisterTable::CompatibleMinAlarm, 7)
     }
@@ -260,16 +773,18 @@ where
     /// Reads the hour alarm value and checks if the alarm is enabled.
     ///
     /// # Returns
-    /// * `Result<u8, E>` - The hour for the alarm, or an error if the read fails.
-    pub fn hour_alarm(&mut self) -> Result<u8, E> {
-        let data = self.read_register(RegisterTable::CompatibleHourAlarm)?;
-        Ok(Self::from_bcd(data & 0b00111111))
+    /// * `Result<u8, Error<E>>` - The hour for the alarm, `Error::InvalidDeviceData` if the register does not hold a
+    ///   valid hour, or an error if the read fails.
+    #[cfg(feature = "alarm")]
+    pub fn hour_alarm(&mut self) -> Result<u8, Error<E>> {
+        self.read_bcd(RegisterTable::CompatibleHourAlarm, 0b00111111, 0..=23)
     }
 
     /// Checks if the hour alarm is currently enabled.
     ///
     /// # Returns
     /// * `Result<bool, E>` - True if the hour alarm is enabled, or an error if the read fails.
+    #[cfg(feature = "alarm")]
     pub fn hour_alarm_enabled(&mut self) -> Result<bool, E> {
         self.read_register_1bit(RegisterTable::CompatibleHourAlarm, 7)
     }
@@ -277,23 +792,19 @@ where
     /// Retrieves the current set weekdays for the week alarm.
     ///
     /// # Returns
-    /// * `Result<Vec<Weekday, 7>, E>` - A vector of `Weekday` representing the days set in the week alarm, or an error if the read fails.
+    /// * `Result<Vec<Weekday, 7>, E>` - A vector of `Weekday` representing the days set in the week alarm, in the order
+    ///   of `week_start`, or an error if the read fails.
+    #[cfg(feature = "alarm")]
     pub fn week_alarm(&mut self) -> Result<Vec<Weekday, 7>, E> {
-        let mut weekdays = Vec::<Weekday, 7>::new();
         let data = self.read_register(RegisterTable::CompatibleWeekDayAlarm)?;
-        for i in 0..7 {
-            let bit = 1 << i;
-            if (data & bit) == bit {
-                weekdays.push(Self::from_week(bit)).unwrap();
-            }
-        }
-        Ok(weekdays)
+        Ok(self.week_start.weekdays(data).collect())
     }
 
     /// Checks if the week alarm is currently enabled.
     ///
     /// # Returns
     /// * `Result<bool, E>` - True if the week alarm is enabled, or an error if the read fails.
+    #[cfg(feature = "alarm")]
     pub fn week_alarm_enabled(&mut self) -> Result<bool, E> {
         self.read_register_1bit(RegisterTable::CompatibleWeekDayAlarm, 7)
     }
@@ -301,16 +812,18 @@ where
     /// Retrieves the day for the day alarm.
     ///
     /// # Returns
-    /// * `Result<u8, E>` - The day in the day alarm, or an error if the read fails.
-    pub fn day_alarm(&mut self) -> Result<u8, E> {
-        let data = self.read_register(RegisterTable::CompatibleWeekDayAlarm)?;
-        Ok(Self::from_bcd(data & 0b01111111))
+    /// * `Result<u8, Error<E>>` - The day in the day alarm, `Error::InvalidDeviceData` if the register does not hold a
+    ///   valid day, or an error if the read fails.
+    #[cfg(feature = "alarm")]
+    pub fn day_alarm(&mut self) -> Result<u8, Error<E>> {
+        self.read_bcd(RegisterTable::CompatibleWeekDayAlarm, 0b01111111, 1..=31)
     }
 
     /// Checks if the day alarm is currently enabled.
     ///
     /// # Returns
     /// * `Result<bool, E>` - True if the day alarm is enabled, or an error if the read fails.
+    #[cfg(feature = "alarm")]
     pub fn day_alarm_enabled(&mut self) -> Result<bool, E> {
         self.read_register_1bit(RegisterTable::CompatibleWeekDayAlarm, 7)
     }
@@ -319,6 +832,7 @@ where
     ///
     /// # Returns
     /// * `Result<u8, E>` - The value of timer counter 0, or an error if the read fails.
+    #[cfg(feature = "timer")]
     pub fn timer_counter0(&mut self) -> Result<u8, E> {
         self.read_register(RegisterTable::CompatibleTimerCounter0)
     }
@@ -327,6 +841,7 @@ where
     ///
     /// # Returns
     /// * `Result<u8, E>` - The value of timer counter 1, or an error if the read fails.
+    #[cfg(feature = "timer")]
     pub fn timer_counter1(&mut self) -> Result<u8, E> {
         self.read_register(RegisterTable::CompatibleTimerCounter1)
     }
@@ -335,27 +850,34 @@ where
     ///
     /// # Returns
     /// * `Result<u16, E>` - The combined value of the two timer counters, or an error if the read fails.
+    #[cfg(feature = "timer")]
     pub fn timer_counter(&mut self) -> Result<u16, E> {
         let data0 = self.read_register(RegisterTable::CompatibleTimerCounter0)?;
         let data1 = self.read_register(RegisterTable::CompatibleTimerCounter1)?;
         Ok((data1 as u16) << 8 | data0 as u16)
     }
 
-    /// Reads the current temperature value from the RTC's temperature sensor.
+    /// Converts the raw temperature value to Celsius.
+    ///
+    /// Only available with the `float` feature, so that the default build stays free of floating point code.
     ///
     /// # Returns
-    /// * `Result<u8, E>` - The raw temperature value, or an error if the read fails.
-    pub fn temp(&mut self) -> Result<u8, E> {
-        self.read_register(RegisterTable::ExtendedTEMP)
+    /// * `Result<f32, E>` - The temperature in Celsius, or an error if the read fails.
+    #[cfg(all(feature = "temperature", feature = "float"))]
+    #[allow(clippy::float_arithmetic)]
+    pub fn temp_in_celsius(&mut self) -> Result<f32, E> {
+        let data = self.temp()?;
+        Ok((data as f32 * 2.0 - 187.19) / 3.218)
     }
 
-    /// Converts the raw temperature value to Celsius.
+    /// Misspelled alias for `temp_in_celsius`.
     ///
     /// # Returns
     /// * `Result<f32, E>` - The temperature in Celsius, or an error if the read fails.
+    #[cfg(all(feature = "temperature", feature = "float"))]
+    #[deprecated(since = "0.2.0", note = "use `temp_in_celsius`")]
     pub fn temp_in_cercius(&mut self) -> Result<f32, E> {
-        let data = self.temp()?;
-        Ok((data as f32 * 2.0 - 187.19) / 3.218)
+        self.temp_in_celsius()
     }
 
     /// Checks if the voltage detector is currently turned off.
@@ -427,6 +949,7 @@ where
     ///
     /// # Returns
     /// * `Result<AlarmType, E>` - The type of alarm (day or week), or an error if the read fails.
+    #[cfg(feature = "alarm")]
     pub fn alarm_type(&mut self) -> Result<AlarmType, E> {
         self.wada().map(|x| if x { AlarmType::DayAlarm } else { AlarmType::WeekAlarm })
     }
@@ -443,6 +966,7 @@ where
     ///
     /// # Returns
     /// * `Result<bool, E>` - True if the WADA bit is set (day alarm), false if cleared (week alarm), or an error if the read fails.
+    #[cfg(feature = "alarm")]
     pub fn wada(&mut self) -> Result<bool, E> {
         self.read_register_1bit(RegisterTable::CompatibleExtensionRegister, 6)
     }
@@ -467,6 +991,7 @@ where
     ///
     /// # Returns
     /// * `Result<bool, E>` - True if the timer is enabled, or an error if the read fails.
+    #[cfg(feature = "timer")]
     pub fn te(&mut self) -> Result<bool, E> {
         self.read_register_1bit(RegisterTable::CompatibleExtensionRegister, 4)
     }
@@ -475,6 +1000,7 @@ where
     ///
     /// # Returns
     /// * `Result<bool, E>` - True if the timer is enabled, or an error if the read fails.
+    #[cfg(feature = "timer")]
     pub fn timer_enable(&mut self) -> Result<bool, E> {
         self.te()
     }
@@ -511,11 +1037,10 @@ where
     pub fn fout_frequency(&mut self) -> Result<FoutFrequency, E> {
         let current = self.fsel()?;
         Ok(match current {
-            0b00 => FoutFrequency::FoutFrequency32_768kHz,
             0b01 => FoutFrequency::FoutFrequency1024Hz,
             0b10 => FoutFrequency::FoutFrequency1Hz,
-            0b11 => FoutFrequency::FoutFrequency32_768kHz,
-            _ => todo!(),
+            // 0b00 and 0b11
+            _ => FoutFrequency::FoutFrequency32_768kHz,
         })
     }
 
@@ -523,14 +1048,15 @@ where
     ///
     /// # Returns
     /// * `Result<SourceClock, E>` - The current source clock setting, or an error if the read fails.
+    #[cfg(feature = "timer")]
     pub fn source_clock(&mut self) -> Result<SourceClock, E> {
         let data = self.read_register(RegisterTable::CompatibleExtensionRegister)?;
         Ok(match (data & 0b00000011) >> 0 {
             0b00 => SourceClock::SourceClock4096Hz,
             0b01 => SourceClock::SourceClock64Hz,
             0b10 => SourceClock::SourceClockSecond,
-            0b11 => SourceClock::SourceClockMinute,
-            _ => todo!(),
+            // 0b11, the only value left by the mask
+            _ => SourceClock::SourceClockMinute,
         })
     }
     
@@ -538,6 +1064,7 @@ where
     ///
     /// # Returns
     /// * `Result<bool, E>` - True if the timer selection bit 1 is set, or an error if the read fails.
+    #[cfg(feature = "timer")]
     pub fn tsel1(&mut self) -> Result<bool, E> {
         self.read_register_1bit(RegisterTable::CompatibleExtensionRegister, 1)
     }
@@ -546,6 +1073,7 @@ where
     ///
     /// # Returns
     /// * `Result<bool, E>` - True if the timer selection bit 0 is set, or an error if the read fails.
+    #[cfg(feature = "timer")]
     pub fn tsel0(&mut self) -> Result<bool, E> {
         self.read_register_1bit(RegisterTable::CompatibleExtensionRegister, 0)
     }
@@ -554,19 +1082,12 @@ where
     ///
     /// # Returns
     /// * `Result<u8, E>` - The timer selection configuration value, or an error if the read fails.
+    #[cfg(feature = "timer")]
     pub fn tsel(&mut self) -> Result<u8, E> {
         let data = self.read_register(RegisterTable::CompatibleExtensionRegister)?;
         Ok((data & 0b00000011) >> 0)
     }
 
-    /// Reads the update flag which indicates if an update occurred.
-    ///
-    /// # Returns
-    /// * `Result<bool, E>` - True if an update has occurred, otherwise false, or an error if the read fails.
-    pub fn uf(&mut self) -> Result<bool, E> {
-        self.read_register_1bit(RegisterTable::CompatibleFlagRegister, 5)
-    }
-
     /// Alias for `uf`.
     ///
     /// # Returns
@@ -575,46 +1096,24 @@ where
         self.uf()
     }
 
-    /// Reads the timer flag which indicates if a timer event has occurred.
-    ///
-    /// # Returns
-    /// * `Result<bool, E>` - True if a timer event has occurred, otherwise false, or an error if the read fails.
-    pub fn tf(&mut self) -> Result<bool, E> {
-        self.read_register_1bit(RegisterTable::CompatibleFlagRegister, 4)
-    }
-
     /// Alias for `tf`.
     ///
     /// # Returns
     /// * `Result<bool, E>` - True if a timer interrupt has occurred, false otherwise, or an error if the read fails.
+    #[cfg(feature = "timer")]
     pub fn timer_flag(&mut self) -> Result<bool, E> {
         self.tf()
     }
 
-    /// Reads the alarm flag which indicates if an alarm has been triggered.
-    ///
-    /// # Returns
-    /// * `Result<bool, E>` - True if an alarm has been triggered, otherwise false, or an error if the read fails.
-    pub fn af(&mut self) -> Result<bool, E> {
-        self.read_register_1bit(RegisterTable::CompatibleFlagRegister, 3)
-    }
-
     /// Alias for `af`.
     ///
     /// # Returns
     /// * `Result<bool, E>` - True if an alarm has been triggered, false otherwise, or an error if the read fails.
+    #[cfg(feature = "alarm")]
     pub fn alarm_flag(&mut self) -> Result<bool, E> {
         self.af()
     }
 
-    /// Reads the voltage low flag which indicates if the battery voltage has dropped below a critical threshold.
-    ///
-    /// # Returns
-    /// * `Result<bool, E>` - True if the voltage is low, otherwise false, or an error if the read fails.
-    pub fn vlf(&mut self) -> Result<bool, E> {
-        self.read_register_1bit(RegisterTable::CompatibleFlagRegister, 1)
-    }
-
     /// Alias for `vlf`.
     ///
     /// # Returns
@@ -623,14 +1122,6 @@ where
         self.vlf()
     }
 
-    /// Reads the voltage detect flag which indicates if a voltage drop has been detected.
-    ///
-    /// # Returns
-    /// * `Result<bool, E>` - True if a voltage drop has been detected, otherwise false, or an error if the read fails.
-    pub fn vdet(&mut self) -> Result<bool, E> {
-        self.read_register_1bit(RegisterTable::CompatibleFlagRegister, 0)
-    }
-
     /// Alias for `vdet`.
     ///
     /// # Returns
@@ -674,8 +1165,8 @@ where
             0b00 => CompensationIntervalType::CompensationInterval0_5s,
             0b01 => CompensationIntervalType::CompensationInterval2_0s,
             0b10 => CompensationIntervalType::CompensationInterval10s,
-            0b11 => CompensationIntervalType::CompensationInterval30s,
-            _ => todo!(),
+            // 0b11, the only value left by the mask
+            _ => CompensationIntervalType::CompensationInterval30s,
         })
     }
 
@@ -699,6 +1190,7 @@ where
     ///
     /// # Returns
     /// * `Result<bool, E>` - True if the timer interrupt is enabled, otherwise false, or an error if the read fails.
+    #[cfg(feature = "timer")]
     pub fn tie(&mut self) -> Result<bool, E> {
         self.read_register_1bit(RegisterTable::CompatibleControlRegister, 4)
     }
@@ -707,6 +1199,7 @@ where
     ///
     /// # Returns
     /// * `Result<bool, E>` - True if the timer interrupt is enabled, otherwise false, or an error if the read fails.
+    #[cfg(feature = "timer")]
     pub fn timer_interrupt_enable(&mut self) -> Result<bool, E> {
         self.tie()
     }
@@ -715,6 +1208,7 @@ where
     ///
     /// # Returns
     /// * `Result<bool, E>` - True if the alarm interrupt is enabled, otherwise false, or an error if the read fails.
+    #[cfg(feature = "alarm")]
     pub fn aie(&mut self) -> Result<bool, E> {
         self.read_register_1bit(RegisterTable::CompatibleControlRegister, 3)
     }
@@ -723,6 +1217,7 @@ where
     ///
     /// # Returns
     /// * `Result<bool, E>` - True if the alarm interrupt is enabled, otherwise false, or an error if the read fails.
+    #[cfg(feature = "alarm")]
     pub fn alarm_interrupt_enable(&mut self) -> Result<bool, E> {
         self.aie()
     }
@@ -738,30 +1233,21 @@ where
 
 impl<I2C, E> Rx8900<I2C>
 where
-    I2C: Write<Error = E>,
+    I2C: Transport<Error = E>,
 {
-    /// Writes a single byte to a specified register.
-    ///
-    /// # Arguments
-    /// * `register` - The register to which the byte will be written.
-    /// * `data` - The data byte to write.
-    ///
-    /// # Returns
-    /// * `Result<(), E>` - Ok if the write was successful, or an error if the write fails.
-    fn write_register(&mut self, register: RegisterTable, data: u8) -> Result<(), E> {
-        self.i2c.write(RX8900_ADDR, &[register as u8, data])?;
-        Ok(())
-    }
-
     /// Sets the second value in the RTC.
     ///
     /// # Arguments
     /// * `data` - The second to be set (0-59).
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the second was successfully set, or an error if the write fails.
-    pub fn set_sec(&mut self, data: u8) -> Result<(), E> {
-        self.write_register(RegisterTable::CompatibleSEC, Self::to_bcd(data & 0b01111111))
+    /// * `Result<(), Error<E>>` - Ok if the second was successfully set, `Error::InvalidInput` if the value is out of
+    ///   range, or an error if the write fails.
+    pub fn set_sec(&mut self, data: u8) -> Result<(), Error<E>> {
+        if !(0..=59).contains(&data) {
+            return Err(Error::InvalidInput);
+        }
+        self.write_register(RegisterTable::CompatibleSEC, bcd::to_bcd(data))
     }
 
     /// Sets the minute value in the RTC.
@@ -770,9 +1256,13 @@ where
     /// * `data` - The minute to be set (0-59).
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the minute was successfully set, or an error if the write fails.
-    pub fn set_min(&mut self, data: u8) -> Result<(), E> {
-        self.write_register(RegisterTable::CompatibleMIN, Self::to_bcd(data & 0b01111111))
+    /// * `Result<(), Error<E>>` - Ok if the minute was successfully set, `Error::InvalidInput` if the value is out of
+    ///   range, or an error if the write fails.
+    pub fn set_min(&mut self, data: u8) -> Result<(), Error<E>> {
+        if !(0..=59).contains(&data) {
+            return Err(Error::InvalidInput);
+        }
+        self.write_register(RegisterTable::CompatibleMIN, bcd::to_bcd(data))
     }
 
     /// Sets the hour value in the RTC.
@@ -781,9 +1271,13 @@ where
     /// * `data` - The hour to be set (0-23).
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the hour was successfully set, or an error if the write fails.
-    pub fn set_hour(&mut self, data: u8) -> Result<(), E> {
-        self.write_register(RegisterTable::CompatibleHOUR, Self::to_bcd(data & 0b00111111))
+    /// * `Result<(), Error<E>>` - Ok if the hour was successfully set, `Error::InvalidInput` if the value is out of
+    ///   range, or an error if the write fails.
+    pub fn set_hour(&mut self, data: u8) -> Result<(), Error<E>> {
+        if !(0..=23).contains(&data) {
+            return Err(Error::InvalidInput);
+        }
+        self.write_register(RegisterTable::CompatibleHOUR, bcd::to_bcd(data))
     }
 
     /// Sets the day of the week in the RTC.
@@ -792,9 +1286,9 @@ where
     /// * `data` - The `Weekday` to be set.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the weekday was successfully set, or an error if the write fails.
-    pub fn set_week(&mut self, data: Weekday) -> Result<(), E> {
-        self.write_register(RegisterTable::CompatibleWEEK, Self::to_week(data))
+    /// * `Result<(), Error<E>>` - Ok if the weekday was successfully set, or an error if the write fails.
+    pub fn set_week(&mut self, data: Weekday) -> Result<(), Error<E>> {
+        self.write_register(RegisterTable::CompatibleWEEK, weekday::weekday_to_bit(data))
     }
 
     /// Sets the day of the month in the RTC.
@@ -803,9 +1297,13 @@ where
     /// * `data` - The day to be set (1-31).
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the day was successfully set, or an error if the write fails.
-    pub fn set_day(&mut self, data: u8) -> Result<(), E> {
-        self.write_register(RegisterTable::CompatibleDAY, Self::to_bcd(data & 0b00111111))
+    /// * `Result<(), Error<E>>` - Ok if the day was successfully set, `Error::InvalidInput` if the value is out of
+    ///   range, or an error if the write fails.
+    pub fn set_day(&mut self, data: u8) -> Result<(), Error<E>> {
+        if !(1..=31).contains(&data) {
+            return Err(Error::InvalidInput);
+        }
+        self.write_register(RegisterTable::CompatibleDAY, bcd::to_bcd(data))
     }
 
     /// Sets the month in the RTC.
@@ -814,9 +1312,13 @@ where
     /// * `data` - The month to be set (1-12).
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the month was successfully set, or an error if the write fails.
-    pub fn set_month(&mut self, data: u8) -> Result<(), E> {
-        self.write_register(RegisterTable::CompatibleMONTH, Self::to_bcd(data & 0b00011111))
+    /// * `Result<(), Error<E>>` - Ok if the month was successfully set, `Error::InvalidInput` if the value is out of
+    ///   range, or an error if the write fails.
+    pub fn set_month(&mut self, data: u8) -> Result<(), Error<E>> {
+        if !(1..=12).contains(&data) {
+            return Err(Error::InvalidInput);
+        }
+        self.write_register(RegisterTable::CompatibleMONTH, bcd::to_bcd(data))
     }
 
     /// Sets the year in the RTC.
@@ -825,20 +1327,32 @@ where
     /// * `data` - The year to be set (0-99).
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the year was successfully set, or an error if the write fails.
-    pub fn set_year(&mut self, data: u8) -> Result<(), E> {
-        self.write_register(RegisterTable::CompatibleYEAR, Self::to_bcd(data & 0b11111111))
+    /// * `Result<(), Error<E>>` - Ok if the year was successfully set, `Error::InvalidInput` if the value is out of
+    ///   range, or an error if the write fails.
+    pub fn set_year(&mut self, data: u8) -> Result<(), Error<E>> {
+        if !(0..=99).contains(&data) {
+            return Err(Error::InvalidInput);
+        }
+        self.write_register(RegisterTable::CompatibleYEAR, bcd::to_bcd(data))
     }
 
     /// Writes a byte to the general-purpose RAM in the RTC.
     ///
+    /// Once the driver has a role from `claim` or `observe_only`, the upper two bits hold the ownership marker and
+    /// are kept: only the lower six bits of `data` are written.
+    ///
     /// # Arguments
     /// * `data` - The byte to be written to RAM.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the write was successful, or an error if the write fails.
-    pub fn set_ram(&mut self, data: u8) -> Result<(), E> {
-        self.write_register(RegisterTable::CompatibleRAM, data)
+    /// * `Result<(), Error<E>>` - Ok if the write was successful, or an error if the write fails.
+    pub fn set_ram(&mut self, data: u8) -> Result<(), Error<E>> {
+        let bits = self.ram_bits();
+        if bits == 0xFF {
+            return self.write_register(RegisterTable::CompatibleRAM, data);
+        }
+        let ram = self.read_register(RegisterTable::CompatibleRAM)?;
+        self.write_register(RegisterTable::CompatibleRAM, ram & !bits | data & bits)
     }
 
     /// Enables or disables the minute alarm.
@@ -848,9 +1362,14 @@ where
     /// * `enabled` - True to enable the alarm, false to disable.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the alarm setting was successful, or an error if the write fails.
-    pub fn set_min_alarm(&mut self, data: u8, enabled: bool) -> Result<(), E> {
-        self.write_register(RegisterTable::CompatibleMinAlarm, Self::to_bcd(data & 0b01111111) | (enabled as u8) << 7)
+    /// * `Result<(), Error<E>>` - Ok if the alarm setting was successful, `Error::InvalidInput` if the minute is above
+    ///   59, or an error if the write fails.
+    #[cfg(feature = "alarm")]
+    pub fn set_min_alarm(&mut self, data: u8, enabled: bool) -> Result<(), Error<E>> {
+        if data > 59 {
+            return Err(Error::InvalidInput);
+        }
+        self.write_register(RegisterTable::CompatibleMinAlarm, bcd::to_bcd(data) | (enabled as u8) << 7)
     }
 
     /// Enables or disables the hour alarm.
@@ -860,9 +1379,14 @@ where
     /// * `enabled` - True to enable the alarm, false to disable.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the alarm setting was successful, or an error if the write fails.
-    pub fn set_hour_alarm(&mut self, data: u8, enabled: bool) -> Result<(), E> {
-        self.write_register(RegisterTable::CompatibleHourAlarm, Self::to_bcd(data & 0b00111111) | (enabled as u8) << 7)
+    /// * `Result<(), Error<E>>` - Ok if the alarm setting was successful, `Error::InvalidInput` if the hour is above
+    ///   23, or an error if the write fails.
+    #[cfg(feature = "alarm")]
+    pub fn set_hour_alarm(&mut self, data: u8, enabled: bool) -> Result<(), Error<E>> {
+        if data > 23 {
+            return Err(Error::InvalidInput);
+        }
+        self.write_register(RegisterTable::CompatibleHourAlarm, bcd::to_bcd(data) | (enabled as u8) << 7)
     }
 
     /// Sets the week alarm for the specified weekdays.
@@ -871,11 +1395,12 @@ where
     /// * `data` - A reference to a vector containing the weekdays for which the alarm should be set.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the alarm was successfully set, or an error if the operation fails.
-    pub fn set_week_alarm(&mut self, data: &Vec<Weekday, 7>) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the alarm was successfully set, or an error if the operation fails.
+    #[cfg(feature = "alarm")]
+    pub fn set_week_alarm(&mut self, data: &Vec<Weekday, 7>) -> Result<(), Error<E>> {
         let mut value = 0;
         for day in data {
-            value |= Self::to_week(*day);
+            value |= weekday::weekday_to_bit(*day);
         }
         self.write_register(RegisterTable::CompatibleWeekDayAlarm, value)
     }
@@ -887,9 +1412,14 @@ where
     /// * `enabled` - True to enable the alarm, false to disable it.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the alarm was successfully set, or an error if the operation fails.
-    pub fn set_day_alarm(&mut self, data: u8, enabled: bool) -> Result<(), E> {
-        self.write_register(RegisterTable::CompatibleWeekDayAlarm, Self::to_bcd(data & 0b01111111) | (enabled as u8) << 7)
+    /// * `Result<(), Error<E>>` - Ok if the alarm was successfully set, `Error::InvalidInput` if the day is not within
+    ///   1-31, or an error if the operation fails.
+    #[cfg(feature = "alarm")]
+    pub fn set_day_alarm(&mut self, data: u8, enabled: bool) -> Result<(), Error<E>> {
+        if !(1..=31).contains(&data) {
+            return Err(Error::InvalidInput);
+        }
+        self.write_register(RegisterTable::CompatibleWeekDayAlarm, bcd::to_bcd(data) | (enabled as u8) << 7)
     }
 
     /// Sets the value of timer counter 0.
@@ -898,8 +1428,9 @@ where
     /// * `data` - The value to set for timer counter 0.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the value was successfully set, or an error if the write fails.
-    pub fn set_timer_counter0(&mut self, data: u8) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the value was successfully set, or an error if the write fails.
+    #[cfg(feature = "timer")]
+    pub fn set_timer_counter0(&mut self, data: u8) -> Result<(), Error<E>> {
         self.write_register(RegisterTable::CompatibleTimerCounter0, data)
     }
 
@@ -909,19 +1440,31 @@ where
     /// * `data` - The value to set for timer counter 1.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the value was successfully set, or an error if the write fails.
-    pub fn set_timer_counter1(&mut self, data: u8) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the value was successfully set, or an error if the write fails.
+    #[cfg(feature = "timer")]
+    pub fn set_timer_counter1(&mut self, data: u8) -> Result<(), Error<E>> {
         self.write_register(RegisterTable::CompatibleTimerCounter1, data)
     }
 
     /// Sets the combined value of timer counter 0 and timer counter 1.
     ///
     /// # Arguments
-    /// * `data` - The 16-bit value to set for the timer counters.
+    /// * `data` - The 12-bit value to set for the timer counters (0-4095).
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the value was successfully set, or an error if the write fails.
-    pub fn set_timer_counter(&mut self, data: u16) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the value was successfully set, `Error::InvalidInput` if the value is out of
+    ///   range, or an error if the write fails.
+    #[cfg(feature = "timer")]
+    pub fn set_timer_counter(&mut self, data: u16) -> Result<(), Error<E>> {
+        if data > 0x0FFF {
+            return Err(Error::InvalidInput);
+        }
+        self.write_timer_counter(data)
+    }
+
+    /// Writes a 12-bit value to timer counter 0 and timer counter 1.
+    #[cfg(feature = "timer")]
+    fn write_timer_counter(&mut self, data: u16) -> Result<(), Error<E>> {
         self.write_register(RegisterTable::CompatibleTimerCounter0, (data & 0x00FF) as u8)?;
         self.write_register(RegisterTable::CompatibleTimerCounter1, ((data & 0xFF00) >> 8) as u8)
     }
@@ -929,73 +1472,681 @@ where
 
 impl<I2C, E> Rx8900<I2C>
 where
-    I2C: Read<Error = E> + WriteRead<Error = E> + Write<Error = E>,
+    I2C: Transport<Error = E>,
 {
     /// Initializes the RTC with default settings.
     ///
+    /// Disables the timer, the FOUT divider selection, the test mode and all interrupts, clears VDET and VLF,
+    /// and sets VDETOFF=0, SWOFF=1. Each affected register is written exactly once.
+    ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if initialization was successful, or an error if the operation fails.
-    pub fn init(&mut self) -> Result<(), E> {
-        self.set_te(false)?;
-        self.set_fsel0(false)?;
-        self.set_fsel1(false)?;
-        self.set_test(false)?;
-        self.set_vdet()?;
-        self.set_vlf()?;
-        self.set_aie(false)?;
-        self.set_tie(false)?;
-        self.set_uie(false)?;
+    /// * `Result<(), Error<E>>` - Ok if initialization was successful, or an error if the operation fails.
+    pub fn init(&mut self) -> Result<(), Error<E>> {
+        self.write_init_config(&InitConfig::default())
+    }
+
+    /// Initializes the RTC with chosen settings instead of the defaults of `init`.
+    ///
+    /// Disables the timer and the test mode, clears VDET and VLF, and programs the FOUT frequency, the interrupt
+    /// enables and the backup settings of `config`, along with the compensation interval and backup sampling time
+    /// if set. Each affected register is written exactly once.
+    ///
+    /// # Arguments
+    /// * `config` - The settings to program.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if initialization was successful, `Error::InvalidInput` if the backup sampling
+    ///   time is out of range, or an error if the operation fails.
+    pub fn init_with(&mut self, config: &InitConfig) -> Result<(), Error<E>> {
+        if !config.is_valid() {
+            return Err(Error::InvalidInput);
+        }
+        self.write_init_config(config)
+    }
+
+    /// Writes the registers programmed by `init_with`, for a configuration that was validated.
+    pub(crate) fn write_init_config(&mut self, config: &InitConfig) -> Result<(), Error<E>> {
+        // Clear VDET and VLF
+        self.update_control_registers(config.extension_register(), 0b00000011, config.control_register())?;
+        let (mask, data) = config.backup_function_register();
+        self.update_register(RegisterTable::ExtendedBackupFunction, mask, data)
+    }
+
+    /// Applies a complete configuration to the extension, control and backup function registers.
+    ///
+    /// The registers are read once, the final register images are computed, and each affected register is written
+    /// exactly once. Pending flags are left untouched.
+    ///
+    /// # Arguments
+    /// * `config` - The configuration to apply.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the configuration was successfully applied, `Error::InvalidInput` if the
+    ///   backup sampling time is out of range, or an error if the operation fails.
+    pub fn apply_config(&mut self, config: &Config) -> Result<(), Error<E>> {
+        if !config.is_valid() {
+            return Err(Error::InvalidInput);
+        }
+        self.write_config(config)
+    }
+
+    /// Writes the registers of a configuration that was validated.
+    fn write_config(&mut self, config: &Config) -> Result<(), Error<E>> {
+        self.update_control_registers(config.extension_register(), 0, config.control_register())?;
+        let (mask, data) = config.backup_function_register();
+        self.update_register(RegisterTable::ExtendedBackupFunction, mask, data)
+    }
+
+    /// Reads the configuration held in the extension, control and backup function registers.
+    ///
+    /// # Returns
+    /// * `Result<Config, E>` - The current configuration, or an error if the read fails.
+    pub fn config(&mut self) -> Result<Config, E> {
+        let mut data = [0; 3];
+        self.read_registers(RegisterTable::CompatibleExtensionRegister, &mut data)?;
+        let backup_function = self.read_register(RegisterTable::ExtendedBackupFunction)?;
+        Ok(Config::from_registers(data[0], data[2], backup_function))
+    }
 
-        // set VDETOFF=”1”
-        self.set_voltage_detector_off(false)?;
-        // set SWOFF=”1”
-        self.set_switch_off(true)?;
+    /// Compares a configuration with the registers of the chip and writes only the registers that differ.
+    ///
+    /// The registers are always read from the chip, bypassing the shadow cache, so this suits a periodic "ensure
+    /// configured" task that repairs a configuration lost or altered behind the driver's back while keeping writes
+    /// to a minimum. Pending flags are left untouched.
+    ///
+    /// # Arguments
+    /// * `config` - The desired configuration.
+    ///
+    /// # Returns
+    /// * `Result<ConfigDelta, Error<E>>` - The bits that differed and have been corrected, `Error::InvalidInput` if
+    ///   the backup sampling time is out of range, or an error if the operation fails.
+    pub fn diff_config(&mut self, config: &Config) -> Result<ConfigDelta, Error<E>> {
+        if !config.is_valid() {
+            return Err(Error::InvalidInput);
+        }
+        self.repair_config(config)
+    }
+
+    /// Sets the configuration that `poll_events` re-verifies and repairs when it sees the voltage detect flag.
+    ///
+    /// Marginal backup supplies can corrupt the control registers during a brown-out, which the RX8900 reports by
+    /// setting VDET. Disabled (None) by default.
+    ///
+    /// # Arguments
+    /// * `config` - The configuration to restore after a brown-out, or None to only report VDET.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the configuration was taken, or `Error::InvalidInput`, leaving the previous
+    ///   one, if the backup sampling time is out of range.
+    pub fn set_reapply_on_vdet(&mut self, config: Option<Config>) -> Result<(), Error<E>> {
+        if config.is_some_and(|config| !config.is_valid()) {
+            return Err(Error::InvalidInput);
+        }
+        self.vdet_config = config;
         Ok(())
     }
 
-    /// Reads the current date and time from the RTC.
+    /// Writes the registers of a validated configuration that differ from the chip, as `diff_config` does.
+    pub(crate) fn repair_config(&mut self, config: &Config) -> Result<ConfigDelta, Error<E>> {
+        let mut data = [0; 3];
+        self.read_registers(RegisterTable::CompatibleExtensionRegister, &mut data)?;
+        let mut backup_function = [0; 1];
+        self.read_registers(RegisterTable::ExtendedBackupFunction, &mut backup_function)?;
+        let diff = |current: u8, (mask, data): (u8, u8)| (current ^ data) & mask;
+        let delta = ConfigDelta {
+            extension: diff(data[0], config.extension_register()),
+            control: diff(data[2], config.control_register()),
+            backup_function: diff(backup_function[0], config.backup_function_register()),
+        };
+        if delta.extension != 0 {
+            self.write_register(RegisterTable::CompatibleExtensionRegister, data[0] ^ delta.extension)?;
+        }
+        if delta.control != 0 {
+            self.write_register(RegisterTable::CompatibleControlRegister, data[2] ^ delta.control)?;
+        }
+        if delta.backup_function != 0 {
+            self.write_register(RegisterTable::ExtendedBackupFunction, backup_function[0] ^ delta.backup_function)?;
+        }
+        Ok(delta)
+    }
+
+    /// Runs a closure with the alarm, timer and update interrupts disabled, then restores their enable bits.
+    ///
+    /// Use it around a reconfiguration touching several registers, e.g. reprogramming the alarm or the timer, so
+    /// that /INT cannot assert on an intermediate state. The enable bits are restored even if the closure fails,
+    /// overwriting any enable bit the closure changed, so enable a new interrupt after the call; the other control
+    /// bits are left as the closure set them.
+    ///
+    /// # Arguments
+    /// * `f` - The closure, given the driver.
+    ///
+    /// # Returns
+    /// * `Result<T, R>` - The result of the closure, or an error if disabling or restoring the interrupts fails.
+    pub fn with_interrupts_disabled<T, R>(&mut self, f: impl FnOnce(&mut Self) -> Result<T, R>) -> Result<T, R>
+    where
+        R: From<Error<E>>,
+    {
+        // UIE, TIE and AIE
+        let mask = 0b00111000;
+        let saved = self.read_register(RegisterTable::CompatibleControlRegister).map_err(Error::Bus)? & mask;
+        self.update_register(RegisterTable::CompatibleControlRegister, mask, 0)?;
+        let result = f(self);
+        let restored = self.update_register(RegisterTable::CompatibleControlRegister, mask, saved);
+        let value = result?;
+        restored?;
+        Ok(value)
+    }
+
+    /// Updates the extension, flag and control registers with one burst read and one burst write.
+    ///
+    /// # Arguments
+    /// * `extension` - The mask of the extension register bits to replace and their new values.
+    /// * `clear_flags` - The flags to clear. The other flags are written as 1, which leaves them unchanged.
+    /// * `control` - The mask of the control register bits to replace and their new values.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the registers were successfully updated, or an error if the operation fails.
+    fn update_control_registers(&mut self, extension: (u8, u8), clear_flags: u8, control: (u8, u8)) -> Result<(), Error<E>> {
+        let mut data = [0; 3];
+        match (
+            self.shadow.get(RegisterTable::CompatibleExtensionRegister),
+            self.shadow.get(RegisterTable::CompatibleControlRegister),
+        ) {
+            // The unused bits of the flag register read as 0.
+            (Some(extension), Some(control)) => data = [extension, 0, control],
+            _ => self.read_registers(RegisterTable::CompatibleExtensionRegister, &mut data)?,
+        }
+        data[0] = data[0] & !extension.0 | extension.1 & extension.0;
+        data[1] = data[1] & !FLAG_BITS | FLAG_BITS & !clear_flags;
+        data[2] = data[2] & !control.0 | control.1 & control.0;
+        self.write_registers(RegisterTable::CompatibleExtensionRegister, &data)
+    }
+
+    /// Replaces the masked bits of a register with a single read-modify-write cycle.
+    ///
+    /// # Arguments
+    /// * `register` - The register to update.
+    /// * `mask` - The bits to replace.
+    /// * `data` - The new values of the masked bits.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the register was successfully updated, or an error if the operation fails.
+    fn update_register(&mut self, register: RegisterTable, mask: u8, data: u8) -> Result<(), Error<E>> {
+        let current = self.read_register_cached(register)?;
+        self.write_register(register, current & !mask | data & mask)
+    }
+
+    /// Reads the date and time in one burst and returns the register bytes along with the decoded value.
+    ///
+    /// # Returns
+    /// * `Result<([u8; 7], Option<NaiveDateTime>), E>` - The registers as returned by `read_time_raw` and the date
+    ///   and time decoded from them, None if a field is not valid BCD or out of range; or an error if the read fails.
+    pub fn datetime_with_raw(&mut self) -> Result<([u8; 7], Option<NaiveDateTime>), E> {
+        let raw = self.read_time_raw()?;
+        Ok((raw, datetime::decode_time(&raw)))
+    }
+
+    /// Reads the date and time until two consecutive reads agree, for noisy buses.
+    ///
+    /// A bit error on the bus, or a rollover between the transfer of two registers on a bus that splits the burst,
+    /// shows up as two reads that differ; the time is then read again, up to four times in total.
+    ///
+    /// # Returns
+    /// * `Result<NaiveDateTime, Error<E>>` - The current date and time, `Error::InvalidDeviceData` if no two
+    ///   consecutive reads agreed, `Error::InvalidTime` with the raw registers if they hold no valid date and time
+    ///   in the decode mode, or an error if a read fails.
+    pub fn datetime_coherent(&mut self) -> Result<NaiveDateTime, Error<E>> {
+        let mut previous = self.read_time_raw()?;
+        for _ in 1..COHERENT_READ_ATTEMPTS {
+            let raw = self.read_time_raw()?;
+            if raw == previous {
+                return self.decode_mode.decode_time_in(&raw, 2000).ok_or(Error::InvalidTime(raw));
+            }
+            previous = raw;
+        }
+        Err(Error::InvalidDeviceData)
+    }
+
+    /// Reads the date and time in one burst and decodes it according to the decode mode.
+    ///
+    /// # Returns
+    /// * `Result<DecodedTime, Error<E>>` - The date and time, flagged as clamped if `DecodeMode::Lenient` had to fix a
+    ///   field; `Error::InvalidTime` if a field is invalid in `DecodeMode::Strict`; or an error if the read fails.
+    pub fn datetime_decoded(&mut self) -> Result<DecodedTime, Error<E>> {
+        let raw = self.read_time_raw()?;
+        match self.decode_mode {
+            DecodeMode::Strict => datetime::decode_time(&raw)
+                .map(|datetime| DecodedTime { datetime, clamped: false })
+                .ok_or(Error::InvalidTime(raw)),
+            DecodeMode::Lenient => Ok(datetime::decode_time_lenient(&raw)),
+        }
+    }
+
+    /// Reads the DAY, MONTH and YEAR registers in one burst and decodes the date according to the decode mode.
+    ///
+    /// # Returns
+    /// * `Result<NaiveDate, Error<E>>` - The date, `Error::InvalidTime` if the registers do not hold a valid date,
+    ///   or an error if the read fails.
+    fn read_date(&mut self) -> Result<NaiveDate, Error<E>> {
+        // The time of day stays at midnight, so only the date registers decide.
+        let mut raw = [0; 7];
+        self.read_registers(RegisterTable::CompatibleDAY, &mut raw[4..])?;
+        let datetime = self.decode_mode.decode_time_in(&raw, 2000).ok_or(Error::InvalidTime(raw))?;
+        Ok(datetime.date())
+    }
+
+    /// Returns the day of the year of the current date.
+    ///
+    /// # Returns
+    /// * `Result<u32, Error<E>>` - The day of the year (1-366), `Error::InvalidTime` if the registers do not hold a
+    ///   valid date, or an error if the read fails.
+    pub fn ordinal(&mut self) -> Result<u32, Error<E>> {
+        Ok(self.read_date()?.ordinal())
+    }
+
+    /// Returns the ISO 8601 week of the current date.
+    ///
+    /// # Returns
+    /// * `Result<IsoWeek, Error<E>>` - The ISO week, whose year may differ from the calendar year in the first and
+    ///   last days of the year, `Error::InvalidTime` if the registers do not hold a valid date, or an error if the
+    ///   read fails.
+    pub fn iso_week(&mut self) -> Result<IsoWeek, Error<E>> {
+        Ok(self.read_date()?.iso_week())
+    }
+
+    /// Returns the number of days from the current date to a date.
+    ///
+    /// # Arguments
+    /// * `date` - The date to count to.
+    ///
+    /// # Returns
+    /// * `Result<i64, Error<E>>` - The number of days, negative if the date is in the past,
+    ///   `Error::InvalidTime` if the registers do not hold a valid date, or an error if the read fails.
+    pub fn days_until(&mut self, date: NaiveDate) -> Result<i64, Error<E>> {
+        Ok((date - self.read_date()?).num_days())
+    }
+
+    /// Returns the number of seconds from the current date and time to a date and time.
+    ///
+    /// # Arguments
+    /// * `datetime` - The `NaiveDateTime` or `DateTime<Tz>` to count to, converted to UTC first.
+    ///
+    /// # Returns
+    /// * `Result<i64, Error<E>>` - The number of seconds, negative if the date and time is in the past,
+    ///   `Error::InvalidTime` if the time registers hold no valid date and time, or an error if the read fails.
+    pub fn seconds_until(&mut self, datetime: impl IntoNaiveUtc) -> Result<i64, Error<E>> {
+        Ok((datetime.into_naive_utc() - self.datetime()?).num_seconds())
+    }
+
+    /// Returns whether a date is the current date.
+    ///
+    /// # Arguments
+    /// * `date` - The date to compare with the current date.
+    ///
+    /// # Returns
+    /// * `Result<bool, Error<E>>` - True if the date is today, `Error::InvalidTime` if the registers do not hold a
+    ///   valid date, or an error if the read fails.
+    pub fn is_today(&mut self, date: NaiveDate) -> Result<bool, Error<E>> {
+        Ok(self.read_date()? == date)
+    }
+
+    /// Reads the current date and time from the RTC as a UTC `DateTime`.
+    ///
+    /// # Returns
+    /// * `Result<DateTime<Utc>, Error<E>>` - The current date and time in UTC, `Error::InvalidTime` if the time
+    ///   registers hold no valid date and time, or an error if the read fails.
+    pub fn now_utc(&mut self) -> Result<DateTime<Utc>, Error<E>> {
+        Ok(self.datetime()?.and_utc())
+    }
+
+    /// Sets a full date and time, first waiting for the minute carry if it is close.
+    ///
+    /// When `set_burst_limit` splits the write of the time registers, a carry into the next minute between two
+    /// transactions can leave the clock one minute off. When the seconds register shows the clock within
+    /// `guard_seconds` of the next minute, this polls it every 10 ms until the carry has passed and adds the time
+    /// waited to `data`; the registers are then written as by `set_datetime`.
+    ///
+    /// # Arguments
+    /// * `data` - The `NaiveDateTime` or `DateTime<Tz>` containing the date and time to be set.
+    /// * `guard_seconds` - The guard window before the minute carry, in seconds (0-59); 0 never waits.
+    /// * `delay` - The delay provider used between two polls of the seconds register.
+    ///
+    /// # Returns
+    /// * `Result<NaiveDateTime, Error<E>>` - The date and time written, including the wait, `Error::InvalidInput` if
+    ///   the guard window is above 59 seconds or the date is outside 2000-2099, or an error if the operation fails.
+    pub fn set_datetime_safe<D: DelayMs<u32>>(
+        &mut self,
+        data: impl IntoNaiveUtc,
+        guard_seconds: u8,
+        delay: &mut D,
+    ) -> Result<NaiveDateTime, Error<E>> {
+        if guard_seconds > 59 {
+            return Err(Error::InvalidInput);
+        }
+        let mut waited_ms = 0;
+        // Bounded by the window and one more second, in case the clock is stopped.
+        while waited_ms <= (guard_seconds as u32 + 1) * 1000
+            && bcd::from_bcd(self.read_register(RegisterTable::CompatibleSEC)? & 0b01111111) >= 60 - guard_seconds
+        {
+            delay.delay_ms(10);
+            waited_ms += 10;
+        }
+        let data = data.into_naive_utc() + Duration::milliseconds(waited_ms as i64);
+        let data = data.with_nanosecond(0).unwrap_or(data);
+        self.set_datetime_in(data, 2000)?;
+        Ok(data)
+    }
+
+    /// Reads the current time as seconds since the Unix epoch, interpreting the clock as UTC.
+    ///
+    /// Leap seconds are counted as configured with `set_leap_seconds`; by default this is a POSIX timestamp.
+    ///
+    /// # Returns
+    /// * `Result<i64, Error<E>>` - The Unix timestamp, `Error::InvalidTime` if the time registers hold no valid date
+    ///   and time, or an error if the read fails.
+    pub fn timestamp(&mut self) -> Result<i64, Error<E>> {
+        let posix = self.datetime()?.and_utc().timestamp();
+        Ok(self.leap_seconds.to_counting(posix))
+    }
+
+    /// Reads a timestamp that never decreases, even when the wall clock is stepped backwards.
+    ///
+    /// The value follows the Unix timestamp of the clock plus an internal offset. Whenever the clock is found to
+    /// be behind the last value returned (for example after `set_datetime` moved it back), the offset grows so
+    /// that the timestamp continues from where it was, which keeps the order of logged events intact. The offset
+    /// lives in the driver and starts at 0, so the timestamp equals the Unix timestamp until the first backward
+    /// step.
+    ///
+    /// # Returns
+    /// * `Result<i64, Error<E>>` - The monotonic timestamp in seconds, `Error::InvalidTime` if the time registers hold
+    ///   no valid date and time, or an error if the read fails.
+    pub fn monotonic_timestamp(&mut self) -> Result<i64, Error<E>> {
+        let posix = self.datetime()?.and_utc().timestamp();
+        Ok(self.monotonic.stitch(posix))
+    }
+
+    /// Moves the clock to a Unix timestamp, applying the time set policy.
+    ///
+    /// # Arguments
+    /// * `seconds` - The requested time as seconds since the Unix epoch (UTC), within 2000-2099, counting leap
+    ///   seconds as configured with `set_leap_seconds`.
+    ///
+    /// # Returns
+    /// * `Result<i64, Error<E>>` - The correction applied in seconds, `Error::InvalidInput` if the time is out of
+    ///   range or rejected by the policy, or an error if the operation fails.
+    pub fn set_timestamp(&mut self, seconds: i64) -> Result<i64, Error<E>> {
+        self.set_timestamp_from(seconds, AdjustmentSource::Manual)
+    }
+
+    /// Moves the clock to a Unix timestamp obtained from the given source, applying the time set policy.
+    ///
+    /// This behaves like `set_timestamp`, and records the source in the adjustment history.
+    ///
+    /// # Arguments
+    /// * `seconds` - The requested time as seconds since the Unix epoch (UTC), within 2000-2099, counting leap
+    ///   seconds as configured with `set_leap_seconds`.
+    /// * `source` - Where the time came from.
     ///
     /// # Returns
-    /// * `Result<NaiveDateTime, E>` - The current date and time, or an error if the read fails.
-    pub fn datetime(&mut self) -> Result<NaiveDateTime, E> {
-        let sec = self.sec()?;
-        let min = self.min()?;
-        let hour = self.hour()?;
-        let day = self.day()?;
-        let month = self.month()?;
-        let yy = self.year()?;
-        let date = NaiveDate::from_ymd_opt(2000 + yy as i32, month as u32, day as u32).unwrap();
+    /// * `Result<i64, Error<E>>` - The correction applied in seconds, `Error::InvalidInput` if the time is out of
+    ///   range or rejected by the policy, or an error if the operation fails.
+    pub fn set_timestamp_from(&mut self, seconds: i64, source: AdjustmentSource) -> Result<i64, Error<E>> {
+        let posix = self.leap_seconds.to_posix(seconds);
+        self.set_posix_timestamp(posix, source)
+    }
+
+    /// Moves the clock to a POSIX timestamp, applying the time set policy.
+    fn set_posix_timestamp(&mut self, posix: i64, source: AdjustmentSource) -> Result<i64, Error<E>> {
+        let offset = posix - self.datetime()?.and_utc().timestamp();
+        self.adjust_seconds_from(offset, source)
+    }
 
-        let mut time = NaiveTime::from_hms_opt(hour as u32, min as u32, sec as u32);
-        if time.is_none() {
-            time = NaiveTime::from_hms_opt(0, 0, 0);
+    /// Moves the clock to the time of an NTP timestamp, applying the time set policy.
+    ///
+    /// # Arguments
+    /// * `seconds` - The seconds field of an NTP timestamp (seconds since 1900-01-01, era 0 or 1).
+    ///
+    /// # Returns
+    /// * `Result<i64, Error<E>>` - The correction applied in seconds, `Error::InvalidInput` if the time is out of
+    ///   range or rejected by the policy, or an error if the operation fails.
+    pub fn sync_from_ntp(&mut self, seconds: u32) -> Result<i64, Error<E>> {
+        // Seconds between 1900-01-01 and 1970-01-01.
+        const NTP_UNIX_OFFSET: i64 = 2_208_988_800;
+        let mut unix = seconds as i64 - NTP_UNIX_OFFSET;
+        if unix < 0 {
+            // Era 1 started on 2036-02-07.
+            unix += 1 << 32;
         }
-        Ok(NaiveDateTime::new(
-            date,
-            time.unwrap(),
-        ))
+        self.set_posix_timestamp(unix, AdjustmentSource::Ntp)
     }
 
-    /// Sets a full date and time in the RTC.
+    /// Moves the clock by a number of seconds, applying the time set policy.
     ///
     /// # Arguments
-    /// * `data` - The `NaiveDateTime` containing the date and time to be set.
+    /// * `offset` - The requested correction in seconds; positive values move the clock forward.
+    ///
+    /// # Returns
+    /// * `Result<i64, Error<E>>` - The correction applied in seconds, `Error::InvalidInput` if the resulting time is
+    ///   out of range or the policy rejects the offset, or an error if the operation fails.
+    pub fn adjust_seconds(&mut self, offset: i64) -> Result<i64, Error<E>> {
+        self.adjust_seconds_from(offset, AdjustmentSource::Manual)
+    }
+
+    /// Moves the clock by a number of seconds, applying the time set policy and recording the adjustment and the
+    /// synchronization.
+    fn adjust_seconds_from(&mut self, offset: i64, source: AdjustmentSource) -> Result<i64, Error<E>> {
+        let correction = self.time_set_policy.correction(offset).ok_or(Error::InvalidInput)?;
+        let now = self.datetime()?;
+        if correction != 0 {
+            let target = Duration::try_seconds(correction)
+                .and_then(|correction| now.checked_add_signed(correction))
+                .filter(|target| (2000..=2099).contains(&target.year()))
+                .ok_or(Error::InvalidInput)?;
+            self.set_datetime(target)?;
+            self.history.record(Adjustment { old: now, new: target, source });
+        }
+        self.quality.record(now, correction, source);
+        Ok(correction)
+    }
+
+    /// Moves the clock by a duration, applying the time set policy.
+    ///
+    /// The RTC has a resolution of one second, so the fractional part of `offset` is truncated.
+    ///
+    /// # Arguments
+    /// * `offset` - The requested correction as a `chrono`, `core` or `fugit` duration; `core` durations can only
+    ///   move the clock forward.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the date and time were successfully set, or an error if the write fails.
-    pub fn set_datetime(&mut self, data: NaiveDateTime) -> Result<(), E> {
-        let date = data.date();
-        let time = data.time();
-        self.set_year((date.year() % 100) as u8)?;
-        self.set_month(date.month() as u8)?;
-        self.set_day(date.day() as u8)?;
-        self.set_week(date.weekday())?;
-        self.set_hour(time.hour() as u8)?;
-        self.set_min(time.minute() as u8)?;
-        self.set_sec(time.second() as u8)?;
+    /// * `Result<i64, Error<E>>` - The correction applied in seconds, `Error::InvalidInput` if the resulting time is
+    ///   out of range or the policy rejects the offset, or an error if the operation fails.
+    pub fn adjust(&mut self, offset: impl IntoRtcDuration) -> Result<i64, Error<E>> {
+        let offset = offset.into_rtc_duration().ok_or(Error::InvalidInput)?;
+        self.adjust_seconds(offset.num_seconds())
+    }
+
+    /// Sets the date and time only if the voltage low flag shows that the clock lost its time.
+    ///
+    /// When VLF is set, the date and time are programmed and VLF is cleared; otherwise the clock is left running
+    /// untouched. This is a convenient default for devices without a network time source, typically used with
+    /// the firmware build time (see `set_datetime_from_build_time!`).
+    ///
+    /// # Arguments
+    /// * `data` - The `NaiveDateTime` or `DateTime<Tz>` to program if the clock is invalid.
+    ///
+    /// # Returns
+    /// * `Result<bool, Error<E>>` - True if the date and time were programmed, false if the clock was valid,
+    ///   `Error::InvalidInput` if the year is outside 2000-2099, or an error if the operation fails.
+    pub fn set_if_invalid(&mut self, data: impl IntoNaiveUtc) -> Result<bool, Error<E>> {
+        if !self.vlf()? {
+            return Ok(false);
+        }
+        self.set_datetime(data)?;
+        // VLF
+        self.clear_flags(0b00000010)?;
+        Ok(true)
+    }
+
+    /// Checks that the clock is not earlier than a floor, such as the firmware build time.
+    ///
+    /// A clock that was reset reads a time before the firmware was built, which would make certificates look not
+    /// yet valid. Call this before a TLS handshake and fall back to a network time source on error:
+    ///
+    /// ```ignore
+    /// let now = rx8900.require_at_least(rx8900::build_time())?;
+    /// ```
+    ///
+    /// # Arguments
+    /// * `floor` - The earliest plausible time, as a `NaiveDateTime` (UTC) or `DateTime<Tz>`.
+    ///
+    /// # Returns
+    /// * `Result<NaiveDateTime, Error<E>>` - The current date and time, `Error::ClockTooEarly` if it is earlier
+    ///   than the floor, or an error if the read fails.
+    pub fn require_at_least(&mut self, floor: impl IntoNaiveUtc) -> Result<NaiveDateTime, Error<E>> {
+        let now = self.datetime()?;
+        if now < floor.into_naive_utc() {
+            return Err(Error::ClockTooEarly);
+        }
+        Ok(now)
+    }
+
+    /// Returns a blocking iterator yielding the current date and time once per second.
+    ///
+    /// The update interrupt is switched to once per second and the update flag is cleared. Each step of the
+    /// iterator then polls the update flag every 10 ms until the next second starts, clears it, and reads the date
+    /// and time, so logging loops stay locked to the RTC instead of counting delay loops.
+    ///
+    /// # Arguments
+    /// * `delay` - The delay provider used between two polls of the update flag.
+    ///
+    /// # Returns
+    /// * `Result<SecondsIter<'_, I2C, D>, Error<E>>` - The iterator, or an error if the setup fails.
+    pub fn seconds_iter<'a, D: DelayMs<u32>>(&'a mut self, delay: &'a mut D) -> Result<SecondsIter<'a, I2C, D>, Error<E>> {
+        self.set_update_interrupt_type(UpdateInterruptType::EverySecond)?;
+        // UF
+        self.clear_flags(0b00100000)?;
+        Ok(SecondsIter::new(self, delay))
+    }
+
+    /// Returns a blocking iterator yielding timestamps spaced exactly `interval` apart, paced by the wake-up timer.
+    ///
+    /// The countdown timer is started with `start_countdown`, and each step of the iterator waits for the timer
+    /// flag, clears it, and yields the previous timestamp plus `interval`. The first timestamp is the time read
+    /// when the timer was started plus `interval`, so a sampling loop stays phase-locked to the RTC instead of
+    /// accumulating the jitter of its own processing time.
+    ///
+    /// # Arguments
+    /// * `interval` - The interval between two timestamps, as any `IntoRtcDuration`; see `start_countdown` for the
+    ///   supported values.
+    /// * `delay` - The delay provider used between two polls of the timer flag.
+    ///
+    /// # Returns
+    /// * `Result<Every<'_, I2C, D>, Error<E>>` - The iterator, `Error::InvalidInput` if the interval cannot be
+    ///   counted down by the timer, or an error if the setup fails.
+    #[cfg(feature = "timer")]
+    pub fn every<'a, D: DelayMs<u32>>(
+        &'a mut self,
+        interval: impl IntoRtcDuration,
+        delay: &'a mut D,
+    ) -> Result<Every<'a, I2C, D>, Error<E>> {
+        let interval = interval.into_rtc_duration().ok_or(Error::InvalidInput)?;
+        self.start_countdown(interval)?;
+        let start = self.datetime()?;
+        Ok(Every::new(self, delay, start, interval))
+    }
+
+    /// Starts the wake-up timer so that it expires every `duration`.
+    ///
+    /// The source clock and counter value are chosen to count down exactly `duration`, preferring the second and
+    /// minute sources which are synchronized with the clock update. The timer reloads itself and raises the timer
+    /// flag each time it expires, until it is disabled with `reset_timer_enable`.
+    ///
+    /// # Arguments
+    /// * `duration` - A `chrono`, `core` or `fugit` duration that is a whole number of 1/4096 s, 1/64 s, seconds or
+    ///   minutes fitting the 12-bit counter (at most 4095 minutes).
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the timer was started, `Error::InvalidInput` if the duration cannot be
+    ///   counted down exactly, `Error::BusSaturated` if handling its interrupts would take more than half of the
+    ///   bus time at the frequency set with `set_bus_frequency_hint`, or an error if the operation fails.
+    #[cfg(feature = "timer")]
+    pub fn start_countdown(&mut self, duration: impl IntoRtcDuration) -> Result<(), Error<E>> {
+        let duration = duration.into_rtc_duration().ok_or(Error::InvalidInput)?;
+        let (source, count) = SourceClock::countdown(duration).ok_or(Error::InvalidInput)?;
+        self.check_bus_load(duration)?;
+        self.start_timer(source, count)?;
         Ok(())
     }
 
+    /// Restarts the wake-up timer with a source clock and counter value, clearing the timer flag.
+    ///
+    /// # Arguments
+    /// * `source` - The source clock of the countdown.
+    /// * `count` - The counter value (1-4095).
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the timer was started, or an error if the operation fails.
+    #[cfg(feature = "timer")]
+    pub(crate) fn start_timer(&mut self, source: SourceClock, count: u16) -> Result<(), Error<E>> {
+        self.set_te(false)?;
+        self.set_source_clock(source)?;
+        self.write_timer_counter(count)?;
+        // TF
+        self.clear_flags(0b00010000)?;
+        self.set_te(true)
+    }
+
+    /// Arms the alarm to assert the INT pin at the given date and time.
+    ///
+    /// The alarm is programmed as a day alarm matching the day of the month, hour and minute,
+    /// the alarm flag is cleared and the alarm interrupt is enabled. The RX8900 alarm has a
+    /// resolution of one minute, so the seconds of `data` are ignored and the alarm fires at the
+    /// start of the given minute. This is intended to be called right before the MCU enters a
+    /// low-power state that is woken up by the INT pin. A time closer than `min_alarm_interval` to the
+    /// previously armed one is deferred.
+    ///
+    /// # Arguments
+    /// * `data` - The `NaiveDateTime` at which the alarm should fire.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the alarm was successfully armed, or an error if the operation fails.
+    #[cfg(feature = "alarm")]
+    pub fn sleep_until(&mut self, data: NaiveDateTime) -> Result<(), Error<E>> {
+        let data = self.alarm_not_before(data);
+        self.last_alarm = Some(data);
+        // The alarm registers must not be updated while AIE is set.
+        self.set_aie(false)?;
+        self.clear_flag(Flag::Alarm)?;
+        self.set_alarm_type(AlarmType::DayAlarm)?;
+        // AE (bit 7) cleared: every alarm register takes part in the comparison.
+        self.write_register(RegisterTable::CompatibleMinAlarm, bcd::to_bcd(data.minute() as u8))?;
+        self.write_register(RegisterTable::CompatibleHourAlarm, bcd::to_bcd(data.hour() as u8))?;
+        self.write_register(RegisterTable::CompatibleWeekDayAlarm, bcd::to_bcd(data.day() as u8))?;
+        self.set_aie(true)
+    }
+
+    /// Arms the alarm and returns a guard that disarms it again when dropped.
+    ///
+    /// The alarm registers are written with the alarm interrupt disabled, the alarm flag is cleared and the alarm
+    /// interrupt is enabled. Dropping the returned `ArmedAlarm` disables the interrupt and clears the flag, so the
+    /// alarm cannot be forgotten once the operation it belongs to is cancelled.
+    ///
+    /// # Arguments
+    /// * `config` - The minute, hour and day at which the alarm fires.
+    ///
+    /// # Returns
+    /// * `Result<ArmedAlarm<'_, I2C, E>, Error<E>>` - The guard, `Error::InvalidInput` if a field of `config` is out
+    ///   of range or the alarm would fire more often than `min_alarm_interval`, or an error if the operation fails.
+    #[cfg(feature = "alarm")]
+    pub fn arm_alarm(&mut self, config: &AlarmConfig) -> Result<ArmedAlarm<'_, I2C, E>, Error<E>> {
+        if !config.is_valid() || !self.alarm_interval_allowed(config) {
+            return Err(Error::InvalidInput);
+        }
+        self.write_alarm(config)?;
+        self.set_aie(true)?;
+        Ok(ArmedAlarm::new(self))
+    }
+
     /// Sets or clears a specific bit in a register.
     ///
     /// # Arguments
@@ -1004,9 +2155,9 @@ where
     /// * `data` - Boolean value to set (`true`) or clear (`false`) the bit.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the bit was successfully modified, or an error if the operation fails.
-    fn set_bit(&mut self, register: RegisterTable, bit: u8, data: bool) -> Result<(), E> {
-        let current = self.read_register(register)?;
+    /// * `Result<(), Error<E>>` - Ok if the bit was successfully modified, or an error if the operation fails.
+    fn set_bit(&mut self, register: RegisterTable, bit: u8, data: bool) -> Result<(), Error<E>> {
+        let current = self.read_register_cached(register)?;
         let data = current & !(1 << bit) | (data as u8) << bit;
         self.write_register(register, data)
     }
@@ -1017,8 +2168,9 @@ where
     /// * `data` - The desired alarm type.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the alarm type was successfully set, or an error if the operation fails.
-    pub fn set_alarm_type(&mut self, data: AlarmType) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the alarm type was successfully set, or an error if the operation fails.
+    #[cfg(feature = "alarm")]
+    pub fn set_alarm_type(&mut self, data: AlarmType) -> Result<(), Error<E>> {
         self.set_wada(data == AlarmType::DayAlarm)
     }
 
@@ -1028,8 +2180,8 @@ where
     /// * `data` - The value to set for the test bit.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the test bit was successfully set, or an error if the operation fails.
-    pub fn set_test(&mut self, data: bool) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the test bit was successfully set, or an error if the operation fails.
+    pub fn set_test(&mut self, data: bool) -> Result<(), Error<E>> {
         self.set_bit(RegisterTable::CompatibleExtensionRegister, 7, data)
     }
 
@@ -1039,8 +2191,9 @@ where
     /// * `data` - The value to set for the week alarm bit.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the week alarm bit was successfully set, or an error if the operation fails.
-    pub fn set_wada(&mut self, data: bool) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the week alarm bit was successfully set, or an error if the operation fails.
+    #[cfg(feature = "alarm")]
+    pub fn set_wada(&mut self, data: bool) -> Result<(), Error<E>> {
         self.set_bit(RegisterTable::CompatibleExtensionRegister, 6, data)
     }
 
@@ -1050,8 +2203,8 @@ where
     /// * `data` - The desired update interrupt type.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the update interrupt type was successfully set, or an error if the operation fails.
-    pub fn set_update_interrupt_type(&mut self, data: UpdateInterruptType) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the update interrupt type was successfully set, or an error if the operation fails.
+    pub fn set_update_interrupt_type(&mut self, data: UpdateInterruptType) -> Result<(), Error<E>> {
         self.set_usel(data == UpdateInterruptType::EveryMinute)
     }
 
@@ -1061,8 +2214,8 @@ where
     /// * `data` - The value to set for the update interrupt selection bit.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the update interrupt selection bit was successfully set, or an error if the operation fails.
-    pub fn set_usel(&mut self, data: bool) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the update interrupt selection bit was successfully set, or an error if the operation fails.
+    pub fn set_usel(&mut self, data: bool) -> Result<(), Error<E>> {
         self.set_bit(RegisterTable::CompatibleExtensionRegister, 5, data)
     }
 
@@ -1072,24 +2225,27 @@ where
     /// * `data` - True to enable the timer, false to disable it.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the timer enable bit was successfully set or cleared, or an error if the operation fails.
-    pub fn set_te(&mut self, data: bool) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the timer enable bit was successfully set or cleared, or an error if the operation fails.
+    #[cfg(feature = "timer")]
+    pub fn set_te(&mut self, data: bool) -> Result<(), Error<E>> {
         self.set_bit(RegisterTable::CompatibleExtensionRegister, 4, data)
     }
 
     /// Sets the timer enable bit in the extension register to enable the timer.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the timer enable bit was successfully set, or an error if the operation fails.
-    pub fn set_timer_enable(&mut self) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the timer enable bit was successfully set, or an error if the operation fails.
+    #[cfg(feature = "timer")]
+    pub fn set_timer_enable(&mut self) -> Result<(), Error<E>> {
         self.set_te(true)
     }
 
     /// Resets the timer enable bit in the extension register to disable the timer.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the timer enable bit was successfully reset, or an error if the operation fails.
-    pub fn reset_timer_enable(&mut self) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the timer enable bit was successfully reset, or an error if the operation fails.
+    #[cfg(feature = "timer")]
+    pub fn reset_timer_enable(&mut self) -> Result<(), Error<E>> {
         self.set_te(false)
     }
 
@@ -1099,8 +2255,8 @@ where
     /// * `data` - The value to set for the fsel1 bit.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the fsel1 bit was successfully set, or an error if the operation fails.
-    pub fn set_fsel1(&mut self, data: bool) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the fsel1 bit was successfully set, or an error if the operation fails.
+    pub fn set_fsel1(&mut self, data: bool) -> Result<(), Error<E>> {
         self.set_bit(RegisterTable::CompatibleExtensionRegister, 3, data)
     }
 
@@ -1110,22 +2266,25 @@ where
     /// * `data` - The value to set for the fsel0 bit.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the fsel0 bit was successfully set, or an error if the operation fails.
-    pub fn set_fsel0(&mut self, data: bool) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the fsel0 bit was successfully set, or an error if the operation fails.
+    pub fn set_fsel0(&mut self, data: bool) -> Result<(), Error<E>> {
         self.set_bit(RegisterTable::CompatibleExtensionRegister, 2, data)
     }
 
     /// Configures the fout frequency selection bits based on the provided data.
     ///
     /// # Arguments
-    /// * `data` - The fout frequency configuration as a byte where the relevant bits will be set.
+    /// * `data` - The fout frequency selection bits (0-3).
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the fout frequency was successfully configured, or an error if the operation fails.
-    pub fn set_fsel(&mut self, data: u8) -> Result<(), E> {
-        let current = self.read_register(RegisterTable::CompatibleExtensionRegister)?;
-        let data = current & 0b11110011 | (data as u8) << 2;
-        self.write_register(RegisterTable::CompatibleExtensionRegister, data)
+    /// * `Result<(), Error<E>>` - Ok if the fout frequency was successfully configured, `Error::InvalidInput` if the
+    ///   value is out of range, or an error if the operation fails.
+    pub fn set_fsel(&mut self, data: u8) -> Result<(), Error<E>> {
+        if data > 0b11 {
+            return Err(Error::InvalidInput);
+        }
+        // FSEL
+        self.update_register(RegisterTable::CompatibleExtensionRegister, 0b00001100, data << 2)
     }
 
     /// Sets the frequency output (fout) control.
@@ -1134,9 +2293,10 @@ where
     /// * `frequency` - The fout frequency setting from the `FoutFrequency` enum.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the fout frequency was successfully set, or an error if the operation fails.
-    pub fn set_fout_frequency(&mut self, data: FoutFrequency) -> Result<(), E> {
-        self.set_fsel(data as u8)
+    /// * `Result<(), Error<E>>` - Ok if the fout frequency was successfully set, or an error if the operation fails.
+    pub fn set_fout_frequency(&mut self, data: FoutFrequency) -> Result<(), Error<E>> {
+        // FSEL
+        self.update_register(RegisterTable::CompatibleExtensionRegister, 0b00001100, (data as u8) << 2)
     }
 
     /// Enables or disables the specified source clock.
@@ -1145,9 +2305,10 @@ where
     /// * `source_clock` - The source clock setting from the `SourceClock` enum.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the source clock was successfully set, or an error if the operation fails.
-    pub fn set_source_clock(&mut self, data: SourceClock) -> Result<(), E> {
-        let current = self.read_register(RegisterTable::CompatibleExtensionRegister)?;
+    /// * `Result<(), Error<E>>` - Ok if the source clock was successfully set, or an error if the operation fails.
+    #[cfg(feature = "timer")]
+    pub fn set_source_clock(&mut self, data: SourceClock) -> Result<(), Error<E>> {
+        let current = self.read_register_cached(RegisterTable::CompatibleExtensionRegister)?;
         let data = current & 0b11111100 | (data as u8) << 0;
         self.write_register(RegisterTable::CompatibleExtensionRegister, data)
     }
@@ -1158,8 +2319,9 @@ where
     /// * `data` - The value to set for the timer selection bit 1.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the timer selection bit 1 was successfully set, or an error if the operation fails.
-    pub fn set_tsel1(&mut self, data: bool) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the timer selection bit 1 was successfully set, or an error if the operation fails.
+    #[cfg(feature = "timer")]
+    pub fn set_tsel1(&mut self, data: bool) -> Result<(), Error<E>> {
         self.set_bit(RegisterTable::CompatibleExtensionRegister, 1, data)
     }
 
@@ -1169,108 +2331,133 @@ where
     /// * `data` - The value to set for the timer selection bit 0.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the timer selection bit 0 was successfully set, or an error if the operation fails.
-    pub fn set_tsel0(&mut self, data: bool) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the timer selection bit 0 was successfully set, or an error if the operation fails.
+    #[cfg(feature = "timer")]
+    pub fn set_tsel0(&mut self, data: bool) -> Result<(), Error<E>> {
         self.set_bit(RegisterTable::CompatibleExtensionRegister, 0, data)
     }
 
     /// Configures the overall timer selection based on the provided bits.
     ///
     /// # Arguments
-    /// * `data` - The timer selection configuration as a byte where the relevant bits will be set.
+    /// * `data` - The timer selection bits (0-3).
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the timer selection was successfully configured, or an error if the operation fails.
-    pub fn set_tsel(&mut self, data: u8) -> Result<(), E> {
-        let current = self.read_register(RegisterTable::CompatibleExtensionRegister)?;
-        let data = current & 0b11111100 | (data as u8) << 0;
-        self.write_register(RegisterTable::CompatibleExtensionRegister, data)
+    /// * `Result<(), Error<E>>` - Ok if the timer selection was successfully configured, `Error::InvalidInput` if the
+    ///   value is out of range, or an error if the operation fails.
+    #[cfg(feature = "timer")]
+    pub fn set_tsel(&mut self, data: u8) -> Result<(), Error<E>> {
+        if data > 0b11 {
+            return Err(Error::InvalidInput);
+        }
+        // TSEL
+        self.update_register(RegisterTable::CompatibleExtensionRegister, 0b00000011, data)
     }
 
     /// Resets the update flag by setting the specific bit to false.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the update flag was successfully reset, or an error if the operation fails.
-    pub fn set_uf(&mut self) -> Result<(), E> {
-        self.set_bit(RegisterTable::CompatibleFlagRegister, 5, false)
+    /// * `Result<(), Error<E>>` - Ok if the update flag was successfully reset, or an error if the operation fails.
+    #[deprecated(since = "0.2.0", note = "use `clear_flag(Flag::Update)`, which cannot clear other flags raised meanwhile")]
+    pub fn set_uf(&mut self) -> Result<(), Error<E>> {
+        self.clear_flag(Flag::Update)
     }
 
     /// Alias for `set_uf`.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the update flag was successfully set or cleared, or an error if the operation fails.
-    pub fn set_update_flag(&mut self) -> Result<(), E> {
-        self.set_uf()
+    /// * `Result<(), Error<E>>` - Ok if the update flag was successfully set or cleared, or an error if the operation fails.
+    #[deprecated(since = "0.2.0", note = "use `clear_flag(Flag::Update)`, which cannot clear other flags raised meanwhile")]
+    pub fn set_update_flag(&mut self) -> Result<(), Error<E>> {
+        self.clear_flag(Flag::Update)
     }
 
-     /// Sets or clears the timer flag.
+    /// Clears the timer flag, or leaves it unchanged.
+    ///
+    /// The chip only lets the host clear a flag, so `true` writes nothing that changes the flag register. Other flags
+    /// are left unchanged even if the device raises them meanwhile.
     ///
     /// # Arguments
-    /// * `data` - True to set the timer flag, false to clear it.
+    /// * `data` - False to clear the timer flag; true leaves it unchanged.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the timer flag was successfully set or cleared, or an error if the operation fails.
-    pub fn set_tf(&mut self, data: bool) -> Result<(), E> {
-        self.set_bit(RegisterTable::CompatibleFlagRegister, 4, data)
+    /// * `Result<(), Error<E>>` - Ok if the flag register was successfully written, or an error if the operation fails.
+    #[cfg(feature = "timer")]
+    #[deprecated(since = "0.2.0", note = "use `clear_flag(Flag::Timer)`; the timer flag cannot be set by the host")]
+    pub fn set_tf(&mut self, data: bool) -> Result<(), Error<E>> {
+        self.clear_flags(if data { 0 } else { Flag::Timer as u8 })
     }
 
     /// Resets the timer flag.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the timer flag was successfully reset, or an error if the operation fails.
-    pub fn reset_timer_flag(&mut self) -> Result<(), E> {
-        self.set_tf(false)
+    /// * `Result<(), Error<E>>` - Ok if the timer flag was successfully reset, or an error if the operation fails.
+    #[cfg(feature = "timer")]
+    #[deprecated(since = "0.2.0", note = "use `clear_flag(Flag::Timer)`, which cannot clear other flags raised meanwhile")]
+    pub fn reset_timer_flag(&mut self) -> Result<(), Error<E>> {
+        self.clear_flag(Flag::Timer)
     }
 
-    /// Sets or clears the alarm flag in the flag register.
+    /// Clears the alarm flag, or leaves it unchanged.
+    ///
+    /// The chip only lets the host clear a flag, so `true` writes nothing that changes the flag register. Other flags
+    /// are left unchanged even if the device raises them meanwhile.
     ///
     /// # Arguments
-    /// * `data` - True to set the alarm flag, false to clear it.
+    /// * `data` - False to clear the alarm flag; true leaves it unchanged.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the alarm flag was successfully set or cleared, or an error if the operation fails.
-    pub fn set_af(&mut self, data: bool) -> Result<(), E> {
-        self.set_bit(RegisterTable::CompatibleFlagRegister, 3, data)
+    /// * `Result<(), Error<E>>` - Ok if the flag register was successfully written, or an error if the operation fails.
+    #[cfg(feature = "alarm")]
+    #[deprecated(since = "0.2.0", note = "use `clear_flag(Flag::Alarm)`; the alarm flag cannot be set by the host")]
+    pub fn set_af(&mut self, data: bool) -> Result<(), Error<E>> {
+        self.clear_flags(if data { 0 } else { Flag::Alarm as u8 })
     }
 
     /// Resets the alarm flag.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the alarm flag was successfully reset, or an error if the operation fails.
-    pub fn reset_alarm_flag(&mut self) -> Result<(), E> {
-        self.set_af(false)
+    /// * `Result<(), Error<E>>` - Ok if the alarm flag was successfully reset, or an error if the operation fails.
+    #[cfg(feature = "alarm")]
+    #[deprecated(since = "0.2.0", note = "use `clear_flag(Flag::Alarm)`, which cannot clear other flags raised meanwhile")]
+    pub fn reset_alarm_flag(&mut self) -> Result<(), Error<E>> {
+        self.clear_flag(Flag::Alarm)
     }
 
     /// Sets or clears the voltage low flag in the flag register.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the voltage low flag was successfully set or cleared, or an error if the operation fails.
-    pub fn set_vlf(&mut self) -> Result<(), E> {
-        self.set_bit(RegisterTable::CompatibleFlagRegister, 1, false)
+    /// * `Result<(), Error<E>>` - Ok if the voltage low flag was successfully set or cleared, or an error if the operation fails.
+    #[deprecated(since = "0.2.0", note = "use `clear_flag(Flag::VoltageLow)`, which cannot clear other flags raised meanwhile")]
+    pub fn set_vlf(&mut self) -> Result<(), Error<E>> {
+        self.clear_flag(Flag::VoltageLow)
     }
 
     /// Alias for `set_vlf`.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the voltage low flag was successfully set or cleared, or an error if the operation fails.
-    pub fn set_voltage_low_flag(&mut self) -> Result<(), E> {
-        self.set_vlf()
+    /// * `Result<(), Error<E>>` - Ok if the voltage low flag was successfully set or cleared, or an error if the operation fails.
+    #[deprecated(since = "0.2.0", note = "use `clear_flag(Flag::VoltageLow)`, which cannot clear other flags raised meanwhile")]
+    pub fn set_voltage_low_flag(&mut self) -> Result<(), Error<E>> {
+        self.clear_flag(Flag::VoltageLow)
     }
 
     /// Sets or clears the voltage detect flag in the flag register.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the voltage detect flag was successfully set or cleared, or an error if the operation fails.
-    pub fn set_vdet(&mut self) -> Result<(), E> {
-        self.set_bit(RegisterTable::CompatibleFlagRegister, 0, false)
+    /// * `Result<(), Error<E>>` - Ok if the voltage detect flag was successfully set or cleared, or an error if the operation fails.
+    #[deprecated(since = "0.2.0", note = "use `clear_flag(Flag::VoltageDetect)`, which cannot clear other flags raised meanwhile")]
+    pub fn set_vdet(&mut self) -> Result<(), Error<E>> {
+        self.clear_flag(Flag::VoltageDetect)
     }
 
     /// Alias for `set_vdet`.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the voltage detect flag was successfully set or cleared, or an error if the operation fails.
-    pub fn set_voltage_detect_flag(&mut self) -> Result<(), E> {
-        self.set_vdet()
+    /// * `Result<(), Error<E>>` - Ok if the voltage detect flag was successfully set or cleared, or an error if the operation fails.
+    #[deprecated(since = "0.2.0", note = "use `clear_flag(Flag::VoltageDetect)`, which cannot clear other flags raised meanwhile")]
+    pub fn set_voltage_detect_flag(&mut self) -> Result<(), Error<E>> {
+        self.clear_flag(Flag::VoltageDetect)
     }
 
     /// Sets or clears the first compensation interval selection bit in the control register.
@@ -1279,8 +2466,8 @@ where
     /// * `data` - True to set the bit, false to clear it.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the compensation interval bit was successfully set or cleared, or an error if the operation fails.
-    pub fn set_csel1(&mut self, data: bool) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the compensation interval bit was successfully set or cleared, or an error if the operation fails.
+    pub fn set_csel1(&mut self, data: bool) -> Result<(), Error<E>> {
         self.set_bit(RegisterTable::CompatibleControlRegister, 7, data)
     }
 
@@ -1290,22 +2477,25 @@ where
     /// * `data` - True to set the bit, false to clear it.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the compensation interval bit was successfully set or cleared, or an error if the operation fails.
-    pub fn set_csel0(&mut self, data: bool) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the compensation interval bit was successfully set or cleared, or an error if the operation fails.
+    pub fn set_csel0(&mut self, data: bool) -> Result<(), Error<E>> {
         self.set_bit(RegisterTable::CompatibleControlRegister, 6, data)
     }
 
     /// Configures the compensation interval type in the control register.
     ///
     /// # Arguments
-    /// * `data` - The compensation interval configuration as a byte where the relevant bits will be set.
+    /// * `data` - The compensation interval selection bits (0-3).
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the compensation interval was successfully configured, or an error if the operation fails.
-    pub fn set_csel(&mut self, data: u8) -> Result<(), E> {
-        let current = self.read_register(RegisterTable::CompatibleControlRegister)?;
-        let data = (current & 0b00111111) | (data << 6);
-        self.write_register(RegisterTable::CompatibleControlRegister, data)
+    /// * `Result<(), Error<E>>` - Ok if the compensation interval was successfully configured, `Error::InvalidInput`
+    ///   if the value is out of range, or an error if the operation fails.
+    pub fn set_csel(&mut self, data: u8) -> Result<(), Error<E>> {
+        if data > 0b11 {
+            return Err(Error::InvalidInput);
+        }
+        // CSEL
+        self.update_register(RegisterTable::CompatibleControlRegister, 0b11000000, data << 6)
     }
 
     /// Enables or disables the compensation interval type setting.
@@ -1314,9 +2504,25 @@ where
     /// * `data` - The compensation interval from the `CompensationIntervalType` enum.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the compensation interval was successfully set, or an error if the operation fails.
-    pub fn set_compensation_interval_type(&mut self, data: CompensationIntervalType) -> Result<(), E> {
-        self.set_csel(data as u8)
+    /// * `Result<(), Error<E>>` - Ok if the compensation interval was successfully set, or an error if the operation fails.
+    pub fn set_compensation_interval_type(&mut self, data: CompensationIntervalType) -> Result<(), Error<E>> {
+        // CSEL
+        self.update_register(RegisterTable::CompatibleControlRegister, 0b11000000, (data as u8) << 6)
+    }
+
+    /// Sets the compensation interval to the supported value closest to the given duration.
+    ///
+    /// # Arguments
+    /// * `duration` - The requested interval as a `chrono`, `core` or `fugit` duration.
+    ///
+    /// # Returns
+    /// * `Result<CompensationIntervalType, Error<E>>` - The interval actually chosen, `Error::InvalidInput` if the
+    ///   duration is out of range, or an error if the operation fails.
+    pub fn set_compensation_interval(&mut self, duration: impl IntoRtcDuration) -> Result<CompensationIntervalType, Error<E>> {
+        let duration = duration.into_rtc_duration().ok_or(Error::InvalidInput)?;
+        let interval = CompensationIntervalType::from_duration(duration).ok_or(Error::InvalidInput)?;
+        self.set_compensation_interval_type(interval)?;
+        Ok(interval)
     }
 
     /// Sets or clears the update interrupt enable bit.
@@ -1325,8 +2531,8 @@ where
     /// * `data` - True to enable the update interrupt, false to disable it.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the update interrupt enable bit was successfully set or cleared, or an error if the operation fails.
-    pub fn set_uie(&mut self, data: bool) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the update interrupt enable bit was successfully set or cleared, or an error if the operation fails.
+    pub fn set_uie(&mut self, data: bool) -> Result<(), Error<E>> {
         self.set_bit(RegisterTable::CompatibleControlRegister, 5, data)
     }
 
@@ -1336,8 +2542,8 @@ where
     /// * `enable` - Set to `true` to enable update interrupts, or `false` to disable them.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the setting was successfully applied, or an error if the operation fails.
-    pub fn set_update_interrupt_enable(&mut self, data: bool) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the setting was successfully applied, or an error if the operation fails.
+    pub fn set_update_interrupt_enable(&mut self, data: bool) -> Result<(), Error<E>> {
         self.set_uie(data)
     }
 
@@ -1347,24 +2553,27 @@ where
     /// * `data` - True to enable the timer interrupt, false to disable it.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the timer interrupt enable bit was successfully set or cleared, or an error if the operation fails.
-    pub fn set_tie(&mut self, data: bool) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the timer interrupt enable bit was successfully set or cleared, or an error if the operation fails.
+    #[cfg(feature = "timer")]
+    pub fn set_tie(&mut self, data: bool) -> Result<(), Error<E>> {
         self.set_bit(RegisterTable::CompatibleControlRegister, 4, data)
     }
 
     /// Enables timer interrupts.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the setting was successfully applied, or an error if the operation fails.
-    pub fn set_timer_interrupt_enable(&mut self) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the setting was successfully applied, or an error if the operation fails.
+    #[cfg(feature = "timer")]
+    pub fn set_timer_interrupt_enable(&mut self) -> Result<(), Error<E>> {
         self.set_tie(true)
     }
 
     /// Disables timer interrupts.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the setting was successfully applied, or an error if the operation fails.
-    pub fn reset_timer_interrupt_enable(&mut self) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the setting was successfully applied, or an error if the operation fails.
+    #[cfg(feature = "timer")]
+    pub fn reset_timer_interrupt_enable(&mut self) -> Result<(), Error<E>> {
         self.set_tie(false)
     }
 
@@ -1374,24 +2583,27 @@ where
     /// * `data` - True to enable the alarm interrupt, false to disable it.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the alarm interrupt enable bit was successfully set or cleared, or an error if the operation fails.
-    pub fn set_aie(&mut self, data: bool) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the alarm interrupt enable bit was successfully set or cleared, or an error if the operation fails.
+    #[cfg(feature = "alarm")]
+    pub fn set_aie(&mut self, data: bool) -> Result<(), Error<E>> {
         self.set_bit(RegisterTable::CompatibleControlRegister, 3, data)
     }
 
     /// Enables alarm interrupts.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the setting was successfully applied, or an error if the operation fails.
-    pub fn set_alarm_interrupt_enable(&mut self) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the setting was successfully applied, or an error if the operation fails.
+    #[cfg(feature = "alarm")]
+    pub fn set_alarm_interrupt_enable(&mut self) -> Result<(), Error<E>> {
         self.set_aie(true)
     }
 
     /// Disables alarm interrupts.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the setting was successfully applied, or an error if the operation fails.
-    pub fn reset_alarm_interrupt_enable(&mut self) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the setting was successfully applied, or an error if the operation fails.
+    #[cfg(feature = "alarm")]
+    pub fn reset_alarm_interrupt_enable(&mut self) -> Result<(), Error<E>> {
         self.set_aie(false)
     }
 
@@ -1401,8 +2613,8 @@ where
     /// * `data` - True to initiate a reset, false otherwise.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the reset bit was successfully set or cleared, or an error if the operation fails.
-    pub fn set_reset(&mut self, data: bool) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the reset bit was successfully set or cleared, or an error if the operation fails.
+    pub fn set_reset(&mut self, data: bool) -> Result<(), Error<E>> {
         self.set_bit(RegisterTable::CompatibleControlRegister, 0, data)
     }
 
@@ -1412,8 +2624,8 @@ where
     /// * `data` - True to turn off the voltage detector, false to keep it on.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the voltage detector off bit was successfully set or cleared, or an error if the operation fails.
-    pub fn set_vdetoff(&mut self, data: bool) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the voltage detector off bit was successfully set or cleared, or an error if the operation fails.
+    pub fn set_vdetoff(&mut self, data: bool) -> Result<(), Error<E>> {
         self.set_bit(RegisterTable::ExtendedBackupFunction, 3, data)
     }
 
@@ -1423,8 +2635,8 @@ where
     /// * `data` - True to turn off the voltage detector, false to keep it on.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the voltage detector off bit was successfully set or cleared, or an error if the operation fails.
-    pub fn set_voltage_detector_off(&mut self, data: bool) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the voltage detector off bit was successfully set or cleared, or an error if the operation fails.
+    pub fn set_voltage_detector_off(&mut self, data: bool) -> Result<(), Error<E>> {
         self.set_vdetoff(data)
     }
 
@@ -1434,8 +2646,8 @@ where
     /// * `data` - True to turn off the switch, false to keep it on.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the switch off bit was successfully set or cleared, or an error if the operation fails.
-    pub fn set_swoff(&mut self, data: bool) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the switch off bit was successfully set or cleared, or an error if the operation fails.
+    pub fn set_swoff(&mut self, data: bool) -> Result<(), Error<E>> {
         self.set_bit(RegisterTable::ExtendedBackupFunction, 2, data)
     }
 
@@ -1445,8 +2657,8 @@ where
     /// * `data` - True to turn off the switch, false to keep it on.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the switch off bit was successfully set or cleared, or an error if the operation fails.
-    pub fn set_switch_off(&mut self, data: bool) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the switch off bit was successfully set or cleared, or an error if the operation fails.
+    pub fn set_switch_off(&mut self, data: bool) -> Result<(), Error<E>> {
         self.set_swoff(data)
     }
 
@@ -1456,8 +2668,8 @@ where
     /// * `data` - True to set the backup mode sample bit 1, false to clear it.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the backup mode sample bit 1 was successfully set or cleared, or an error if the operation fails.
-    pub fn set_bksmp1(&mut self, data: bool) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the backup mode sample bit 1 was successfully set or cleared, or an error if the operation fails.
+    pub fn set_bksmp1(&mut self, data: bool) -> Result<(), Error<E>> {
         self.set_bit(RegisterTable::ExtendedBackupFunction, 1, data)
     }
 
@@ -1467,32 +2679,35 @@ where
     /// * `data` - True to set the backup mode sample bit 0, false to clear it.
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the backup mode sample bit 0 was successfully set or cleared, or an error if the operation fails.
-    pub fn set_bksmp0(&mut self, data: bool) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - Ok if the backup mode sample bit 0 was successfully set or cleared, or an error if the operation fails.
+    pub fn set_bksmp0(&mut self, data: bool) -> Result<(), Error<E>> {
         self.set_bit(RegisterTable::ExtendedBackupFunction, 0, data)
     }
 
     /// Configures the backup mode sampling time by setting the corresponding bits.
     ///
     /// # Arguments
-    /// * `data` - The value to set for the backup mode sampling time.
+    /// * `data` - The backup mode sampling time bits (0-3).
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the backup mode sampling time was successfully configured, or an error if the operation fails.
-    pub fn set_bksmp(&mut self, data: u8) -> Result<(), E> {
-        let current = self.read_register(RegisterTable::ExtendedBackupFunction)?;
-        let data = (current & 0b11111100) | (data & 0b00000011);
-        self.write_register(RegisterTable::ExtendedBackupFunction, data)
+    /// * `Result<(), Error<E>>` - Ok if the backup mode sampling time was successfully configured,
+    ///   `Error::InvalidInput` if the value is out of range, or an error if the operation fails.
+    pub fn set_bksmp(&mut self, data: u8) -> Result<(), Error<E>> {
+        if data > 0b11 {
+            return Err(Error::InvalidInput);
+        }
+        // BKSMP
+        self.update_register(RegisterTable::ExtendedBackupFunction, 0b00000011, data)
     }
     
     /// Alias for `set_bksmp`.
     ///
     /// # Arguments
-    /// * `data` - The value to set for the backup mode sampling time.
+    /// * `data` - The backup mode sampling time bits (0-3).
     ///
     /// # Returns
-    /// * `Result<(), E>` - Ok if the backup mode sampling time was successfully configured, or an error if the operation fails.
-    pub fn set_backup_mode_sampling_time(&mut self, data: u8) -> Result<(), E> {
+    /// * `Result<(), Error<E>>` - As for `set_bksmp`.
+    pub fn set_backup_mode_sampling_time(&mut self, data: u8) -> Result<(), Error<E>> {
         self.set_bksmp(data)
     }
 }
@@ -0,0 +1,145 @@
+//! A periodic data logger woken by the alarm or the wake-up timer.
+
+use chrono::{Duration, NaiveDateTime, Timelike};
+
+use crate::{Error, IntoRtcDuration, Rx8900, Transport};
+
+/// How the logger is woken up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Wake {
+    /// The alarm, re-armed after each sample; used for whole minutes.
+    Alarm,
+    /// The self-reloading wake-up timer; used for shorter periods.
+    Timer,
+}
+
+/// Calls a sampling closure periodically, woken by the alarm or the wake-up timer through the INT pin.
+///
+/// Periods of whole minutes use the alarm, which fires at the start of a minute; other periods use the wake-up
+/// timer and must be counted down exactly by it (see `Rx8900::start_countdown`). The MCU can sleep between two
+/// samples and call `poll` after each wake-up:
+///
+/// ```ignore
+/// let mut logger = Logger::start(&mut rx8900, Duration::minutes(15), |at| store(at, sensor.read()))?;
+/// loop {
+///     wait_for_interrupt();
+///     logger.poll(&mut rx8900)?;
+/// }
+/// ```
+pub struct Logger<F> {
+    sample: F,
+    period: Duration,
+    next: NaiveDateTime,
+    wake: Wake,
+}
+
+impl<F> Logger<F>
+where
+    F: FnMut(NaiveDateTime),
+{
+    /// Arms the alarm or the wake-up timer for the first sample.
+    ///
+    /// With the alarm, the first sample is due one period after the start of the current minute; with the timer,
+    /// one period after now.
+    ///
+    /// # Arguments
+    /// * `rx8900` - The RTC waking the MCU up.
+    /// * `period` - The interval between two samples, as any `IntoRtcDuration`.
+    /// * `sample` - The closure called with the time each sample was due.
+    ///
+    /// # Returns
+    /// * `Result<Logger<F>, Error<E>>` - The logger, `Error::InvalidInput` if the period is neither whole minutes up to
+    ///   28 days nor counted down exactly by the timer, or an error if the operation fails.
+    pub fn start<I2C, E>(rx8900: &mut Rx8900<I2C>, period: impl IntoRtcDuration, sample: F) -> Result<Self, Error<E>>
+    where
+        I2C: Transport<Error = E>,
+    {
+        let period = period.into_rtc_duration().ok_or(Error::InvalidInput)?;
+        let now = rx8900.datetime()?;
+        let whole_minutes = period >= Duration::minutes(1) && period.num_milliseconds() % 60_000 == 0;
+        let (wake, next) = if whole_minutes {
+            // The alarm matches the day of the month, so it cannot tell months apart.
+            if period > Duration::days(28) {
+                return Err(Error::InvalidInput);
+            }
+            let minute = now.with_second(0).and_then(|minute| minute.with_nanosecond(0)).unwrap_or(now);
+            let next = minute.checked_add_signed(period).ok_or(Error::InvalidInput)?;
+            rx8900.sleep_until(next)?;
+            (Wake::Alarm, next)
+        } else {
+            rx8900.start_countdown(period)?;
+            (Wake::Timer, now + period)
+        };
+        Ok(Self { sample, period, next, wake })
+    }
+
+    /// Takes a sample if one is due, and re-arms the alarm.
+    ///
+    /// Samples whose alarm was missed while the MCU was busy are skipped, so the next one is always in the future.
+    ///
+    /// # Arguments
+    /// * `rx8900` - The RTC passed to `start`.
+    ///
+    /// # Returns
+    /// * `Result<bool, Error<E>>` - True if a sample was taken, false if none was due, `Error::InvalidTime` if the time
+    ///   registers hold no valid date and time, or an error if the operation fails.
+    pub fn poll<I2C, E>(&mut self, rx8900: &mut Rx8900<I2C>) -> Result<bool, Error<E>>
+    where
+        I2C: Transport<Error = E>,
+    {
+        let due = self.next;
+        match self.wake {
+            Wake::Alarm => {
+                if !rx8900.af()? {
+                    return Ok(false);
+                }
+                let now = rx8900.datetime()?;
+                while self.next <= now {
+                    self.next += self.period;
+                }
+                rx8900.sleep_until(self.next)?;
+            }
+            Wake::Timer => {
+                if !rx8900.tf()? {
+                    return Ok(false);
+                }
+                // TF
+                rx8900.clear_flags(0b00010000)?;
+                self.next += self.period;
+            }
+        }
+        (self.sample)(due);
+        Ok(true)
+    }
+
+    /// Returns the time the next sample is due.
+    pub fn next_sample(&self) -> NaiveDateTime {
+        self.next
+    }
+
+    /// Disarms the alarm or stops the timer, and returns the sampling closure.
+    ///
+    /// # Arguments
+    /// * `rx8900` - The RTC passed to `start`.
+    ///
+    /// # Returns
+    /// * `Result<F, Error<E>>` - The sampling closure, or an error if the operation fails.
+    pub fn stop<I2C, E>(self, rx8900: &mut Rx8900<I2C>) -> Result<F, Error<E>>
+    where
+        I2C: Transport<Error = E>,
+    {
+        match self.wake {
+            Wake::Alarm => {
+                rx8900.set_aie(false)?;
+                // AF
+                rx8900.clear_flags(0b00001000)?;
+            }
+            Wake::Timer => {
+                rx8900.set_te(false)?;
+                // TF
+                rx8900.clear_flags(0b00010000)?;
+            }
+        }
+        Ok(self.sample)
+    }
+}
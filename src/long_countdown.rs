@@ -0,0 +1,161 @@
+//! One-shot countdowns of arbitrary length, chaining cycles of the wake-up timer.
+
+use chrono::{Duration, NaiveDateTime};
+
+use crate::periodic::arm_chunk;
+use crate::{Error, IntoRtcDuration, Rx8900, Transport};
+
+/// Keeps the target of a countdown across MCU resets, e.g. in flash, in a backup register or in a retained RAM
+/// section.
+pub trait CountdownStorage {
+    /// Stores the target of a running countdown.
+    fn save(&mut self, target: NaiveDateTime);
+
+    /// Returns the stored target, or None if no countdown is running.
+    fn load(&mut self) -> Option<NaiveDateTime>;
+
+    /// Forgets the stored target once the countdown ended or was cancelled.
+    fn clear(&mut self);
+}
+
+/// A countdown running until a target date and time, however far it is.
+///
+/// Created by `Rx8900::start_long_countdown`, `start_persistent_countdown` or `resume_countdown`. The hardware
+/// counter covers at most 4095 minutes per cycle, so the countdown chains as many cycles as needed, re-arming the
+/// counter from `on_timer_interrupt` until the target is reached; the last cycle counts seconds so the countdown
+/// ends on the second.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LongCountdown {
+    target: NaiveDateTime,
+    cycles: u32,
+}
+
+impl LongCountdown {
+    /// Returns the date and time the countdown ends at.
+    pub fn target(&self) -> NaiveDateTime {
+        self.target
+    }
+
+    /// Returns the number of hardware cycles that expired so far.
+    pub fn cycles(&self) -> u32 {
+        self.cycles
+    }
+
+    /// Checks the timer flag and, if it is set, clears it and either re-arms the counter or ends the countdown.
+    ///
+    /// The time is read before the flag is cleared, so an interrupt whose time could not be read is handled again
+    /// by the next call.
+    ///
+    /// # Arguments
+    /// * `rx8900` - The RTC running the countdown.
+    ///
+    /// # Returns
+    /// * `Result<bool, Error<E>>` - True once the target is reached, after which the timer and its interrupt are
+    ///   disabled, false if the flag was not set or the counter was re-armed, `Error::InvalidTime` if the time
+    ///   registers hold no valid date and time, or an error if a bus operation fails.
+    pub fn on_timer_interrupt<I2C, E>(&mut self, rx8900: &mut Rx8900<I2C>) -> Result<bool, Error<E>>
+    where
+        I2C: Transport<Error = E>,
+    {
+        if !rx8900.tf()? {
+            return Ok(false);
+        }
+        let now = rx8900.datetime()?;
+        // TF
+        rx8900.clear_flags(0b00010000)?;
+        self.cycles = self.cycles.saturating_add(1);
+        if now >= self.target {
+            self.cancel(rx8900)?;
+            return Ok(true);
+        }
+        arm_chunk(rx8900, self.target - now)?;
+        Ok(false)
+    }
+
+    /// Stops the countdown, disabling the timer and its interrupt.
+    ///
+    /// # Arguments
+    /// * `rx8900` - The RTC running the countdown.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the timer was stopped, or an error if a bus operation fails.
+    pub fn cancel<I2C, E>(&self, rx8900: &mut Rx8900<I2C>) -> Result<(), Error<E>>
+    where
+        I2C: Transport<Error = E>,
+    {
+        rx8900.set_te(false)?;
+        rx8900.set_tie(false)
+    }
+}
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Starts a countdown of any length on the wake-up timer, and enables the timer interrupt.
+    ///
+    /// Pass the returned `LongCountdown` the timer interrupts; it re-arms the counter transparently and reports
+    /// when the whole duration elapsed. The duration is rounded up to whole seconds.
+    ///
+    /// # Arguments
+    /// * `duration` - The duration of the countdown, as any `IntoRtcDuration`, e.g. days or weeks.
+    ///
+    /// # Returns
+    /// * `Result<LongCountdown, Error<E>>` - The running countdown, `Error::InvalidInput` if the duration is not
+    ///   positive or too long to be represented, or an error if a bus operation fails.
+    pub fn start_long_countdown(&mut self, duration: impl IntoRtcDuration) -> Result<LongCountdown, Error<E>> {
+        let duration = duration.into_rtc_duration().ok_or(Error::InvalidInput)?;
+        let ms = duration.num_milliseconds();
+        if ms <= 0 {
+            return Err(Error::InvalidInput);
+        }
+        let duration = Duration::seconds((ms as u64).div_ceil(1000) as i64);
+        let target = self.datetime()?.checked_add_signed(duration).ok_or(Error::InvalidInput)?;
+        arm_chunk(self, duration)?;
+        self.set_tie(true)?;
+        Ok(LongCountdown { target, cycles: 0 })
+    }
+
+    /// Starts a countdown as `start_long_countdown` does, and saves its target so `resume_countdown` can continue
+    /// it after a reset of the MCU.
+    ///
+    /// Clear the storage once the countdown ended or was cancelled.
+    ///
+    /// # Arguments
+    /// * `duration` - The duration of the countdown, as any `IntoRtcDuration`.
+    /// * `storage` - Where the target is kept.
+    ///
+    /// # Returns
+    /// * `Result<LongCountdown, Error<E>>` - The running countdown, `Error::InvalidInput` if the duration is not
+    ///   positive or too long to be represented, or an error if a bus operation fails.
+    pub fn start_persistent_countdown<S: CountdownStorage>(
+        &mut self,
+        duration: impl IntoRtcDuration,
+        storage: &mut S,
+    ) -> Result<LongCountdown, Error<E>> {
+        let countdown = self.start_long_countdown(duration)?;
+        storage.save(countdown.target);
+        Ok(countdown)
+    }
+
+    /// Continues a countdown saved by `start_persistent_countdown`, typically after a watchdog reset.
+    ///
+    /// The counter is re-armed for the time left until the stored target, and the timer interrupt enabled. A
+    /// target that passed while the MCU was down ends the countdown at the next second.
+    ///
+    /// # Arguments
+    /// * `storage` - Where the target is kept.
+    ///
+    /// # Returns
+    /// * `Result<Option<LongCountdown>, Error<E>>` - The resumed countdown, None if no target is stored,
+    ///   `Error::InvalidTime` if the time registers hold no valid date and time, or an error if a bus operation fails.
+    pub fn resume_countdown<S: CountdownStorage>(&mut self, storage: &mut S) -> Result<Option<LongCountdown>, Error<E>> {
+        let Some(target) = storage.load() else {
+            return Ok(None);
+        };
+        let now = self.datetime()?;
+        arm_chunk(self, target - now)?;
+        self.set_tie(true)?;
+        Ok(Some(LongCountdown { target, cycles: 0 }))
+    }
+}
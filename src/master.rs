@@ -0,0 +1,160 @@
+//! Coordination of two bus masters sharing one RX8900, e.g. an application MCU and a supervisor.
+//!
+//! The owner of the chip is recorded in the upper two bits of the user RAM register, so it is visible to every
+//! master and survives their resets. Only the owner writes the time and configuration; the others observe, and
+//! every write of an observer is refused with `Error::NotOwner`.
+//!
+//! The scheme is cooperative and not atomic: the chip has no compare-and-swap, so two masters claiming at the same
+//! time can both read an unclaimed marker and both write theirs, and nothing stops a master that ignores the marker
+//! from writing the chip. Claim once at startup, with staggered boot times or an id-based delay, and let the
+//! owner check in `as_owner` catch a takeover afterwards.
+
+use crate::{Error, RegisterTable, Rx8900, Transport};
+
+/// The bits of the user RAM register holding the id of the owner, 0 when the chip is not claimed.
+pub(crate) const OWNER_MASK: u8 = 0b11000000;
+
+/// The role of this driver among the bus masters sharing the chip.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Role {
+    /// The ownership marker is ignored; the default, for a single master.
+    #[default]
+    Unmanaged,
+    /// This master claimed the chip with the given id (1-3).
+    Owner(u8),
+    /// This master only reads the chip.
+    Observer,
+}
+
+impl<I2C> Rx8900<I2C> {
+    /// Switches to observing the chip, so every write and `as_owner` are refused with `Error::NotOwner`. The ownership
+    /// marker is left untouched.
+    pub fn observe_only(&mut self) {
+        self.role = Role::Observer;
+    }
+
+    /// Returns the role of this driver.
+    ///
+    /// # Returns
+    /// * `Role` - The role, `Role::Unmanaged` until `claim` or `observe_only` is called.
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    /// Returns the bits of the user RAM register this driver may change: all of them without a role, all but the
+    /// ownership marker otherwise.
+    pub(crate) fn ram_bits(&self) -> u8 {
+        match self.role {
+            Role::Unmanaged => 0xFF,
+            Role::Owner(_) | Role::Observer => !OWNER_MASK,
+        }
+    }
+}
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Returns the id of the master owning the chip.
+    ///
+    /// # Returns
+    /// * `Result<Option<u8>, E>` - The id of the owner (1-3), None if the chip is not claimed, or an error if the
+    ///   read fails.
+    pub fn owner(&mut self) -> Result<Option<u8>, E> {
+        let id = (self.read_register(RegisterTable::CompatibleRAM)? & OWNER_MASK) >> 6;
+        Ok((id != 0).then_some(id))
+    }
+
+    /// Claims the chip for this master, unless another master owns it.
+    ///
+    /// The marker is written only when the chip is not claimed yet. When another master owns it, this driver
+    /// switches to observing. The read and the write are two transactions, so two masters claiming at once may
+    /// both succeed; see the module documentation.
+    ///
+    /// # Arguments
+    /// * `id` - The id of this master (1-3), distinct for each master sharing the chip.
+    ///
+    /// # Returns
+    /// * `Result<bool, Error<E>>` - True if this master owns the chip, false if another one does,
+    ///   `Error::InvalidInput` if the id is out of range, or an error if a bus operation fails.
+    pub fn claim(&mut self, id: u8) -> Result<bool, Error<E>> {
+        if !(1..=3).contains(&id) {
+            return Err(Error::InvalidInput);
+        }
+        let ram = self.read_register(RegisterTable::CompatibleRAM)?;
+        match (ram & OWNER_MASK) >> 6 {
+            0 => {
+                // An observer may claim a chip nobody owns, so take the role before writing the marker.
+                let role = core::mem::replace(&mut self.role, Role::Owner(id));
+                if let Err(error) = self.write_register(RegisterTable::CompatibleRAM, ram & !OWNER_MASK | id << 6) {
+                    self.role = role;
+                    return Err(error);
+                }
+            }
+            owner if owner != id => {
+                self.role = Role::Observer;
+                return Ok(false);
+            }
+            _ => {}
+        }
+        self.role = Role::Owner(id);
+        Ok(true)
+    }
+
+    /// Gives the chip up so another master can claim it, and switches to observing.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the marker was cleared, `Error::NotOwner` if this master does not own the
+    ///   chip, or an error if a bus operation fails.
+    pub fn release_claim(&mut self) -> Result<(), Error<E>> {
+        self.ensure_owner()?;
+        let ram = self.read_register(RegisterTable::CompatibleRAM)?;
+        self.write_register(RegisterTable::CompatibleRAM, ram & !OWNER_MASK)?;
+        self.role = Role::Observer;
+        Ok(())
+    }
+
+    /// Runs a closure writing the chip only if this master may write it.
+    ///
+    /// An owner checks the marker again before running the closure, in case another master took the chip over;
+    /// it then switches to observing. Without a role, the closure always runs.
+    ///
+    /// # Arguments
+    /// * `f` - The closure, given the driver.
+    ///
+    /// # Returns
+    /// * `Result<T, R>` - The result of the closure, `Error::NotOwner` if this master may not write the chip, or an
+    ///   error if reading the marker fails.
+    pub fn as_owner<T, R>(&mut self, f: impl FnOnce(&mut Self) -> Result<T, R>) -> Result<T, R>
+    where
+        R: From<Error<E>>,
+    {
+        self.ensure_owner()?;
+        f(self)
+    }
+
+    /// Refuses the writes of an observer, as the expansion site of `register_access!` must; an owner is not checked
+    /// against the marker again, which `as_owner` does.
+    pub(crate) fn check_writable(&self) -> Result<(), Error<E>> {
+        match self.role {
+            Role::Observer => Err(Error::NotOwner),
+            Role::Unmanaged | Role::Owner(_) => Ok(()),
+        }
+    }
+
+    /// Checks that this master may write the chip.
+    fn ensure_owner(&mut self) -> Result<(), Error<E>> {
+        match self.role {
+            Role::Unmanaged => Ok(()),
+            Role::Observer => Err(Error::NotOwner),
+            Role::Owner(id) => {
+                if self.owner()? == Some(id) {
+                    Ok(())
+                } else {
+                    self.role = Role::Observer;
+                    Err(Error::NotOwner)
+                }
+            }
+        }
+    }
+}
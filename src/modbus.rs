@@ -0,0 +1,150 @@
+//! A 16-bit holding register bank view of the RTC, for surfacing it to a PLC over Modbus.
+//!
+//! | Address | Register | Access |
+//! |---------|----------|--------|
+//! | 0       | Year (2000-2099) | read-write |
+//! | 1       | Month (1-12) | read-write |
+//! | 2       | Day (1-31) | read-write |
+//! | 3       | Hour (0-23) | read-write |
+//! | 4       | Minute (0-59) | read-write |
+//! | 5       | Second (0-59) | read-write |
+//! | 6       | Weekday (0 is Sunday) | read-only |
+//! | 7       | Temperature in hundredths of a degree Celsius, two's complement | read-only |
+//! | 8       | Flags (UF, TF, AF, VLF, VDET); writing 0 to a flag clears it | read-write |
+//! | 9       | Alarm minute (0-59), or 0xFF for any minute | read-write |
+//! | 10      | Alarm hour (0-23), or 0xFF for any hour | read-write |
+//! | 11      | Alarm day, see `RtcObject::AlarmDay` | read-write |
+//! | 12      | Alarm interrupt enable (AIE) | read-write |
+//!
+//! Requests for addresses beyond `REGISTER_COUNT` should be answered with the exception code 2
+//! (illegal data address), and `Error::InvalidInput` otherwise with the exception code 3 (illegal data value).
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+use crate::{Error, RegisterTable, RtcObject, Rx8900, Transport, FLAG_BITS};
+
+/// Address of the year register.
+pub const YEAR: u16 = 0;
+/// Address of the month register.
+pub const MONTH: u16 = 1;
+/// Address of the day register.
+pub const DAY: u16 = 2;
+/// Address of the hour register.
+pub const HOUR: u16 = 3;
+/// Address of the minute register.
+pub const MINUTE: u16 = 4;
+/// Address of the second register.
+pub const SECOND: u16 = 5;
+/// Address of the weekday register.
+pub const WEEKDAY: u16 = 6;
+/// Address of the temperature register.
+pub const TEMPERATURE: u16 = 7;
+/// Address of the flags register.
+pub const FLAGS: u16 = 8;
+/// Address of the alarm minute register.
+pub const ALARM_MINUTE: u16 = 9;
+/// Address of the alarm hour register.
+pub const ALARM_HOUR: u16 = 10;
+/// Address of the alarm day register.
+pub const ALARM_DAY: u16 = 11;
+/// Address of the alarm interrupt enable register.
+pub const ALARM_ENABLE: u16 = 12;
+/// Number of registers in the bank.
+pub const REGISTER_COUNT: u16 = 13;
+
+/// The alarm registers and the objects backing them.
+const ALARM_OBJECTS: [(u16, RtcObject); 4] = [
+    (ALARM_MINUTE, RtcObject::AlarmMinute),
+    (ALARM_HOUR, RtcObject::AlarmHour),
+    (ALARM_DAY, RtcObject::AlarmDay),
+    (ALARM_ENABLE, RtcObject::AlarmEnable),
+];
+
+/// Returns the end of a register range, or None if it exceeds the bank.
+fn range_end(address: u16, count: usize) -> Option<u16> {
+    u16::try_from(count).ok().and_then(|count| address.checked_add(count)).filter(|&end| end <= REGISTER_COUNT)
+}
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Reads consecutive holding registers, as for the Modbus function code 3.
+    ///
+    /// The date and time are read once for the whole request.
+    ///
+    /// # Arguments
+    /// * `address` - The address of the first register.
+    /// * `values` - The buffer receiving the register values.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the registers were read, `Error::InvalidInput` if the range exceeds
+    ///   `REGISTER_COUNT`, or an error if the read fails.
+    pub fn read_holding_registers(&mut self, address: u16, values: &mut [u16]) -> Result<(), Error<E>> {
+        let end = range_end(address, values.len()).ok_or(Error::InvalidInput)?;
+        let now = if address <= WEEKDAY && end > YEAR { self.datetime()? } else { NaiveDateTime::default() };
+        for (register, value) in (address..end).zip(values.iter_mut()) {
+            *value = match register {
+                YEAR => now.year() as u16,
+                MONTH => now.month() as u16,
+                DAY => now.day() as u16,
+                HOUR => now.hour() as u16,
+                MINUTE => now.minute() as u16,
+                SECOND => now.second() as u16,
+                WEEKDAY => now.weekday().num_days_from_sunday() as u16,
+                TEMPERATURE => (self.temp_in_millicelsius()? / 10) as i16 as u16,
+                FLAGS => (self.read_register(RegisterTable::CompatibleFlagRegister)? & FLAG_BITS) as u16,
+                _ => {
+                    let (_, object) = ALARM_OBJECTS.iter().find(|(alarm, _)| *alarm == register).ok_or(Error::InvalidInput)?;
+                    self.read_object(*object)? as u16
+                }
+            };
+        }
+        Ok(())
+    }
+
+    /// Writes consecutive holding registers, as for the Modbus function codes 6 and 16.
+    ///
+    /// Date and time registers are merged with the current date and time and written at once, without applying
+    /// the time set policy. The registers are validated and written in the order date and time, flags, alarm; if
+    /// a value is rejected, the preceding groups stay written.
+    ///
+    /// # Arguments
+    /// * `address` - The address of the first register.
+    /// * `values` - The register values.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the registers were written, `Error::InvalidInput` if the range exceeds
+    ///   `REGISTER_COUNT`, includes a read-only register or a value is out of range, or an error if the operation
+    ///   fails.
+    pub fn write_holding_registers(&mut self, address: u16, values: &[u16]) -> Result<(), Error<E>> {
+        let end = range_end(address, values.len()).ok_or(Error::InvalidInput)?;
+        let covers = |register: u16| (address..end).contains(&register);
+        let value = |register: u16| values[(register - address) as usize];
+        if covers(WEEKDAY) || covers(TEMPERATURE) {
+            return Err(Error::InvalidInput);
+        }
+        if (YEAR..=SECOND).any(covers) {
+            let now = self.datetime()?;
+            let field = |register: u16, current: u32| if covers(register) { value(register) as u32 } else { current };
+            let date = NaiveDate::from_ymd_opt(field(YEAR, now.year() as u32) as i32, field(MONTH, now.month()), field(DAY, now.day()));
+            let time = NaiveTime::from_hms_opt(field(HOUR, now.hour()), field(MINUTE, now.minute()), field(SECOND, now.second()));
+            let datetime = date
+                .zip(time)
+                .map(|(date, time)| date.and_time(time))
+                .filter(|datetime| (2000..=2099).contains(&datetime.year()))
+                .ok_or(Error::InvalidInput)?;
+            self.set_datetime(datetime)?;
+        }
+        if covers(FLAGS) {
+            let flags = u8::try_from(value(FLAGS)).map_err(|_| Error::InvalidInput)?;
+            self.write_register(RegisterTable::CompatibleFlagRegister, FLAG_BITS & flags)?;
+        }
+        for (register, object) in ALARM_OBJECTS {
+            if covers(register) {
+                self.write_object(object, value(register) as u32)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+//! Stitching of the wall clock into a timestamp that never goes backwards.
+
+/// The offset added to the wall clock and the last monotonic timestamp handed out.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Monotonic {
+    offset: i64,
+    last: Option<i64>,
+}
+
+impl Monotonic {
+    /// Converts a wall clock reading to a monotonic timestamp.
+    ///
+    /// When the wall clock has been stepped backwards since the last call, the offset grows by the
+    /// size of the step so the result continues from the last value instead of jumping back.
+    ///
+    /// # Arguments
+    /// * `wall` - The current wall clock time in seconds.
+    pub(crate) fn stitch(&mut self, wall: i64) -> i64 {
+        let mut timestamp = wall + self.offset;
+        if let Some(last) = self.last {
+            if timestamp < last {
+                self.offset += last - timestamp;
+                timestamp = last;
+            }
+        }
+        self.last = Some(timestamp);
+        timestamp
+    }
+}
@@ -0,0 +1,219 @@
+//! A mapping of the RTC parameters to `u32`-keyed objects, for CANopen object dictionaries and Modbus register maps.
+
+#[cfg(feature = "alarm")]
+use crate::bcd;
+use crate::{Error, RegisterTable, Rx8900, Transport};
+
+/// An RTC parameter exposed as a `u32` object.
+///
+/// The key of an object is its CANopen index and subindex, `index << 8 | subindex`, in the
+/// manufacturer-specific area. Values are unsigned unless noted; booleans are 0 or 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum RtcObject {
+    /// Date and time as seconds since the Unix epoch (UTC), read-write.
+    Timestamp = 0x200001,
+    /// 1 if the clock holds a valid time (VLF clear), read-only.
+    TimeValid = 0x200002,
+    /// Temperature in millidegrees Celsius as a two's complement `i32`, read-only.
+    #[cfg(feature = "temperature")]
+    Temperature = 0x200003,
+    /// Raw flag register (UF, TF, AF, VLF, VDET), read-only.
+    Flags = 0x200004,
+    /// Alarm minute (0-59), or 0xFF to match any minute, read-write.
+    #[cfg(feature = "alarm")]
+    AlarmMinute = 0x200101,
+    /// Alarm hour (0-23), or 0xFF to match any hour, read-write.
+    #[cfg(feature = "alarm")]
+    AlarmHour = 0x200102,
+    /// Alarm day of the month (1-31) with a day alarm, or weekday bits (bit 0 is Sunday) with a week alarm,
+    /// or 0xFF to match any day, read-write.
+    #[cfg(feature = "alarm")]
+    AlarmDay = 0x200103,
+    /// Alarm interrupt enable (AIE), read-write.
+    #[cfg(feature = "alarm")]
+    AlarmEnable = 0x200104,
+    /// FOUT frequency (FSEL, 0-3), read-write.
+    FoutFrequency = 0x200201,
+    /// Update interrupt every minute instead of every second (USEL), read-write.
+    UpdateInterruptType = 0x200202,
+    /// Temperature compensation interval (CSEL, 0-3), read-write.
+    CompensationInterval = 0x200203,
+    /// Backup mode sampling time (BKSMP, 0-3), read-write.
+    BackupSamplingTime = 0x200204,
+    /// Voltage detector off (VDETOFF), read-write.
+    VoltageDetectorOff = 0x200205,
+    /// Switch off (SWOFF), read-write.
+    SwitchOff = 0x200206,
+    /// General-purpose RAM byte, read-write.
+    Ram = 0x200301,
+}
+
+impl RtcObject {
+    /// Looks up an object by key.
+    ///
+    /// # Arguments
+    /// * `key` - The object key, `index << 8 | subindex`.
+    ///
+    /// # Returns
+    /// * `Option<RtcObject>` - The object, or None if no object has this key.
+    pub fn from_key(key: u32) -> Option<Self> {
+        Some(match key {
+            0x200001 => RtcObject::Timestamp,
+            0x200002 => RtcObject::TimeValid,
+            #[cfg(feature = "temperature")]
+            0x200003 => RtcObject::Temperature,
+            0x200004 => RtcObject::Flags,
+            #[cfg(feature = "alarm")]
+            0x200101 => RtcObject::AlarmMinute,
+            #[cfg(feature = "alarm")]
+            0x200102 => RtcObject::AlarmHour,
+            #[cfg(feature = "alarm")]
+            0x200103 => RtcObject::AlarmDay,
+            #[cfg(feature = "alarm")]
+            0x200104 => RtcObject::AlarmEnable,
+            0x200201 => RtcObject::FoutFrequency,
+            0x200202 => RtcObject::UpdateInterruptType,
+            0x200203 => RtcObject::CompensationInterval,
+            0x200204 => RtcObject::BackupSamplingTime,
+            0x200205 => RtcObject::VoltageDetectorOff,
+            0x200206 => RtcObject::SwitchOff,
+            0x200301 => RtcObject::Ram,
+            _ => return None,
+        })
+    }
+
+    /// Returns the key of the object, `index << 8 | subindex`.
+    pub fn key(self) -> u32 {
+        self as u32
+    }
+
+    /// Returns the CANopen index of the object.
+    pub fn index(self) -> u16 {
+        (self.key() >> 8) as u16
+    }
+
+    /// Returns the CANopen subindex of the object.
+    pub fn subindex(self) -> u8 {
+        self.key() as u8
+    }
+
+    /// Returns whether the object can be written.
+    pub fn is_writable(self) -> bool {
+        match self {
+            RtcObject::TimeValid | RtcObject::Flags => false,
+            #[cfg(feature = "temperature")]
+            RtcObject::Temperature => false,
+            _ => true,
+        }
+    }
+}
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Reads the value of an object.
+    ///
+    /// # Arguments
+    /// * `object` - The object to read.
+    ///
+    /// # Returns
+    /// * `Result<u32, Error<E>>` - The value, `Error::InvalidDeviceData` if the time is before 1970 or after 2106,
+    ///   or an error if the read fails.
+    pub fn read_object(&mut self, object: RtcObject) -> Result<u32, Error<E>> {
+        Ok(match object {
+            RtcObject::Timestamp => u32::try_from(self.timestamp()?).map_err(|_| Error::InvalidDeviceData)?,
+            RtcObject::TimeValid => !self.vlf()? as u32,
+            #[cfg(feature = "temperature")]
+            RtcObject::Temperature => self.temp_in_millicelsius()? as u32,
+            RtcObject::Flags => self.read_register(RegisterTable::CompatibleFlagRegister)? as u32,
+            #[cfg(feature = "alarm")]
+            RtcObject::AlarmMinute => self.read_alarm_object(RegisterTable::CompatibleMinAlarm, true)?,
+            #[cfg(feature = "alarm")]
+            RtcObject::AlarmHour => self.read_alarm_object(RegisterTable::CompatibleHourAlarm, true)?,
+            #[cfg(feature = "alarm")]
+            RtcObject::AlarmDay => {
+                let day_alarm = self.wada()?;
+                self.read_alarm_object(RegisterTable::CompatibleWeekDayAlarm, day_alarm)?
+            }
+            #[cfg(feature = "alarm")]
+            RtcObject::AlarmEnable => self.aie()? as u32,
+            RtcObject::FoutFrequency => self.fsel()? as u32,
+            RtcObject::UpdateInterruptType => self.usel()? as u32,
+            RtcObject::CompensationInterval => self.csel()? as u32,
+            RtcObject::BackupSamplingTime => self.bksmp()? as u32,
+            RtcObject::VoltageDetectorOff => self.vdetoff()? as u32,
+            RtcObject::SwitchOff => self.swoff()? as u32,
+            RtcObject::Ram => self.ram()? as u32,
+        })
+    }
+
+    /// Writes the value of an object.
+    ///
+    /// The timestamp is written with `set_timestamp`, so the time set policy applies.
+    ///
+    /// # Arguments
+    /// * `object` - The object to write.
+    /// * `value` - The new value.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the value was written, `Error::InvalidInput` if the object is read-only or
+    ///   the value is out of range, or an error if the operation fails.
+    pub fn write_object(&mut self, object: RtcObject, value: u32) -> Result<(), Error<E>> {
+        let byte = |max: u8| u8::try_from(value).ok().filter(|&value| value <= max).ok_or(Error::InvalidInput);
+        match object {
+            RtcObject::Timestamp => {
+                self.set_timestamp(value as i64)?;
+            }
+            #[cfg(feature = "alarm")]
+            RtcObject::AlarmMinute => self.write_alarm_object(RegisterTable::CompatibleMinAlarm, value, 0, 59, true)?,
+            #[cfg(feature = "alarm")]
+            RtcObject::AlarmHour => self.write_alarm_object(RegisterTable::CompatibleHourAlarm, value, 0, 23, true)?,
+            #[cfg(feature = "alarm")]
+            RtcObject::AlarmDay => {
+                if self.wada()? {
+                    self.write_alarm_object(RegisterTable::CompatibleWeekDayAlarm, value, 1, 31, true)?
+                } else {
+                    self.write_alarm_object(RegisterTable::CompatibleWeekDayAlarm, value, 1, 0x7F, false)?
+                }
+            }
+            #[cfg(feature = "alarm")]
+            RtcObject::AlarmEnable => self.set_aie(byte(1)? != 0)?,
+            RtcObject::FoutFrequency => self.set_fsel(byte(3)?)?,
+            RtcObject::UpdateInterruptType => self.set_usel(byte(1)? != 0)?,
+            RtcObject::CompensationInterval => self.set_csel(byte(3)?)?,
+            RtcObject::BackupSamplingTime => self.set_bksmp(byte(3)?)?,
+            RtcObject::VoltageDetectorOff => self.set_vdetoff(byte(1)? != 0)?,
+            RtcObject::SwitchOff => self.set_swoff(byte(1)? != 0)?,
+            RtcObject::Ram => self.set_ram(byte(0xFF)?)?,
+            _ => return Err(Error::InvalidInput),
+        }
+        Ok(())
+    }
+
+    /// Reads an alarm register as an object value, 0xFF if AE is set.
+    #[cfg(feature = "alarm")]
+    fn read_alarm_object(&mut self, register: RegisterTable, bcd_coded: bool) -> Result<u32, E> {
+        let data = self.read_register(register)?;
+        Ok(match data {
+            // AE
+            _ if data & 0b10000000 != 0 => 0xFF,
+            _ if bcd_coded => bcd::from_bcd(data & 0b01111111) as u32,
+            _ => data as u32,
+        })
+    }
+
+    /// Writes an object value within `min..=max` to an alarm register, setting AE for 0xFF.
+    #[cfg(feature = "alarm")]
+    fn write_alarm_object(&mut self, register: RegisterTable, value: u32, min: u8, max: u8, bcd_coded: bool) -> Result<(), Error<E>> {
+        let data = match u8::try_from(value) {
+            // AE
+            Ok(0xFF) => 0b10000000,
+            Ok(value) if (min..=max).contains(&value) && bcd_coded => bcd::to_bcd(value),
+            Ok(value) if (min..=max).contains(&value) => value,
+            _ => return Err(Error::InvalidInput),
+        };
+        self.write_register(register, data)
+    }
+}
@@ -0,0 +1,198 @@
+//! Deferred processing of the INT pin: the interrupt handler only takes note, the main loop talks to the RTC.
+
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+use crate::{Error, RegisterTable, Rx8900, Transport};
+
+/// The interrupt sources found set by `PendingInterrupt::take_pending`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InterruptEvents {
+    /// Update flag (UF).
+    pub update: bool,
+    /// Timer flag (TF).
+    #[cfg(feature = "timer")]
+    pub timer: bool,
+    /// Alarm flag (AF).
+    #[cfg(feature = "alarm")]
+    pub alarm: bool,
+}
+
+impl InterruptEvents {
+    /// Returns whether no interrupt source was found set.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Decodes the interrupt sources from the flag register.
+    pub(crate) fn from_flags(flags: u8) -> Self {
+        InterruptEvents {
+            update: flags & 0b00100000 != 0,
+            #[cfg(feature = "timer")]
+            timer: flags & 0b00010000 != 0,
+            #[cfg(feature = "alarm")]
+            alarm: flags & 0b00001000 != 0,
+        }
+    }
+}
+
+/// Latency between the assertion of the INT pin and the clearing of the flags, in ticks of the user clock.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LatencyStats {
+    /// Number of measured interrupts.
+    pub count: u32,
+    /// Longest latency measured.
+    pub max: u32,
+    /// Average latency, rounded down.
+    pub average: u32,
+}
+
+/// A lock-free cell coalescing INT pin interrupts until the main loop processes them.
+///
+/// The I2C bus should not be used from an interrupt handler, yet the flags have to be read and
+/// cleared over I2C. Place the cell in a `static`, call `note_interrupt` from the handler of the
+/// INT pin, and call `take_pending` from the main loop. Any number of interrupts noted in between
+/// are handled by a single read of the flag register.
+///
+/// ```ignore
+/// static RTC_INT: PendingInterrupt = PendingInterrupt::new();
+///
+/// #[interrupt]
+/// fn GPIOTE() {
+///     RTC_INT.note_interrupt();
+/// }
+///
+/// loop {
+///     let events = RTC_INT.take_pending(&mut rx8900)?;
+///     if events.update {
+///         // ...
+///     }
+/// }
+/// ```
+///
+/// Created with `with_clock`, the cell also measures the time from the first noted interrupt to the
+/// clearing of the flags, which helps tuning interrupt and bus priorities; see `stats`.
+#[derive(Debug, Default)]
+pub struct PendingInterrupt {
+    pending: AtomicBool,
+    clock: Option<fn() -> u32>,
+    asserted: AtomicU32,
+    count: AtomicU32,
+    max: AtomicU32,
+    total: AtomicU32,
+}
+
+impl PendingInterrupt {
+    /// Creates a cell without any pending interrupt.
+    pub const fn new() -> Self {
+        Self {
+            pending: AtomicBool::new(false),
+            clock: None,
+            asserted: AtomicU32::new(0),
+            count: AtomicU32::new(0),
+            max: AtomicU32::new(0),
+            total: AtomicU32::new(0),
+        }
+    }
+
+    /// Creates a cell that measures the interrupt latency with the given clock.
+    ///
+    /// # Arguments
+    /// * `clock` - Returns a free-running tick count, e.g. a cycle counter or a monotonic timer; it is called from
+    ///   the interrupt handler and may wrap around.
+    pub const fn with_clock(clock: fn() -> u32) -> Self {
+        let mut cell = Self::new();
+        cell.clock = Some(clock);
+        cell
+    }
+
+    /// Records that the INT pin was asserted; safe to call from an interrupt handler.
+    pub fn note_interrupt(&self) {
+        if let Some(clock) = self.clock {
+            // Only the first of coalesced interrupts starts the measurement.
+            if !self.is_pending() {
+                self.asserted.store(clock(), Ordering::Relaxed);
+            }
+        }
+        self.pending.store(true, Ordering::Release);
+    }
+
+    /// Returns the latency measured so far; all zero unless the cell was created with `with_clock`.
+    pub fn stats(&self) -> LatencyStats {
+        let count = self.count.load(Ordering::Relaxed);
+        LatencyStats {
+            count,
+            max: self.max.load(Ordering::Relaxed),
+            average: self.total.load(Ordering::Relaxed).checked_div(count).unwrap_or(0),
+        }
+    }
+
+    /// Clears the latency measured so far.
+    pub fn reset_stats(&self) {
+        self.count.store(0, Ordering::Relaxed);
+        self.max.store(0, Ordering::Relaxed);
+        self.total.store(0, Ordering::Relaxed);
+    }
+
+    /// Adds a measured latency; only called from the main loop.
+    fn record(&self, latency: u32) {
+        let count = self.count.load(Ordering::Relaxed);
+        let total = self.total.load(Ordering::Relaxed);
+        if count == u32::MAX || total.checked_add(latency).is_none() {
+            // Keep the statistics consistent rather than wrapping around.
+            return;
+        }
+        self.count.store(count + 1, Ordering::Relaxed);
+        self.total.store(total + latency, Ordering::Relaxed);
+        self.max.store(self.max.load(Ordering::Relaxed).max(latency), Ordering::Relaxed);
+    }
+
+    /// Returns whether an interrupt was noted and not taken yet.
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Acquire)
+    }
+
+    /// Reads and clears the interrupt flags if an interrupt was noted since the last call.
+    ///
+    /// The note is cleared before the flag register is read, so an interrupt arriving meanwhile is
+    /// taken by the next call instead of being lost. When nothing was noted the bus is not accessed.
+    ///
+    /// # Arguments
+    /// * `rx8900` - The RTC driving the INT pin.
+    ///
+    /// # Returns
+    /// * `Result<InterruptEvents, Error<E>>` - The interrupt flags that were set and have been cleared, or an error
+    ///   if the bus operation fails; the note is restored on error so the next call retries.
+    pub fn take_pending<I2C, E>(&self, rx8900: &mut Rx8900<I2C>) -> Result<InterruptEvents, Error<E>>
+    where
+        I2C: Transport<Error = E>,
+    {
+        if !self.is_pending() {
+            return Ok(InterruptEvents::default());
+        }
+        // Read before the note is cleared, so an interrupt arriving meanwhile cannot overwrite it.
+        let asserted = self.asserted.load(Ordering::Relaxed);
+        self.pending.store(false, Ordering::Release);
+        let result = Self::read_and_clear(rx8900);
+        match (&result, self.clock) {
+            (Err(_), _) => {
+                self.asserted.store(asserted, Ordering::Relaxed);
+                self.pending.store(true, Ordering::Release);
+            }
+            (Ok(_), Some(clock)) => self.record(clock().wrapping_sub(asserted)),
+            (Ok(_), None) => {}
+        }
+        result
+    }
+
+    fn read_and_clear<I2C, E>(rx8900: &mut Rx8900<I2C>) -> Result<InterruptEvents, Error<E>>
+    where
+        I2C: Transport<Error = E>,
+    {
+        // UF, TF and AF
+        let flags = rx8900.read_register(RegisterTable::CompatibleFlagRegister)? & 0b00111000;
+        if flags != 0 {
+            rx8900.clear_flags(flags)?;
+        }
+        Ok(InterruptEvents::from_flags(flags))
+    }
+}
@@ -0,0 +1,207 @@
+//! Periods longer than the wake-up timer counts, re-armed in software without accumulating latency.
+
+use chrono::{Duration, NaiveDateTime, Timelike};
+
+use crate::{Error, IntoRtcDuration, Rx8900, SourceClock, Transport};
+
+/// The drift and re-arming figures of a `PeriodicTimer`, in seconds of the RTC.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PeriodicStats {
+    /// Number of periods that elapsed.
+    pub periods: u32,
+    /// Number of periods skipped because the interrupt was handled more than a period late.
+    pub missed: u32,
+    /// Number of intermediate re-arms of the counter.
+    pub rearms: u32,
+    /// Lateness of the last period, from its nominal end to the moment it was handled; negative if it ended early.
+    pub residual: i64,
+    /// The residual of the largest magnitude seen.
+    pub max_residual: i64,
+}
+
+/// Raises a timer event every period, including periods the 12-bit counter cannot count down in one go.
+///
+/// Periods the counter can count exactly, such as 6 hours as 360 minutes, run on the auto-reload of the hardware.
+/// Other periods, e.g. 2 hours and 30 seconds or several days, are split into chunks re-armed from
+/// `on_timer_interrupt`. Each chunk is computed from the nominal end of the period and the current time of the
+/// RTC, rather than added to the previous one, so the latency of handling the interrupts and re-arming is
+/// compensated instead of accumulating from period to period; what remains is reported by `stats`.
+///
+/// ```ignore
+/// let mut timer = PeriodicTimer::new(Duration::days(7)).unwrap();
+/// timer.start(&mut rx8900)?;
+/// loop {
+///     if timer.on_timer_interrupt(&mut rx8900)? {
+///         run_weekly_job();
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct PeriodicTimer {
+    period: Duration,
+    next_due: Option<NaiveDateTime>,
+    stats: PeriodicStats,
+}
+
+impl PeriodicTimer {
+    /// Creates a timer for a period of whole seconds.
+    ///
+    /// # Arguments
+    /// * `period` - The period, as any `IntoRtcDuration`.
+    ///
+    /// # Returns
+    /// * `Option<PeriodicTimer>` - The timer, or None if the period is not a positive number of seconds.
+    pub fn new(period: impl IntoRtcDuration) -> Option<Self> {
+        let period = period.into_rtc_duration()?;
+        (period > Duration::zero() && period.subsec_nanos() == 0).then_some(Self {
+            period,
+            next_due: None,
+            stats: PeriodicStats::default(),
+        })
+    }
+
+    /// Returns the period.
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Returns the nominal end of the current period, or None if the timer was not started.
+    pub fn next_due(&self) -> Option<NaiveDateTime> {
+        self.next_due
+    }
+
+    /// Returns the drift and re-arming figures since the timer was started.
+    pub fn stats(&self) -> PeriodicStats {
+        self.stats
+    }
+
+    /// Returns whether the period runs on the auto-reload of the hardware, without re-arming.
+    fn hardware(&self) -> bool {
+        SourceClock::countdown(self.period).is_some()
+    }
+
+    /// Starts the first period now, and enables the timer interrupt.
+    ///
+    /// # Arguments
+    /// * `rx8900` - The RTC whose wake-up timer is used.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the timer was started, `Error::InvalidTime` if the time registers hold no valid
+    ///   date and time, `Error::BusSaturated` if the period is too short for the bus, as for
+    ///   `Rx8900::start_countdown`, `Error::InvalidInput` if the end of the period is past the dates `chrono`
+    ///   represents, or an error if a bus operation fails.
+    pub fn start<I2C, E>(&mut self, rx8900: &mut Rx8900<I2C>) -> Result<(), Error<E>>
+    where
+        I2C: Transport<Error = E>,
+    {
+        rx8900.check_bus_load(self.period)?;
+        let now = rx8900.datetime()?;
+        self.stats = PeriodicStats::default();
+        match SourceClock::countdown(self.period) {
+            Some((source, count)) => {
+                // The first count of the minute source comes at the next minute update.
+                let start = match source {
+                    SourceClock::SourceClockMinute => now.with_second(0).unwrap_or(now),
+                    _ => now,
+                };
+                self.next_due = Some(start.checked_add_signed(self.period).ok_or(Error::InvalidInput)?);
+                rx8900.start_timer(source, count)?;
+            }
+            None => {
+                self.next_due = Some(now.checked_add_signed(self.period).ok_or(Error::InvalidInput)?);
+                arm_chunk(rx8900, self.period)?;
+            }
+        }
+        rx8900.set_tie(true)
+    }
+
+    /// Stops the wake-up timer and disables its interrupt.
+    ///
+    /// # Arguments
+    /// * `rx8900` - The RTC whose wake-up timer is used.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the timer was stopped, or an error if a bus operation fails.
+    pub fn stop<I2C, E>(&mut self, rx8900: &mut Rx8900<I2C>) -> Result<(), Error<E>>
+    where
+        I2C: Transport<Error = E>,
+    {
+        self.next_due = None;
+        rx8900.set_te(false)?;
+        rx8900.set_tie(false)
+    }
+
+    /// Checks the timer flag and, if it is set, clears it and re-arms the counter if needed.
+    ///
+    /// The time is read and the next period computed before the flag is cleared, so an interrupt that fails is
+    /// handled again by the next call.
+    ///
+    /// # Arguments
+    /// * `rx8900` - The RTC whose wake-up timer is used.
+    ///
+    /// # Returns
+    /// * `Result<bool, Error<E>>` - True if a period elapsed, false if the flag was not set or only a chunk of the
+    ///   period elapsed, `Error::InvalidInput` if the timer was not started or the clock jumped so far ahead that
+    ///   the next period is past the dates `chrono` represents, or an error if a bus operation fails.
+    pub fn on_timer_interrupt<I2C, E>(&mut self, rx8900: &mut Rx8900<I2C>) -> Result<bool, Error<E>>
+    where
+        I2C: Transport<Error = E>,
+    {
+        let due = self.next_due.ok_or(Error::InvalidInput)?;
+        if !rx8900.tf()? {
+            return Ok(false);
+        }
+        let now = rx8900.datetime()?;
+        // With the auto-reload of the hardware, every expiry ends a period, even one handled early.
+        let hardware = self.hardware();
+        let elapsed = hardware || now >= due;
+        let late = now.signed_duration_since(due).num_seconds();
+        // Saturated rather than wrapped, should the clock have been set far ahead.
+        let missed = u32::try_from(late.max(0) / self.period.num_seconds()).unwrap_or(u32::MAX);
+        let next_due = if elapsed {
+            i32::try_from(missed)
+                .ok()
+                .and_then(|missed| missed.checked_add(1))
+                .and_then(|periods| self.period.checked_mul(periods))
+                .and_then(|skipped| due.checked_add_signed(skipped))
+                .ok_or(Error::InvalidInput)?
+        } else {
+            due
+        };
+        // TF
+        rx8900.clear_flags(0b00010000)?;
+        if elapsed {
+            self.next_due = Some(next_due);
+            self.stats.periods = self.stats.periods.saturating_add(1);
+            self.stats.missed = self.stats.missed.saturating_add(missed);
+            self.stats.residual = late;
+            if late.abs() > self.stats.max_residual.abs() {
+                self.stats.max_residual = late;
+            }
+        } else {
+            self.stats.rearms = self.stats.rearms.saturating_add(1);
+        }
+        if !hardware {
+            arm_chunk(rx8900, next_due - now)?;
+        }
+        Ok(elapsed)
+    }
+}
+
+/// Arms the counter for the next chunk of the time remaining until the end of a period.
+///
+/// Up to 4095 seconds are counted on the second source, which ends exactly on a clock update. Longer times are
+/// counted on the minute source, leaving at least a minute to the next chunk since its first count comes at the
+/// next minute update, which may be anywhere within a minute.
+pub(crate) fn arm_chunk<I2C, E>(rx8900: &mut Rx8900<I2C>, remaining: Duration) -> Result<(), Error<E>>
+where
+    I2C: Transport<Error = E>,
+{
+    let seconds = remaining.num_seconds().max(1);
+    if seconds <= 4095 {
+        rx8900.start_timer(SourceClock::SourceClockSecond, seconds as u16)
+    } else {
+        let minutes = (seconds / 60 - 1).min(4095);
+        rx8900.start_timer(SourceClock::SourceClockMinute, minutes as u16)
+    }
+}
@@ -0,0 +1,178 @@
+//! Wrappers for the RX8900 pins wired to the MCU, so the wiring of a board is expressed in its types.
+//!
+//! Operations needing a pin take its wrapper as an argument, so they cannot be called on a board where the pin
+//! is not wired: a board-support crate only hands out the wrappers for the pins its schematic connects.
+
+use core::convert::Infallible;
+
+use embedded_hal::digital::v2::{InputPin, OutputPin};
+
+use crate::{Error, FoutFrequency, PolledEvents, Rx8900, Transport};
+
+/// The MCU input connected to the open-drain /INT output of the RX8900.
+pub struct IntPin<P> {
+    pin: P,
+}
+
+impl<P: InputPin> IntPin<P> {
+    /// Wraps the input connected to /INT, which needs a pull-up.
+    pub fn new(pin: P) -> Self {
+        Self { pin }
+    }
+
+    /// Returns whether the RX8900 asserts /INT, i.e. holds it low.
+    ///
+    /// # Returns
+    /// * `Result<bool, P::Error>` - True if an interrupt is pending, or an error if the pin cannot be read.
+    pub fn is_asserted(&self) -> Result<bool, P::Error> {
+        self.pin.is_low()
+    }
+
+    /// Unwraps the input pin.
+    pub fn release(self) -> P {
+        self.pin
+    }
+}
+
+/// The MCU output driving the FOE input of the RX8900, which enables the FOUT clock output.
+pub struct FoePin<P> {
+    pin: P,
+}
+
+impl<P: OutputPin> FoePin<P> {
+    /// Wraps the output driving FOE.
+    pub fn new(pin: P) -> Self {
+        Self { pin }
+    }
+
+    /// Drives FOE high, enabling FOUT.
+    ///
+    /// # Returns
+    /// * `Result<(), P::Error>` - Ok if the pin was set, or an error if the pin cannot be driven.
+    pub fn enable(&mut self) -> Result<(), P::Error> {
+        self.pin.set_high()
+    }
+
+    /// Drives FOE low, turning FOUT off and lowering the current consumption.
+    ///
+    /// # Returns
+    /// * `Result<(), P::Error>` - Ok if the pin was set, or an error if the pin cannot be driven.
+    pub fn disable(&mut self) -> Result<(), P::Error> {
+        self.pin.set_low()
+    }
+
+    /// Unwraps the output pin.
+    pub fn release(self) -> P {
+        self.pin
+    }
+}
+
+impl FoePin<TiedHigh> {
+    /// Describes a FOE input tied high on the board, leaving FOUT always enabled.
+    pub fn tied_high() -> Self {
+        Self { pin: TiedHigh }
+    }
+}
+
+/// A FOE input tied high on the board rather than driven by the MCU; driving it is a no-op.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TiedHigh;
+
+impl OutputPin for TiedHigh {
+    type Error = Infallible;
+
+    fn set_low(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+/// The peripheral fed by the FOUT clock output, e.g. a timer input or the low-speed clock input of the MCU.
+///
+/// It is only handed out by `Rx8900::attach_clock_output`, once FOUT runs at a known frequency.
+pub struct ClkoutConsumer<C> {
+    consumer: C,
+    frequency: FoutFrequency,
+}
+
+impl<C> ClkoutConsumer<C> {
+    /// Returns the frequency FOUT was configured to.
+    pub fn frequency(&self) -> FoutFrequency {
+        self.frequency
+    }
+
+    /// Returns the peripheral fed by FOUT.
+    pub fn consumer(&mut self) -> &mut C {
+        &mut self.consumer
+    }
+}
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Reads and clears the flags if /INT is asserted, without accessing the bus otherwise.
+    ///
+    /// # Arguments
+    /// * `int` - The input connected to /INT.
+    ///
+    /// # Returns
+    /// * `Result<PolledEvents, R>` - The flags that were set and have been cleared, none if /INT was not asserted,
+    ///   or an error if the pin cannot be read or a bus operation fails.
+    pub fn poll_int_pin<P, R>(&mut self, int: &IntPin<P>) -> Result<PolledEvents, R>
+    where
+        P: InputPin,
+        R: From<Error<E>> + From<P::Error>,
+    {
+        if !int.is_asserted()? {
+            return Ok(PolledEvents::default());
+        }
+        Ok(self.poll_events()?)
+    }
+
+    /// Configures the FOUT frequency, enables FOUT and hands the peripheral it feeds back as a `ClkoutConsumer`.
+    ///
+    /// # Arguments
+    /// * `foe` - The output driving FOE.
+    /// * `consumer` - The peripheral fed by FOUT.
+    /// * `frequency` - The frequency to output.
+    ///
+    /// # Returns
+    /// * `Result<ClkoutConsumer<C>, R>` - The peripheral, tagged with the frequency it receives, or an error if the
+    ///   pin cannot be driven or a bus operation fails.
+    pub fn attach_clock_output<P, C, R>(
+        &mut self,
+        foe: &mut FoePin<P>,
+        consumer: C,
+        frequency: FoutFrequency,
+    ) -> Result<ClkoutConsumer<C>, R>
+    where
+        P: OutputPin,
+        R: From<Error<E>> + From<P::Error>,
+    {
+        self.set_fout_frequency(frequency)?;
+        foe.enable()?;
+        Ok(ClkoutConsumer { consumer, frequency })
+    }
+
+    /// Disables FOUT and returns the peripheral it fed.
+    ///
+    /// With FOE tied high, FOUT keeps running and only the peripheral is returned.
+    ///
+    /// # Arguments
+    /// * `foe` - The output driving FOE.
+    /// * `consumer` - The peripheral returned by `attach_clock_output`.
+    ///
+    /// # Returns
+    /// * `Result<C, P::Error>` - The peripheral, or an error if the pin cannot be driven.
+    pub fn detach_clock_output<P, C>(&mut self, foe: &mut FoePin<P>, consumer: ClkoutConsumer<C>) -> Result<C, P::Error>
+    where
+        P: OutputPin,
+    {
+        foe.disable()?;
+        Ok(consumer.consumer)
+    }
+}
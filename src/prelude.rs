@@ -0,0 +1,14 @@
+//! The types and traits needed by most applications, for a single glob import:
+//!
+//! ```ignore
+//! use rx8900::prelude::*;
+//! ```
+
+#[cfg(feature = "alarm")]
+pub use crate::{AlarmConfig, AlarmDay, AlarmType};
+#[cfg(feature = "timer")]
+pub use crate::SourceClock;
+pub use crate::{
+    CompensationIntervalType, Config, DecodeMode, Error, Flag, FoutFrequency, InitConfig, IntoNaiveUtc, IntoRtcDuration,
+    RtcAlarm, RtcDevice, Rx8900, UpdateInterruptType,
+};
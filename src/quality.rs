@@ -0,0 +1,68 @@
+//! Metadata describing how trustworthy the clock is, for loggers and TLS stacks.
+
+use chrono::NaiveDateTime;
+
+use crate::AdjustmentSource;
+
+/// Frequency tolerance of the RX8900 over its operating temperature range, in ppm.
+const TOLERANCE_PPM: u32 = 5;
+
+/// Shortest interval between two synchronizations over which the drift of the clock is measured, in seconds.
+///
+/// The clock has a resolution of one second, so shorter intervals would overestimate the drift.
+const MIN_DRIFT_INTERVAL: i64 = 86_400;
+
+/// Where the time came from, when it was last synchronized and how fast it is expected to drift.
+///
+/// Returned by `Rx8900::time_quality`. Updated by every adjustment made through the time set policy
+/// (`set_timestamp`, `set_timestamp_from`, `sync_from_ntp`, `adjust_seconds`, `adjust` and
+/// `apply_time_message`), including adjustments that found the clock already correct; `set_datetime`
+/// leaves it untouched.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimeQuality {
+    /// The source of the last synchronization, or None if the clock was never synchronized.
+    pub source: Option<AdjustmentSource>,
+    /// The time of the clock at the last synchronization, after the adjustment.
+    pub last_sync: Option<NaiveDateTime>,
+    /// The estimated drift of the clock in ppm: the drift measured between the last two synchronizations at
+    /// least a day apart, and never less than the tolerance of the RX8900 (5 ppm).
+    pub estimated_error_ppm: u32,
+}
+
+impl Default for TimeQuality {
+    fn default() -> Self {
+        Self { source: None, last_sync: None, estimated_error_ppm: TOLERANCE_PPM }
+    }
+}
+
+impl TimeQuality {
+    /// Estimates the error accumulated since the last synchronization.
+    ///
+    /// # Arguments
+    /// * `now` - The current time of the clock.
+    ///
+    /// # Returns
+    /// * `Option<u64>` - The estimated error in milliseconds, or None if the clock was never synchronized.
+    pub fn estimated_error_ms(&self, now: NaiveDateTime) -> Option<u64> {
+        let elapsed = (now - self.last_sync?).num_seconds().unsigned_abs();
+        Some(elapsed.saturating_mul(self.estimated_error_ppm as u64) / 1_000)
+    }
+
+    /// Records a synchronization.
+    ///
+    /// # Arguments
+    /// * `old` - The time of the clock just before the adjustment.
+    /// * `correction` - The correction applied in seconds.
+    /// * `source` - Where the time came from.
+    pub(crate) fn record(&mut self, old: NaiveDateTime, correction: i64, source: AdjustmentSource) {
+        if let Some(last_sync) = self.last_sync {
+            let elapsed = (old - last_sync).num_seconds();
+            if elapsed >= MIN_DRIFT_INTERVAL {
+                let drift = correction.unsigned_abs().saturating_mul(1_000_000) / elapsed as u64;
+                self.estimated_error_ppm = u32::try_from(drift).unwrap_or(u32::MAX).max(TOLERANCE_PPM);
+            }
+        }
+        self.source = Some(source);
+        self.last_sync = Some(old + chrono::Duration::seconds(correction));
+    }
+}
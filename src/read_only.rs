@@ -0,0 +1,133 @@
+//! A view of the driver exposing only the getters, for supervisor and monitor cores.
+
+use chrono::{DateTime, IsoWeek, NaiveDateTime, NaiveTime, Utc, Weekday};
+#[cfg(feature = "alarm")]
+use heapless::Vec;
+
+use crate::{AmPm, Config, DecodedTime, Error, NamedOffset, RegisterDump, Rx8900, Snapshot, StateBlob, Transport};
+
+/// An RX8900 driver that can only read the chip.
+///
+/// None of its methods writes a register, so a core holding it can never change the time, the configuration or
+/// the flags, not even by clearing a flag it has seen. Create it with `new` on a bus of its own, or from a driver
+/// with `Rx8900::into_read_only`.
+pub struct Rx8900ReadOnly<I2C> {
+    rx8900: Rx8900<I2C>,
+}
+
+impl<I2C> Rx8900ReadOnly<I2C> {
+    /// Creates a read-only driver.
+    ///
+    /// # Arguments
+    /// * `i2c` - An instance of the I2C peripheral to communicate with the RX8900.
+    pub fn new(i2c: I2C) -> Self {
+        Self { rx8900: Rx8900::new_unchecked(i2c) }
+    }
+
+    /// Unwraps the bus.
+    pub fn release(self) -> I2C {
+        self.rx8900.release()
+    }
+}
+
+impl<I2C> Rx8900<I2C> {
+    /// Turns the driver into a view that can only read the chip, keeping its settings.
+    pub fn into_read_only(self) -> Rx8900ReadOnly<I2C> {
+        Rx8900ReadOnly { rx8900: self }
+    }
+}
+
+impl<I2C> From<Rx8900<I2C>> for Rx8900ReadOnly<I2C> {
+    fn from(rx8900: Rx8900<I2C>) -> Self {
+        rx8900.into_read_only()
+    }
+}
+
+/// Forwards getters to the wrapped driver, which documents them.
+macro_rules! getters {
+    ($($(#[$attr:meta])* $name:ident -> $ret:ty;)*) => {
+        $(
+            $(#[$attr])*
+            #[doc = concat!("See `Rx8900::", stringify!($name), "`.")]
+            pub fn $name(&mut self) -> $ret {
+                self.rx8900.$name()
+            }
+        )*
+    };
+}
+
+impl<I2C, E> Rx8900ReadOnly<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    getters! {
+        datetime -> Result<NaiveDateTime, Error<E>>;
+        datetime_decoded -> Result<DecodedTime, Error<E>>;
+        datetime_coherent -> Result<NaiveDateTime, Error<E>>;
+        datetime_fast -> Result<NaiveTime, Error<E>>;
+        read_time_raw -> Result<[u8; 7], E>;
+        now_utc -> Result<DateTime<Utc>, Error<E>>;
+        timestamp -> Result<i64, Error<E>>;
+        sec -> Result<u8, Error<E>>;
+        min -> Result<u8, Error<E>>;
+        hour -> Result<u8, Error<E>>;
+        hour12 -> Result<(u8, AmPm), Error<E>>;
+        week -> Result<Weekday, Error<E>>;
+        day -> Result<u8, Error<E>>;
+        month -> Result<u8, Error<E>>;
+        year -> Result<u8, Error<E>>;
+        ordinal -> Result<u32, Error<E>>;
+        iso_week -> Result<IsoWeek, Error<E>>;
+        ram -> Result<u8, E>;
+        stored_zone -> Result<Option<NamedOffset>, E>;
+        owner -> Result<Option<u8>, E>;
+        #[cfg(feature = "alarm")]
+        min_alarm -> Result<u8, Error<E>>;
+        #[cfg(feature = "alarm")]
+        min_alarm_enabled -> Result<bool, E>;
+        #[cfg(feature = "alarm")]
+        hour_alarm -> Result<u8, Error<E>>;
+        #[cfg(feature = "alarm")]
+        hour_alarm_enabled -> Result<bool, E>;
+        #[cfg(feature = "alarm")]
+        week_alarm -> Result<Vec<Weekday, 7>, E>;
+        #[cfg(feature = "alarm")]
+        week_alarm_enabled -> Result<bool, E>;
+        #[cfg(feature = "alarm")]
+        day_alarm -> Result<u8, Error<E>>;
+        #[cfg(feature = "alarm")]
+        day_alarm_enabled -> Result<bool, E>;
+        #[cfg(feature = "alarm")]
+        scheduled_wake -> Result<Option<NaiveDateTime>, Error<E>>;
+        #[cfg(feature = "timer")]
+        timer_counter -> Result<u16, E>;
+        #[cfg(feature = "temperature")]
+        temp -> Result<u8, E>;
+        #[cfg(feature = "temperature")]
+        temp_in_millicelsius -> Result<i32, E>;
+        #[cfg(all(feature = "temperature", feature = "float"))]
+        temp_in_celsius -> Result<f32, E>;
+        update_flag -> Result<bool, E>;
+        #[cfg(feature = "timer")]
+        timer_flag -> Result<bool, E>;
+        #[cfg(feature = "alarm")]
+        alarm_flag -> Result<bool, E>;
+        voltage_low_flag -> Result<bool, E>;
+        voltage_detect_flag -> Result<bool, E>;
+        config -> Result<Config, E>;
+        snapshot -> Result<Snapshot, Error<E>>;
+        register_dump -> Result<RegisterDump, E>;
+        export_state -> Result<StateBlob, E>;
+        is_present -> Result<bool, E>;
+    }
+
+    /// See `Rx8900::read_registers_into`.
+    pub fn read_registers_into(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Error<E>> {
+        self.rx8900.read_registers_into(address, buffer)
+    }
+
+    /// See `Rx8900::read_time_raw_into`.
+    pub fn read_time_raw_into(&mut self, buffer: &mut [u8; 7]) -> Result<(), E> {
+        self.rx8900.read_time_raw_into(buffer)
+    }
+}
@@ -0,0 +1,162 @@
+//! A pair of RX8900s used as main and backup clock on redundant boards.
+
+use chrono::NaiveDateTime;
+
+use crate::{Error, Rx8900, Transport};
+
+/// One of the two devices of a `RedundantRtc`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    /// The main RTC.
+    Main,
+    /// The backup RTC.
+    Backup,
+}
+
+/// The state of a `RedundantRtc` found by `RedundantRtc::check`.
+///
+/// A device is failed when it cannot be read or its VLF flag shows it lost its time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Health {
+    /// Both devices agree within the threshold.
+    Healthy,
+    /// Both devices run but differ by more than the threshold; which one is right cannot be told.
+    Diverged {
+        /// Main minus backup, in seconds.
+        difference: i64,
+    },
+    /// Only the given device is usable.
+    Failed(Side),
+    /// Neither device is usable.
+    BothFailed,
+}
+
+impl Health {
+    /// Returns the device to trust, preferring the main one when both are healthy.
+    pub fn healthy_side(&self) -> Option<Side> {
+        match self {
+            Health::Healthy => Some(Side::Main),
+            Health::Failed(Side::Main) => Some(Side::Backup),
+            Health::Failed(Side::Backup) => Some(Side::Main),
+            Health::Diverged { .. } | Health::BothFailed => None,
+        }
+    }
+}
+
+/// Two RX8900s kept in agreement, for designs that carry a main and a backup RTC.
+///
+/// `check` reads both and reports divergence beyond a threshold, and `resync` copies the time of
+/// the healthy device to the other one.
+pub struct RedundantRtc<A, B> {
+    main: Rx8900<A>,
+    backup: Rx8900<B>,
+    threshold: u32,
+}
+
+impl<A, B> RedundantRtc<A, B> {
+    /// Creates a pair from two drivers.
+    ///
+    /// # Arguments
+    /// * `main` - The main RTC.
+    /// * `backup` - The backup RTC.
+    /// * `threshold` - The largest difference, in seconds, still considered in agreement.
+    pub fn new(main: Rx8900<A>, backup: Rx8900<B>, threshold: u32) -> Self {
+        Self { main, backup, threshold }
+    }
+
+    /// Returns the main RTC.
+    pub fn main(&mut self) -> &mut Rx8900<A> {
+        &mut self.main
+    }
+
+    /// Returns the backup RTC.
+    pub fn backup(&mut self) -> &mut Rx8900<B> {
+        &mut self.backup
+    }
+
+    /// Returns the two drivers.
+    pub fn into_inner(self) -> (Rx8900<A>, Rx8900<B>) {
+        (self.main, self.backup)
+    }
+}
+
+impl<A, B, E> RedundantRtc<A, B>
+where
+    A: Transport<Error = E>,
+    B: Transport<Error = E>,
+{
+    /// Reads both devices and compares them.
+    ///
+    /// # Returns
+    /// * `Health` - The state of the pair; bus errors are reported as failed devices.
+    pub fn check(&mut self) -> Health {
+        match (Self::valid_datetime(&mut self.main), Self::valid_datetime(&mut self.backup)) {
+            (Some(main), Some(backup)) => {
+                let difference = (main - backup).num_seconds();
+                if difference.unsigned_abs() <= self.threshold as u64 {
+                    Health::Healthy
+                } else {
+                    Health::Diverged { difference }
+                }
+            }
+            (Some(_), None) => Health::Failed(Side::Backup),
+            (None, Some(_)) => Health::Failed(Side::Main),
+            (None, None) => Health::BothFailed,
+        }
+    }
+
+    /// Reads the date and time from the healthy device, preferring the main one.
+    ///
+    /// # Returns
+    /// * `Option<NaiveDateTime>` - The date and time, or None if neither device can be trusted.
+    pub fn datetime(&mut self) -> Option<NaiveDateTime> {
+        match self.check().healthy_side()? {
+            Side::Main => Self::valid_datetime(&mut self.main),
+            Side::Backup => Self::valid_datetime(&mut self.backup),
+        }
+    }
+
+    /// Copies the date and time of one device to the other and clears the VLF flag of the target.
+    ///
+    /// The copy has a resolution of one second, so the two devices may still differ by up to one second.
+    ///
+    /// # Arguments
+    /// * `from` - The device to copy from.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the target was set, `Error::InvalidTime` if the time registers hold no valid
+    ///   date and time, or an error if a bus operation fails.
+    pub fn resync(&mut self, from: Side) -> Result<(), Error<E>> {
+        match from {
+            Side::Main => {
+                let now = self.main.datetime()?;
+                Self::set(&mut self.backup, now)
+            }
+            Side::Backup => {
+                let now = self.backup.datetime()?;
+                Self::set(&mut self.main, now)
+            }
+        }
+    }
+
+    /// Reads the date and time of a device that has not lost its time.
+    fn valid_datetime<I2C>(rx8900: &mut Rx8900<I2C>) -> Option<NaiveDateTime>
+    where
+        I2C: Transport<Error = E>,
+    {
+        if rx8900.vlf().ok()? {
+            return None;
+        }
+        rx8900.datetime().ok()
+    }
+
+    /// Sets the date and time of a device and clears its VLF flag.
+    fn set<I2C>(rx8900: &mut Rx8900<I2C>, now: NaiveDateTime) -> Result<(), Error<E>>
+    where
+        I2C: Transport<Error = E>,
+    {
+        rx8900.set_datetime(now)?;
+        // VLF
+        rx8900.clear_flags(0b00000010)
+    }
+}
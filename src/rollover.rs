@@ -0,0 +1,118 @@
+//! Callbacks on the rollover of the second, minute, hour and day, dispatched from the update interrupt.
+
+use chrono::{NaiveDateTime, Timelike};
+
+use crate::{Error, Rx8900, Transport};
+
+/// Invokes user callbacks when the second, minute, hour or day of the RTC rolls over.
+///
+/// Enable the update interrupt, once per second or once per minute depending on `UpdateInterruptType`, then
+/// call `on_update_interrupt` from the main loop whenever the INT pin is asserted (or simply poll it). The time
+/// is read once per update and compared with the previous one, and each callback runs only when its field
+/// changed; callbacks run in the caller's context and receive the new time. The first update only records the
+/// time. If updates were missed, a callback runs once for all of them.
+///
+/// ```ignore
+/// let mut log = |now| log_hourly(now);
+/// let mut rollover = Rollover::new();
+/// rollover.on_hour(&mut log);
+/// rx8900.set_update_interrupt_enable(true)?;
+/// loop {
+///     rollover.on_update_interrupt(&mut rx8900)?;
+/// }
+/// ```
+#[derive(Default)]
+pub struct Rollover<'a> {
+    last: Option<NaiveDateTime>,
+    second: Option<&'a mut dyn FnMut(NaiveDateTime)>,
+    minute: Option<&'a mut dyn FnMut(NaiveDateTime)>,
+    hour: Option<&'a mut dyn FnMut(NaiveDateTime)>,
+    day: Option<&'a mut dyn FnMut(NaiveDateTime)>,
+}
+
+impl<'a> Rollover<'a> {
+    /// Creates a dispatcher without any callback.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the callback run when the second changes, replacing the previous one.
+    pub fn on_second(&mut self, callback: &'a mut dyn FnMut(NaiveDateTime)) {
+        self.second = Some(callback);
+    }
+
+    /// Sets the callback run when the minute changes, replacing the previous one.
+    pub fn on_minute(&mut self, callback: &'a mut dyn FnMut(NaiveDateTime)) {
+        self.minute = Some(callback);
+    }
+
+    /// Sets the callback run when the hour changes, replacing the previous one.
+    pub fn on_hour(&mut self, callback: &'a mut dyn FnMut(NaiveDateTime)) {
+        self.hour = Some(callback);
+    }
+
+    /// Sets the callback run when the date changes, replacing the previous one.
+    pub fn on_day(&mut self, callback: &'a mut dyn FnMut(NaiveDateTime)) {
+        self.day = Some(callback);
+    }
+
+    /// Compares a time with the previous one and runs the callbacks of the fields that changed.
+    ///
+    /// Callbacks run from the finest field to the coarsest, so the minute callback runs before the hour one.
+    ///
+    /// # Arguments
+    /// * `now` - The current time of the RTC.
+    pub fn update(&mut self, now: NaiveDateTime) {
+        let Some(last) = self.last.replace(now) else {
+            return;
+        };
+        if now == last {
+            return;
+        }
+        // A change of date changes every field, even when the time of day happens to be the same.
+        let day = now.date() != last.date();
+        let hour = day || now.hour() != last.hour();
+        let minute = hour || now.minute() != last.minute();
+        let fields = [
+            (true, &mut self.second),
+            (minute, &mut self.minute),
+            (hour, &mut self.hour),
+            (day, &mut self.day),
+        ];
+        for (changed, callback) in fields {
+            if let (true, Some(callback)) = (changed, callback) {
+                callback(now);
+            }
+        }
+    }
+
+    /// Forgets the previous time, e.g. after the clock was set, so the next update only records the time.
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+
+    /// Checks the update flag of the RTC and, if it is set, reads the time, clears the flag and runs the callbacks.
+    ///
+    /// The time is read before the flag is cleared, so an update whose time could not be read stays pending and is
+    /// processed by the next call.
+    ///
+    /// # Arguments
+    /// * `rx8900` - The RTC generating the update interrupts.
+    ///
+    /// # Returns
+    /// * `Result<bool, Error<E>>` - True if an update was processed, or an error if a bus operation fails or the
+    ///   registers do not hold a valid date and time.
+    pub fn on_update_interrupt<I2C, E>(&mut self, rx8900: &mut Rx8900<I2C>) -> Result<bool, Error<E>>
+    where
+        I2C: Transport<Error = E>,
+    {
+        if !rx8900.uf()? {
+            return Ok(false);
+        }
+        let now = rx8900.datetime()?;
+        // UF
+        rx8900.clear_flags(0b00100000)?;
+        self.update(now);
+        Ok(true)
+    }
+}
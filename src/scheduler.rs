@@ -0,0 +1,111 @@
+//! A cooperative periodic-task runner anchored to the RX8900 update interrupt.
+
+use crate::{Error, Rx8900, Transport};
+
+/// Identifies a task registered with a `TickScheduler`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TaskId(usize);
+
+/// A periodic task: the callback runs every `period` ticks.
+struct Task<'a> {
+    period: u32,
+    remaining: u32,
+    callback: &'a mut dyn FnMut(),
+}
+
+/// Runs up to `N` callbacks, each every given number of ticks.
+///
+/// A tick is one update interrupt of the RX8900, i.e. one second or one minute depending on
+/// `UpdateInterruptType`. Enable the update interrupt, then call `on_update_interrupt` from the
+/// main loop whenever the INT pin is asserted (or simply poll it); callbacks run in the caller's context.
+///
+/// ```ignore
+/// let mut blink = || led.toggle();
+/// let mut log = || log_sample();
+/// let mut scheduler = TickScheduler::<2>::new();
+/// scheduler.every(1, &mut blink).unwrap();
+/// scheduler.every(60, &mut log).unwrap();
+/// rx8900.set_update_interrupt_enable(true)?;
+/// loop {
+///     scheduler.on_update_interrupt(&mut rx8900)?;
+/// }
+/// ```
+pub struct TickScheduler<'a, const N: usize> {
+    tasks: [Option<Task<'a>>; N],
+    ticks: u32,
+}
+
+impl<'a, const N: usize> Default for TickScheduler<'a, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, const N: usize> TickScheduler<'a, N> {
+    /// Creates a scheduler without any task.
+    pub fn new() -> Self {
+        Self { tasks: [(); N].map(|_| None), ticks: 0 }
+    }
+
+    /// Registers a callback to run every `period` ticks, starting `period` ticks from now.
+    ///
+    /// # Arguments
+    /// * `period` - The number of ticks between two runs; 0 is treated as 1.
+    /// * `callback` - The callback to run.
+    ///
+    /// # Returns
+    /// * `Option<TaskId>` - The id of the task, or None if all `N` slots are in use.
+    pub fn every(&mut self, period: u32, callback: &'a mut dyn FnMut()) -> Option<TaskId> {
+        let period = period.max(1);
+        let index = self.tasks.iter().position(Option::is_none)?;
+        self.tasks[index] = Some(Task { period, remaining: period, callback });
+        Some(TaskId(index))
+    }
+
+    /// Removes a task, so its callback does not run anymore.
+    ///
+    /// # Arguments
+    /// * `id` - The id returned by `every`.
+    pub fn cancel(&mut self, id: TaskId) {
+        if let Some(task) = self.tasks.get_mut(id.0) {
+            *task = None;
+        }
+    }
+
+    /// Returns the number of ticks seen since the scheduler was created.
+    pub fn ticks(&self) -> u32 {
+        self.ticks
+    }
+
+    /// Advances the scheduler by one tick and runs the callbacks that are due.
+    pub fn tick(&mut self) {
+        self.ticks = self.ticks.wrapping_add(1);
+        for task in self.tasks.iter_mut().flatten() {
+            task.remaining -= 1;
+            if task.remaining == 0 {
+                task.remaining = task.period;
+                (task.callback)();
+            }
+        }
+    }
+
+    /// Checks the update flag of the RTC and, if it is set, clears it and advances the scheduler by one tick.
+    ///
+    /// # Arguments
+    /// * `rx8900` - The RTC generating the update interrupts.
+    ///
+    /// # Returns
+    /// * `Result<bool, Error<E>>` - True if a tick was processed, or an error if the bus operation fails.
+    pub fn on_update_interrupt<I2C, E>(&mut self, rx8900: &mut Rx8900<I2C>) -> Result<bool, Error<E>>
+    where
+        I2C: Transport<Error = E>,
+    {
+        if !rx8900.uf()? {
+            return Ok(false);
+        }
+        // UF
+        rx8900.clear_flags(0b00100000)?;
+        self.tick();
+        Ok(true)
+    }
+}
@@ -0,0 +1,69 @@
+//! Shadow copies of the configuration registers, used to skip the read of read-modify-write cycles.
+
+use crate::RegisterTable;
+
+/// The configuration registers whose value only changes when the host writes them.
+///
+/// The flag register is never cached since the chip sets flags on its own.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Shadow {
+    enabled: bool,
+    extension: Option<u8>,
+    control: Option<u8>,
+    backup_function: Option<u8>,
+}
+
+impl Shadow {
+    /// Enables or disables the cache, dropping all cached values.
+    pub(crate) fn set_enabled(&mut self, enabled: bool) {
+        *self = Self { enabled, ..Self::default() };
+    }
+
+    /// Returns whether the cache is enabled.
+    pub(crate) fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Drops all cached values, keeping the cache enabled or disabled.
+    pub(crate) fn invalidate(&mut self) {
+        self.set_enabled(self.enabled);
+    }
+
+    /// Returns the cached value of a register, if the cache is enabled and holds one.
+    pub(crate) fn get(&mut self, register: RegisterTable) -> Option<u8> {
+        if !self.enabled {
+            return None;
+        }
+        self.slot(register as u8).and_then(|slot| *slot)
+    }
+
+    /// Records a value just read from or written to a register.
+    ///
+    /// # Arguments
+    /// * `address` - The register address; registers that are not cached are ignored.
+    /// * `data` - The register value.
+    pub(crate) fn store(&mut self, address: u8, data: u8) {
+        if !self.enabled {
+            return;
+        }
+        let data = if address & 0x0F == RegisterTable::CompatibleControlRegister as u8 {
+            // RESET clears itself once the divider chain has been reset.
+            data & !0b00000001
+        } else {
+            data
+        };
+        if let Some(slot) = self.slot(address) {
+            *slot = Some(data);
+        }
+    }
+
+    /// Returns the cache slot of a register, resolving the extended register mirrors.
+    fn slot(&mut self, address: u8) -> Option<&mut Option<u8>> {
+        match address {
+            0x0D | 0x1D => Some(&mut self.extension),
+            0x0F | 0x1F => Some(&mut self.control),
+            0x18 => Some(&mut self.backup_function),
+            _ => None,
+        }
+    }
+}
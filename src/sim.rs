@@ -0,0 +1,447 @@
+//! An in-memory model of the RX8900 register file, for exercising the driver on the host without hardware.
+
+use core::convert::Infallible;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Timelike};
+use embedded_hal::blocking::i2c::{Read, Write, WriteRead};
+
+use crate::bcd;
+use crate::error::UnwrapInfallible;
+use crate::{RegisterTable, FLAG_BITS, RX8900_ADDR};
+
+/// Number of addressable registers, including the extended register bank.
+pub const REGISTER_COUNT: usize = 32;
+
+/// The error of a simulated transaction the chip did not acknowledge, reported by `Faulty`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Nack;
+
+/// The failure modes a `Simulator` reproduces, all off by default.
+///
+/// Failures are deterministic, so a test reproducing a failure mode behaves the same on every run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ErrorModel {
+    /// Deviation of the simulated oscillator, in ppm; positive runs fast. Applied by `Simulator::elapse`.
+    pub drift_ppm: i32,
+}
+
+/// A simulated RX8900 that implements the blocking I2C traits.
+///
+/// The extended registers 0x10-0x16 and 0x1B-0x1F are mirrors of the compatible registers
+/// 0x00-0x06 and 0x0B-0x0F, like on the real chip. The register pointer auto-increments after
+/// each byte and wraps around at 0x1F.
+///
+/// An `ErrorModel` adds clock drift, and `inject_flag_race` raises flags right after the host
+/// read them, to exercise retry and consistency logic. `advance`, `set_time` and `fire_alarm`
+/// script the passing of time deterministically. Transactions never fail; wrap the simulator
+/// in a `Faulty` bus to inject NACKs.
+#[derive(Clone, Debug)]
+pub struct Simulator {
+    registers: [u8; REGISTER_COUNT],
+    pointer: u8,
+    model: ErrorModel,
+    transactions: u32,
+    pending_flags: u8,
+    fraction_ns: i64,
+}
+
+impl Default for Simulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Simulator {
+    /// Creates a simulator in the state of a freshly powered chip.
+    ///
+    /// The calendar reads 2000-01-01 (Saturday) 00:00:00 and the VLF flag is set.
+    pub fn new() -> Self {
+        let mut registers = [0; REGISTER_COUNT];
+        registers[RegisterTable::CompatibleWEEK as usize] = 0b01000000;
+        registers[RegisterTable::CompatibleDAY as usize] = 0x01;
+        registers[RegisterTable::CompatibleMONTH as usize] = 0x01;
+        registers[RegisterTable::CompatibleFlagRegister as usize] = 0b00000010;
+        Self { registers, ..Self::from_registers(&[]) }
+    }
+
+    /// Creates a simulator from a raw register image.
+    ///
+    /// # Arguments
+    /// * `data` - The register contents starting at address 0x00. Images shorter than 32 bytes
+    ///   leave the remaining registers cleared, longer images are truncated.
+    pub fn from_registers(data: &[u8]) -> Self {
+        let mut simulator = Self {
+            registers: [0; REGISTER_COUNT],
+            pointer: 0,
+            model: ErrorModel::default(),
+            transactions: 0,
+            pending_flags: 0,
+            fraction_ns: 0,
+        };
+        for (address, value) in data.iter().take(REGISTER_COUNT).enumerate() {
+            simulator.registers[Self::index(address as u8)] = *value;
+        }
+        simulator
+    }
+
+    /// Returns the value of a register as the chip would report it.
+    ///
+    /// # Arguments
+    /// * `address` - The register address (0x00-0x1F).
+    pub fn register(&self, address: u8) -> u8 {
+        self.registers[Self::index(address)]
+    }
+
+    /// Overwrites a register directly, bypassing the write rules of the flag register.
+    ///
+    /// # Arguments
+    /// * `address` - The register address (0x00-0x1F).
+    /// * `data` - The new register value.
+    pub fn set_register(&mut self, address: u8, data: u8) {
+        self.registers[Self::index(address)] = data;
+    }
+
+    /// Sets the failure modes to reproduce.
+    ///
+    /// # Arguments
+    /// * `model` - The failure modes.
+    pub fn set_error_model(&mut self, model: ErrorModel) {
+        self.model = model;
+    }
+
+    /// Returns the failure modes reproduced.
+    pub fn error_model(&self) -> ErrorModel {
+        self.model
+    }
+
+    /// Returns the number of transactions the host started.
+    pub fn transactions(&self) -> u32 {
+        self.transactions
+    }
+
+    /// Raises flags right after the host next reads the flag register, as if they were set between the read and
+    /// the write clearing the flags seen.
+    ///
+    /// # Arguments
+    /// * `flags` - The flags to raise, e.g. `0b00001000` for AF.
+    pub fn inject_flag_race(&mut self, flags: u8) {
+        self.pending_flags |= flags & FLAG_BITS;
+    }
+
+    /// Lets real time pass, advancing the calendar by that time with the drift of the `ErrorModel` applied.
+    ///
+    /// Fractions of a second carry over to the next call. Flags are raised as by `advance`.
+    ///
+    /// # Arguments
+    /// * `elapsed` - The real time passed.
+    pub fn elapse(&mut self, elapsed: Duration) {
+        let Some(ns) = elapsed.num_nanoseconds() else {
+            return;
+        };
+        self.fraction_ns += ns + ns / 1_000_000 * i64::from(self.model.drift_ppm);
+        let seconds = self.fraction_ns.div_euclid(1_000_000_000);
+        self.fraction_ns = self.fraction_ns.rem_euclid(1_000_000_000);
+        self.advance(Duration::seconds(seconds));
+    }
+
+    /// Moves the calendar forward by whole seconds, exactly, raising the flags the chip would raise meanwhile.
+    ///
+    /// UF is raised if a second (or, with USEL set, a minute) update happened, and AF if the alarm matched at one
+    /// of the minutes passed. The calendar is left as is while its registers hold no valid date and time, and
+    /// durations that are not positive do nothing.
+    ///
+    /// # Arguments
+    /// * `duration` - The time to skip; fractions of a second are dropped.
+    pub fn advance(&mut self, duration: Duration) {
+        let seconds = duration.num_seconds();
+        let Some(start) = self.time() else {
+            return;
+        };
+        let Some(end) = start.checked_add_signed(Duration::seconds(seconds)).filter(|_| seconds > 0) else {
+            return;
+        };
+        // USEL
+        let minute_updates = self.register(RegisterTable::CompatibleExtensionRegister as u8) & 0b00100000 != 0;
+        let first_minute = start.with_second(0).unwrap_or(start) + Duration::minutes(1);
+        let mut flags = 0;
+        if !minute_updates || first_minute <= end {
+            // UF
+            flags |= 0b00100000;
+        }
+        let mut minute = first_minute;
+        while minute <= end {
+            if self.alarm_matches(minute) {
+                // AF
+                flags |= 0b00001000;
+                break;
+            }
+            minute += Duration::minutes(1);
+        }
+        self.store_datetime(end);
+        self.raise_flags(flags);
+    }
+
+    /// Sets the calendar, as a host write of the time registers would, and restarts the sub-second count.
+    ///
+    /// # Arguments
+    /// * `datetime` - The new date and time; the year is stored modulo 100.
+    pub fn set_time(&mut self, datetime: NaiveDateTime) {
+        self.store_datetime(datetime);
+        self.fraction_ns = 0;
+    }
+
+    /// Returns the date and time the calendar registers hold.
+    ///
+    /// # Returns
+    /// * `Option<NaiveDateTime>` - The date and time in 2000-2099, or None if the registers hold invalid values.
+    pub fn time(&self) -> Option<NaiveDateTime> {
+        let field = |register: RegisterTable| bcd::checked_from_bcd(self.register(register as u8)).ok();
+        NaiveDate::from_ymd_opt(
+            2000 + i32::from(field(RegisterTable::CompatibleYEAR)?),
+            u32::from(field(RegisterTable::CompatibleMONTH)?),
+            u32::from(field(RegisterTable::CompatibleDAY)?),
+        )?
+        .and_hms_opt(
+            u32::from(field(RegisterTable::CompatibleHOUR)?),
+            u32::from(field(RegisterTable::CompatibleMIN)?),
+            u32::from(field(RegisterTable::CompatibleSEC)?),
+        )
+    }
+
+    /// Raises the alarm flag now, whatever the alarm registers hold.
+    pub fn fire_alarm(&mut self) {
+        // AF
+        self.raise_flags(0b00001000);
+    }
+
+    /// Sets flags as the chip does on an event, leaving the others unchanged.
+    fn raise_flags(&mut self, flags: u8) {
+        let index = RegisterTable::CompatibleFlagRegister as usize;
+        self.registers[index] |= flags & FLAG_BITS;
+    }
+
+    /// Checks whether the alarm registers match a minute, honouring their AE bits and the WADA bit.
+    fn alarm_matches(&self, minute: NaiveDateTime) -> bool {
+        let matches = |register: RegisterTable, value: u8| {
+            let data = self.register(register as u8);
+            // AE
+            data & 0b10000000 != 0 || data & 0b01111111 == value
+        };
+        // WADA
+        let day_alarm = self.register(RegisterTable::CompatibleExtensionRegister as u8) & 0b01000000 != 0;
+        let day_matches = if day_alarm {
+            matches(RegisterTable::CompatibleWeekDayAlarm, bcd::to_bcd(minute.day() as u8))
+        } else {
+            let data = self.register(RegisterTable::CompatibleWeekDayAlarm as u8);
+            data & 0b10000000 != 0 || data & (1 << minute.weekday().num_days_from_sunday()) != 0
+        };
+        matches(RegisterTable::CompatibleMinAlarm, bcd::to_bcd(minute.minute() as u8))
+            && matches(RegisterTable::CompatibleHourAlarm, bcd::to_bcd(minute.hour() as u8))
+            && day_matches
+    }
+
+    /// Encodes a date and time into the calendar registers, wrapping the year to two digits.
+    fn store_datetime(&mut self, datetime: NaiveDateTime) {
+        let fields = [
+            (RegisterTable::CompatibleSEC, bcd::to_bcd(datetime.second() as u8)),
+            (RegisterTable::CompatibleMIN, bcd::to_bcd(datetime.minute() as u8)),
+            (RegisterTable::CompatibleHOUR, bcd::to_bcd(datetime.hour() as u8)),
+            (RegisterTable::CompatibleWEEK, 1 << datetime.weekday().num_days_from_sunday()),
+            (RegisterTable::CompatibleDAY, bcd::to_bcd(datetime.day() as u8)),
+            (RegisterTable::CompatibleMONTH, bcd::to_bcd(datetime.month() as u8)),
+            (RegisterTable::CompatibleYEAR, bcd::to_bcd(datetime.year().rem_euclid(100) as u8)),
+        ];
+        for (register, value) in fields {
+            self.set_register(register as u8, value);
+        }
+    }
+
+    /// Counts a transaction started by the host.
+    fn transaction(&mut self) {
+        self.transactions = self.transactions.wrapping_add(1);
+    }
+
+    /// Maps an address to its backing storage, resolving the extended register mirrors.
+    fn index(address: u8) -> usize {
+        let address = address & 0x1F;
+        match address {
+            0x10..=0x16 | 0x1B..=0x1F => (address - 0x10) as usize,
+            _ => address as usize,
+        }
+    }
+
+    /// Handles a byte written by the host at the current register pointer.
+    fn write_byte(&mut self, data: u8) {
+        let index = Self::index(self.pointer);
+        if index == RegisterTable::CompatibleFlagRegister as usize {
+            let current = self.registers[index];
+            self.registers[index] = (data & !FLAG_BITS) | (current & data & FLAG_BITS);
+        } else {
+            self.registers[index] = data;
+        }
+        self.pointer = (self.pointer + 1) & 0x1F;
+    }
+
+    /// Returns the byte at the current register pointer and advances it.
+    fn read_byte(&mut self) -> u8 {
+        let index = Self::index(self.pointer);
+        let data = self.registers[index];
+        if index == RegisterTable::CompatibleFlagRegister as usize {
+            self.registers[index] |= self.pending_flags;
+            self.pending_flags = 0;
+        }
+        self.pointer = (self.pointer + 1) & 0x1F;
+        data
+    }
+
+    /// Sets the register pointer and writes the bytes following it.
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        if let Some((pointer, data)) = bytes.split_first() {
+            self.pointer = pointer & 0x1F;
+            for byte in data {
+                self.write_byte(*byte);
+            }
+        }
+    }
+
+    /// Reads bytes from the register pointer on.
+    fn read_bytes(&mut self, buffer: &mut [u8]) {
+        for byte in buffer.iter_mut() {
+            *byte = self.read_byte();
+        }
+    }
+}
+
+impl Write for Simulator {
+    type Error = Infallible;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        debug_assert_eq!(address, RX8900_ADDR);
+        self.transaction();
+        self.write_bytes(bytes);
+        Ok(())
+    }
+}
+
+impl Read for Simulator {
+    type Error = Infallible;
+
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        debug_assert_eq!(address, RX8900_ADDR);
+        self.transaction();
+        self.read_bytes(buffer);
+        Ok(())
+    }
+}
+
+impl WriteRead for Simulator {
+    type Error = Infallible;
+
+    fn write_read(&mut self, address: u8, bytes: &[u8], buffer: &mut [u8]) -> Result<(), Self::Error> {
+        debug_assert_eq!(address, RX8900_ADDR);
+        self.transaction();
+        self.write_bytes(bytes);
+        self.read_bytes(buffer);
+        Ok(())
+    }
+}
+
+/// A bus failing every n-th transaction with `Nack`, leaving the registers of the device behind it untouched.
+///
+/// Wraps an infallible bus such as the `Simulator`, so tests that do not inject NACKs keep an infallible
+/// error type.
+#[derive(Clone, Debug)]
+pub struct Faulty<B> {
+    bus: B,
+    nack_every: u32,
+    transactions: u32,
+    nacks: u32,
+}
+
+impl<B> Faulty<B> {
+    /// Wraps a bus.
+    ///
+    /// # Arguments
+    /// * `bus` - The bus to wrap.
+    /// * `nack_every` - Fails every n-th transaction; 0 never fails.
+    pub fn new(bus: B, nack_every: u32) -> Self {
+        Self { bus, nack_every, transactions: 0, nacks: 0 }
+    }
+
+    /// Changes how often transactions fail, counting from the transactions already started.
+    ///
+    /// # Arguments
+    /// * `nack_every` - Fails every n-th transaction; 0 never fails.
+    pub fn set_nack_every(&mut self, nack_every: u32) {
+        self.nack_every = nack_every;
+    }
+
+    /// Returns the number of transactions failed with `Nack`.
+    pub fn nacks(&self) -> u32 {
+        self.nacks
+    }
+
+    /// Returns the wrapped bus.
+    pub fn inner(&self) -> &B {
+        &self.bus
+    }
+
+    /// Returns the wrapped bus mutably.
+    pub fn inner_mut(&mut self) -> &mut B {
+        &mut self.bus
+    }
+
+    /// Releases the wrapped bus.
+    pub fn into_inner(self) -> B {
+        self.bus
+    }
+
+    /// Starts a transaction, failing it if it is the n-th one.
+    fn transaction(&mut self) -> Result<(), Nack> {
+        self.transactions = self.transactions.wrapping_add(1);
+        if self.nack_every != 0 && self.transactions % self.nack_every == 0 {
+            self.nacks = self.nacks.wrapping_add(1);
+            return Err(Nack);
+        }
+        Ok(())
+    }
+}
+
+impl<B> Write for Faulty<B>
+where
+    B: Write<Error = Infallible>,
+{
+    type Error = Nack;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.transaction()?;
+        self.bus.write(address, bytes).unwrap_infallible();
+        Ok(())
+    }
+}
+
+impl<B> Read for Faulty<B>
+where
+    B: Read<Error = Infallible>,
+{
+    type Error = Nack;
+
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.transaction()?;
+        self.bus.read(address, buffer).unwrap_infallible();
+        Ok(())
+    }
+}
+
+impl<B> WriteRead for Faulty<B>
+where
+    B: WriteRead<Error = Infallible>,
+{
+    type Error = Nack;
+
+    fn write_read(&mut self, address: u8, bytes: &[u8], buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.transaction()?;
+        self.bus.write_read(address, bytes, buffer).unwrap_infallible();
+        Ok(())
+    }
+}
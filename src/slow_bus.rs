@@ -0,0 +1,93 @@
+//! Inter-transaction delays for buses run near the timing limits of the chip.
+
+use embedded_hal::blocking::delay::DelayUs;
+
+use crate::{Rx8900, Transport};
+
+/// The bus free time inserted after each transaction by default, in microseconds.
+///
+/// The datasheet requires 4.7 µs between a stop and the next start condition in standard mode (1.3 µs in fast
+/// mode); it is rounded up to a whole microsecond so coarse delay providers still meet it.
+pub const DEFAULT_BUS_FREE_TIME_US: u32 = 5;
+
+/// A transport waiting a bus free time after each transaction.
+///
+/// Bit-banged buses and bridges may issue the next start condition right after a stop, faster than the RX8900
+/// accepts. Wrapping them in a `SlowBus` keeps the timing compliant whatever the caller does between two accesses.
+pub struct SlowBus<T, D> {
+    bus: T,
+    delay: D,
+    bus_free_time_us: u32,
+}
+
+impl<T, D> SlowBus<T, D> {
+    /// Wraps a transport, waiting `DEFAULT_BUS_FREE_TIME_US` after each transaction.
+    ///
+    /// # Arguments
+    /// * `bus` - The transport the RX8900 is connected to.
+    /// * `delay` - The delay provider used between two transactions.
+    pub fn new(bus: T, delay: D) -> Self {
+        Self { bus, delay, bus_free_time_us: DEFAULT_BUS_FREE_TIME_US }
+    }
+
+    /// Sets the time waited after each transaction.
+    ///
+    /// # Arguments
+    /// * `us` - The bus free time in microseconds.
+    pub fn set_bus_free_time(&mut self, us: u32) {
+        self.bus_free_time_us = us;
+    }
+
+    /// Returns the time waited after each transaction.
+    ///
+    /// # Returns
+    /// * `u32` - The bus free time in microseconds.
+    pub fn bus_free_time(&self) -> u32 {
+        self.bus_free_time_us
+    }
+
+    /// Unwraps the transport and the delay provider.
+    pub fn release(self) -> (T, D) {
+        (self.bus, self.delay)
+    }
+}
+
+impl<T, D, E> Transport for SlowBus<T, D>
+where
+    T: Transport<Error = E>,
+    D: DelayUs<u32>,
+{
+    type Error = E;
+
+    fn read_registers(&mut self, register: u8, data: &mut [u8]) -> Result<(), E> {
+        let result = self.bus.read_registers(register, data);
+        self.delay.delay_us(self.bus_free_time_us);
+        result
+    }
+
+    fn write_registers(&mut self, register: u8, data: &[u8]) -> Result<(), E> {
+        let result = self.bus.write_registers(register, data);
+        self.delay.delay_us(self.bus_free_time_us);
+        result
+    }
+}
+
+impl<T, D> Rx8900<SlowBus<T, D>> {
+    /// Creates a driver waiting the datasheet bus free time after each transaction.
+    ///
+    /// Use it for designs running the bus near the chip's limits; the wait can be tuned with
+    /// `SlowBus::set_bus_free_time` through `bus_mut`. Like `new_unchecked`, it does not check the chip; on a cold
+    /// boot, pass a `SlowBus` to `uninitialized`.
+    ///
+    /// # Arguments
+    /// * `i2c` - The I2C bus or transport the RX8900 is connected to.
+    /// * `delay` - The delay provider used between two transactions.
+    pub fn new_slow_bus(i2c: T, delay: D) -> Self {
+        Self::new_unchecked(SlowBus::new(i2c, delay))
+    }
+
+    /// Returns the slow bus wrapper, to tune its bus free time.
+    pub fn bus_mut(&mut self) -> &mut SlowBus<T, D> {
+        &mut self.i2c
+    }
+}
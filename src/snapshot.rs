@@ -0,0 +1,71 @@
+//! A telemetry snapshot of the clock health, for gateways publishing it to a broker.
+
+use crate::{Config, Error, RegisterTable, Rx8900, Transport};
+
+/// The time, supply flags, temperature and configuration of the RTC, captured by `Rx8900::snapshot`.
+///
+/// With the `json` feature, `to_json` and `from_json` convert it to and from a JSON payload such as
+/// `{"timestamp":1700000000,"voltage_low":false,"voltage_detected":false,"temperature_millicelsius":24500,"config":{...}}`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Snapshot {
+    /// The date and time as seconds since the Unix epoch (UTC), as returned by `Rx8900::timestamp`.
+    pub timestamp: i64,
+    /// Voltage low flag (VLF): the clock lost its time and must be set again.
+    pub voltage_low: bool,
+    /// Voltage detection flag (VDET): the temperature compensation stopped because of a low supply voltage.
+    pub voltage_detected: bool,
+    /// The temperature in millidegrees Celsius, or None without the `temperature` feature.
+    pub temperature_millicelsius: Option<i32>,
+    /// The configuration.
+    pub config: Config,
+}
+
+impl Snapshot {
+    /// Serializes the snapshot to JSON.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> std::string::String {
+        // Serializing plain fields and unit enum variants cannot fail.
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Parses a snapshot serialized with `to_json`.
+    ///
+    /// # Arguments
+    /// * `json` - The JSON text.
+    ///
+    /// # Returns
+    /// * `Result<Snapshot, serde_json::Error>` - The snapshot, or an error if the text is not a valid snapshot.
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Captures the time, supply flags, temperature and configuration.
+    ///
+    /// # Returns
+    /// * `Result<Snapshot, Error<E>>` - The snapshot, `Error::InvalidTime` if the time registers hold no valid date and
+    ///   time, or an error if the read fails.
+    pub fn snapshot(&mut self) -> Result<Snapshot, Error<E>> {
+        let timestamp = self.timestamp()?;
+        let flags = self.read_register(RegisterTable::CompatibleFlagRegister)?;
+        #[cfg(feature = "temperature")]
+        let temperature_millicelsius = Some(self.temp_in_millicelsius()?);
+        #[cfg(not(feature = "temperature"))]
+        let temperature_millicelsius = None;
+        Ok(Snapshot {
+            timestamp,
+            // VLF
+            voltage_low: flags & 0b00000010 != 0,
+            // VDET
+            voltage_detected: flags & 0b00000001 != 0,
+            temperature_millicelsius,
+            config: self.config()?,
+        })
+    }
+}
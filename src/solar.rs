@@ -0,0 +1,189 @@
+//! Alarms relative to sunrise and sunset, computed by a user-supplied solar calculator.
+
+use chrono::{Duration, NaiveDate, NaiveDateTime, Timelike};
+use heapless::Vec;
+
+use crate::{Error, Rx8900, Transport};
+
+/// Computes the sunrise and sunset of a day at the location of the product.
+///
+/// Implement it with the solar position algorithm of your choice; the driver only asks for the days it needs to
+/// arm the next alarm.
+pub trait SolarCalculator {
+    /// Returns the sunrise and sunset of a day, in UTC.
+    ///
+    /// # Arguments
+    /// * `date` - The day, in UTC.
+    ///
+    /// # Returns
+    /// * `Option<SunTimes>` - The sunrise and sunset, or None if the sun does not rise or set that day (polar
+    ///   day or night); no rule fires then.
+    fn sun_times(&mut self, date: NaiveDate) -> Option<SunTimes>;
+}
+
+/// The sunrise and sunset of a day, in UTC.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SunTimes {
+    /// The time of sunrise.
+    pub sunrise: NaiveDateTime,
+    /// The time of sunset.
+    pub sunset: NaiveDateTime,
+}
+
+/// The solar event a rule is relative to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolarEvent {
+    /// Sunrise.
+    Sunrise,
+    /// Sunset.
+    Sunset,
+}
+
+/// A time relative to a solar event, e.g. 30 minutes after sunrise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SolarRule {
+    /// The event.
+    pub event: SolarEvent,
+    /// The offset from the event; negative for a time before it.
+    pub offset: Duration,
+}
+
+impl SolarRule {
+    /// Returns a rule firing `offset` after sunrise.
+    pub fn sunrise(offset: Duration) -> Self {
+        Self { event: SolarEvent::Sunrise, offset }
+    }
+
+    /// Returns a rule firing `offset` after sunset.
+    pub fn sunset(offset: Duration) -> Self {
+        Self { event: SolarEvent::Sunset, offset }
+    }
+
+    /// Returns the time the rule fires on the day of `sun`, truncated to the minute like the alarm.
+    fn time(&self, sun: &SunTimes) -> Option<NaiveDateTime> {
+        let event = match self.event {
+            SolarEvent::Sunrise => sun.sunrise,
+            SolarEvent::Sunset => sun.sunset,
+        };
+        event.checked_add_signed(self.offset)?.with_second(0)?.with_nanosecond(0)
+    }
+}
+
+/// Runs up to `N` solar rules on the alarm, re-arming it for the next rule after each wake-up.
+///
+/// The sun times are asked from the calculator again each time the alarm is armed, so they follow the seasons.
+/// When no rule fires before the end of the next day, e.g. during a polar night, the alarm is armed for the next
+/// midnight instead, so the schedule is recomputed at least daily:
+///
+/// ```ignore
+/// let mut schedule = SolarSchedule::<_, 2>::new(calculator);
+/// schedule.add(SolarRule::sunrise(Duration::minutes(30))).unwrap(); // lights off
+/// schedule.add(SolarRule::sunset(Duration::minutes(-15))).unwrap(); // lights on
+/// schedule.arm(&mut rx8900)?;
+/// loop {
+///     wait_for_interrupt();
+///     if let Some(rule) = schedule.poll(&mut rx8900)? {
+///         toggle_lights(rule);
+///     }
+/// }
+/// ```
+pub struct SolarSchedule<C, const N: usize> {
+    calculator: C,
+    rules: Vec<SolarRule, N>,
+    /// The time the alarm is armed for, and the rule due then, None for a recomputation at midnight.
+    next: Option<(Option<usize>, NaiveDateTime)>,
+}
+
+impl<C, const N: usize> SolarSchedule<C, N>
+where
+    C: SolarCalculator,
+{
+    /// Creates a schedule without any rule.
+    ///
+    /// # Arguments
+    /// * `calculator` - The solar calculator.
+    pub fn new(calculator: C) -> Self {
+        Self { calculator, rules: Vec::new(), next: None }
+    }
+
+    /// Adds a rule; call `arm` afterwards to take it into account.
+    ///
+    /// # Arguments
+    /// * `rule` - The rule.
+    ///
+    /// # Returns
+    /// * `Option<usize>` - The index of the rule, as returned by `poll`, or None if all `N` slots are in use.
+    pub fn add(&mut self, rule: SolarRule) -> Option<usize> {
+        self.rules.push(rule).ok()?;
+        Some(self.rules.len() - 1)
+    }
+
+    /// Returns the time the alarm is armed for and the index of the rule due then, None for a recomputation at
+    /// midnight; or None before `arm`.
+    pub fn next(&self) -> Option<(Option<usize>, NaiveDateTime)> {
+        self.next
+    }
+
+    /// Finds the first rule firing after `now` today or tomorrow, or the next midnight if there is none.
+    fn next_after(&mut self, now: NaiveDateTime) -> Option<(Option<usize>, NaiveDateTime)> {
+        let today = now.date();
+        let mut next: Option<(usize, NaiveDateTime)> = None;
+        for date in [today, today.succ_opt()?] {
+            let Some(sun) = self.calculator.sun_times(date) else { continue };
+            for (index, rule) in self.rules.iter().enumerate() {
+                if let Some(time) = rule.time(&sun).filter(|&time| time > now) {
+                    if next.map_or(true, |(_, next)| time < next) {
+                        next = Some((index, time));
+                    }
+                }
+            }
+        }
+        let midnight = today.succ_opt()?.and_hms_opt(0, 0, 0)?;
+        Some(match next {
+            Some((index, time)) if time <= midnight + Duration::days(1) => (Some(index), time),
+            _ => (None, midnight),
+        })
+    }
+
+    /// Asks the calculator for the sun times and arms the alarm for the next rule.
+    ///
+    /// # Arguments
+    /// * `rx8900` - The RTC waking the MCU up.
+    ///
+    /// # Returns
+    /// * `Result<Option<(Option<usize>, NaiveDateTime)>, Error<E>>` - The time the alarm is armed for and the index of
+    ///   the rule due then, None for a recomputation at midnight; None if the date cannot be represented,
+    ///   `Error::InvalidTime` if the time registers hold no valid date and time, or an error if the operation fails.
+    pub fn arm<I2C, E>(&mut self, rx8900: &mut Rx8900<I2C>) -> Result<Option<(Option<usize>, NaiveDateTime)>, Error<E>>
+    where
+        I2C: Transport<Error = E>,
+    {
+        let now = rx8900.datetime()?;
+        self.next = self.next_after(now);
+        if let Some((_, time)) = self.next {
+            rx8900.sleep_until(time)?;
+        }
+        Ok(self.next)
+    }
+
+    /// Checks the alarm flag and, if it is set, re-arms the alarm for the next rule.
+    ///
+    /// # Arguments
+    /// * `rx8900` - The RTC passed to `arm`.
+    ///
+    /// # Returns
+    /// * `Result<Option<usize>, Error<E>>` - The index of the rule that fired, None if the alarm has not fired or only
+    ///   woke the schedule up for its daily recomputation, `Error::InvalidTime` if the time registers hold no valid
+    ///   date and time, or an error if the operation fails.
+    pub fn poll<I2C, E>(&mut self, rx8900: &mut Rx8900<I2C>) -> Result<Option<usize>, Error<E>>
+    where
+        I2C: Transport<Error = E>,
+    {
+        if !rx8900.af()? {
+            return Ok(None);
+        }
+        let fired = self.next.and_then(|(rule, _)| rule);
+        self.arm(rx8900)?;
+        Ok(fired)
+    }
+}
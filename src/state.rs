@@ -0,0 +1,140 @@
+//! Export and import of the device state, to restore it after the backup battery was replaced.
+
+use crate::{Century, Error, RegisterTable, Rx8900, Transport, FLAG_BITS};
+
+/// Format version stored in the first byte of a `StateBlob`.
+const VERSION: u8 = 2;
+
+/// The year base of `Rx8900`, which maps the two-digit year to 2000-2099.
+const DEFAULT_YEAR_BASE: i32 = 2000;
+
+/// A snapshot of the configuration, alarm, timer and user RAM registers, and of the year base the two-digit year
+/// register is read with.
+///
+/// Created by `Rx8900::export_state`. Store `as_bytes` in MCU flash, and restore it with
+/// `StateBlob::from_bytes` and `Rx8900::import_state` when the VLF flag shows that the chip lost
+/// its contents. The date and time and the flags are not part of the state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StateBlob {
+    bytes: [u8; StateBlob::LEN],
+}
+
+impl StateBlob {
+    /// Size of the serialized state in bytes.
+    pub const LEN: usize = 12;
+
+    /// Returns the serialized state.
+    pub fn as_bytes(&self) -> &[u8; Self::LEN] {
+        &self.bytes
+    }
+
+    /// Returns the first year of the century the state was exported for: 2000 for `Rx8900`, `BASE` for
+    /// `Century<I2C, BASE>`.
+    pub fn year_base(&self) -> i32 {
+        i32::from(i16::from_le_bytes([self.bytes[10], self.bytes[11]]))
+    }
+
+    /// Parses a serialized state.
+    ///
+    /// # Arguments
+    /// * `bytes` - Bytes returned by `as_bytes`.
+    ///
+    /// # Returns
+    /// * `Option<StateBlob>` - The state, or None if the length or format version does not match.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes: [u8; Self::LEN] = bytes.try_into().ok()?;
+        (bytes[0] == VERSION).then_some(Self { bytes })
+    }
+}
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Reads the configuration, alarm, timer and user RAM registers into a `StateBlob`.
+    ///
+    /// # Returns
+    /// * `Result<StateBlob, E>` - The state, or an error if the read fails.
+    pub fn export_state(&mut self) -> Result<StateBlob, E> {
+        self.export_state_in(DEFAULT_YEAR_BASE)
+    }
+
+    /// Writes a state captured by `export_state` back to the chip.
+    ///
+    /// The interrupts and the timer are disabled while the alarm and timer registers are written. Flags are left
+    /// untouched, so `set_if_invalid` can still be used afterwards to program the time and clear VLF. Once the chip
+    /// was claimed, the ownership marker bits of the user RAM are kept, as `set_ram` does.
+    ///
+    /// # Arguments
+    /// * `state` - The state to restore.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the state was restored, `Error::InvalidInput` if it was exported for another
+    ///   year base than 2000, or an error if the operation fails.
+    pub fn import_state(&mut self, state: &StateBlob) -> Result<(), Error<E>> {
+        self.import_state_in(state, DEFAULT_YEAR_BASE)
+    }
+
+    /// Reads the state, recording `year_base` as the century the year register is read with.
+    fn export_state_in(&mut self, year_base: i32) -> Result<StateBlob, E> {
+        let mut bytes = [0; StateBlob::LEN];
+        bytes[0] = VERSION;
+        // RAM, alarm, timer counter, extension, flag and control registers.
+        let mut data = [0; 9];
+        self.read_registers(RegisterTable::CompatibleRAM, &mut data)?;
+        bytes[1..7].copy_from_slice(&data[..6]);
+        // TEST is never restored.
+        bytes[7] = data[6] & !0b10000000;
+        // RESET is never restored.
+        bytes[8] = data[8] & !0b00000001;
+        bytes[9] = self.read_register(RegisterTable::ExtendedBackupFunction)? & 0b00001111;
+        // Bases are multiples of 100 well within i16.
+        bytes[10..12].copy_from_slice(&(year_base as i16).to_le_bytes());
+        Ok(StateBlob { bytes })
+    }
+
+    /// Restores the state if it was exported for `year_base`.
+    fn import_state_in(&mut self, state: &StateBlob, year_base: i32) -> Result<(), Error<E>> {
+        if state.year_base() != year_base {
+            return Err(Error::InvalidInput);
+        }
+        let bytes = &state.bytes;
+        // TE=0 / AIE=0, TIE=0, UIE=0
+        self.update_control_registers((0b00010000, 0), 0, (0b00111000, 0))?;
+        let mut data = [0; 6];
+        data.copy_from_slice(&bytes[1..7]);
+        let bits = self.ram_bits();
+        if bits != 0xFF {
+            let ram = self.read_register(RegisterTable::CompatibleRAM)?;
+            data[0] = ram & !bits | data[0] & bits;
+        }
+        self.write_registers(RegisterTable::CompatibleRAM, &data)?;
+        self.write_registers(RegisterTable::CompatibleExtensionRegister, &[bytes[7], FLAG_BITS, bytes[8]])?;
+        self.update_register(RegisterTable::ExtendedBackupFunction, 0b00001111, bytes[9])
+    }
+}
+
+impl<I2C, E, const BASE: i32> Century<I2C, BASE>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Reads the state as `Rx8900::export_state` does, with `BASE` as its year base.
+    ///
+    /// # Returns
+    /// * `Result<StateBlob, E>` - The state, or an error if the read fails.
+    pub fn export_state(&mut self) -> Result<StateBlob, E> {
+        self.rx8900().export_state_in(BASE)
+    }
+
+    /// Writes a state captured by `export_state` back to the chip, as `Rx8900::import_state` does.
+    ///
+    /// # Arguments
+    /// * `state` - The state to restore.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the state was restored, `Error::InvalidInput` if it was exported for another
+    ///   year base than `BASE`, or an error if the operation fails.
+    pub fn import_state(&mut self, state: &StateBlob) -> Result<(), Error<E>> {
+        self.rx8900().import_state_in(state, BASE)
+    }
+}
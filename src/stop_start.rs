@@ -0,0 +1,65 @@
+//! Register reads as a write and a separate read, for I2C masters and bridges without repeated start support.
+
+use embedded_hal::blocking::i2c::{Read, Write};
+
+use crate::{Rx8900, Transport, RX8900_ADDR};
+
+/// A transport reading registers with a stop condition between setting the address and reading the data.
+///
+/// By default the driver reads with `WriteRead`, a write of the register address followed by a repeated start and
+/// the read. Some masters and bridges only support one of the two sequences, or misbehave on combined transfers;
+/// wrapping the bus in a `StopStart` selects a `Write` of the address, a stop, then a `Read`. The RX8900 keeps its
+/// address pointer across the stop, so both sequences return the same data. Writes are unchanged.
+///
+/// The time registers are only latched during a single read, which is not split by the stop, so reading the time
+/// remains consistent. Another master accessing the chip between the write and the read would move the address
+/// pointer though: only use it on a bus the driver is alone on.
+pub struct StopStart<I2C> {
+    i2c: I2C,
+}
+
+impl<I2C> StopStart<I2C> {
+    /// Wraps an I2C bus, reading with a write and a separate read.
+    ///
+    /// # Arguments
+    /// * `i2c` - The I2C bus the RX8900 is connected to; it only needs `Read` and `Write`.
+    pub fn new(i2c: I2C) -> Self {
+        Self { i2c }
+    }
+
+    /// Unwraps the I2C bus.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+}
+
+impl<I2C, E> Transport for StopStart<I2C>
+where
+    I2C: Read<Error = E> + Write<Error = E>,
+{
+    type Error = E;
+
+    fn read_registers(&mut self, register: u8, data: &mut [u8]) -> Result<(), E> {
+        self.i2c.write(RX8900_ADDR, &[register])?;
+        self.i2c.read(RX8900_ADDR, data)
+    }
+
+    fn write_registers(&mut self, register: u8, data: &[u8]) -> Result<(), E> {
+        let mut buffer = [0; 17];
+        buffer[0] = register;
+        buffer[1..=data.len()].copy_from_slice(data);
+        self.i2c.write(RX8900_ADDR, &buffer[..=data.len()])
+    }
+}
+
+impl<I2C> Rx8900<StopStart<I2C>> {
+    /// Creates a driver reading registers with a write and a separate read instead of a repeated start.
+    ///
+    /// Like `new_unchecked`, it does not check the chip; on a cold boot, pass a `StopStart` to `uninitialized`.
+    ///
+    /// # Arguments
+    /// * `i2c` - The I2C bus the RX8900 is connected to; it only needs `Read` and `Write`.
+    pub fn new_stop_start(i2c: I2C) -> Self {
+        Self::new_unchecked(StopStart::new(i2c))
+    }
+}
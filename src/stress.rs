@@ -0,0 +1,101 @@
+//! Bus stress testing, for qualifying pull-ups, trace lengths and bus speeds on new hardware.
+
+use embedded_hal::blocking::delay::DelayMs;
+
+use crate::{RegisterTable, Rx8900, Transport};
+
+/// The patterns written to the user RAM register, cycled through by `stress_test`.
+///
+/// Solid and alternating bits stress the edges of the bus, walking ones catch stuck or shorted data bits.
+const PATTERNS: [u8; 12] = [0x00, 0xFF, 0x55, 0xAA, 0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80];
+
+/// The outcome of a `stress_test` run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StressReport {
+    /// Number of iterations run.
+    pub iterations: u32,
+    /// Number of bus transactions attempted, saturating at `u32::MAX`.
+    pub transactions: u32,
+    /// Number of transactions reporting a bus error.
+    pub bus_errors: u32,
+    /// Number of reads returning a value other than the one written.
+    pub mismatches: u32,
+}
+
+impl StressReport {
+    /// Returns the number of failed transactions, whether the bus reported it or not.
+    ///
+    /// # Returns
+    /// * `u32` - The sum of bus errors and mismatches.
+    pub fn errors(&self) -> u32 {
+        self.bus_errors.saturating_add(self.mismatches)
+    }
+
+    /// Returns the error rate in failures per million transactions.
+    ///
+    /// # Returns
+    /// * `u32` - The error rate, rounded down, or 0 if no transaction was attempted.
+    pub fn error_rate_ppm(&self) -> u32 {
+        let rate = u64::from(self.errors()) * 1_000_000;
+        rate.checked_div(u64::from(self.transactions)).unwrap_or(0) as u32
+    }
+
+    /// Returns whether every transaction succeeded.
+    pub fn passed(&self) -> bool {
+        self.errors() == 0
+    }
+}
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Cycles writes of test patterns to the user RAM and register reads, counting the failures.
+    ///
+    /// Each iteration writes a pattern to the user RAM register, reads it back alone, then reads it back as part of
+    /// a burst of the 16 compatible registers, and waits 1 ms. Bus errors do not stop the run, they are counted in
+    /// the report along with the values read back wrong. The user RAM is restored at the end; the date and time are
+    /// not touched. Once the driver has a role from `claim` or `observe_only`, the ownership marker in the upper two
+    /// bits of the user RAM is kept and only the lower six bits are tested.
+    ///
+    /// # Arguments
+    /// * `iterations` - The number of write/read cycles to run.
+    /// * `delay` - The delay provider used between two iterations.
+    ///
+    /// # Returns
+    /// * `Result<StressReport, E>` - The report, or an error if the user RAM could not be saved before the run,
+    ///   which usually means nothing answers on the bus.
+    pub fn stress_test<D: DelayMs<u32>>(&mut self, iterations: u32, delay: &mut D) -> Result<StressReport, E> {
+        let saved = self.read_register(RegisterTable::CompatibleRAM)?;
+        let bits = self.ram_bits();
+        let mut report = StressReport { iterations, ..StressReport::default() };
+        let mut burst = [0; 16];
+        for iteration in 0..iterations {
+            let pattern = saved & !bits | PATTERNS[iteration as usize % PATTERNS.len()] & bits;
+            report.transactions = report.transactions.saturating_add(1);
+            if self.write_register(RegisterTable::CompatibleRAM, pattern).is_err() {
+                // The reads cannot be checked against a value that may not have been written.
+                report.bus_errors = report.bus_errors.saturating_add(1);
+                delay.delay_ms(1);
+                continue;
+            }
+            report.transactions = report.transactions.saturating_add(2);
+            match self.read_register(RegisterTable::CompatibleRAM) {
+                Ok(value) if value == pattern => {}
+                Ok(_) => report.mismatches = report.mismatches.saturating_add(1),
+                Err(_) => report.bus_errors = report.bus_errors.saturating_add(1),
+            }
+            match self.read_registers(RegisterTable::CompatibleSEC, &mut burst) {
+                Ok(()) if burst[RegisterTable::CompatibleRAM as usize] == pattern => {}
+                Ok(_) => report.mismatches = report.mismatches.saturating_add(1),
+                Err(_) => report.bus_errors = report.bus_errors.saturating_add(1),
+            }
+            delay.delay_ms(1);
+        }
+        report.transactions = report.transactions.saturating_add(1);
+        if self.write_register(RegisterTable::CompatibleRAM, saved).is_err() {
+            report.bus_errors = report.bus_errors.saturating_add(1);
+        }
+        Ok(report)
+    }
+}
@@ -0,0 +1,134 @@
+//! A host-side stand-in for a board with an RX8900, for integration tests of sleep and wake-up logic.
+//!
+//! `FakeBoard` wires one `Simulator` to the driver, to a fake delay and to a fake /INT pin, so code written
+//! against the `embedded-hal` traits runs unchanged in `cargo test`.
+
+use core::convert::Infallible;
+use std::cell::{Ref, RefCell, RefMut};
+use std::rc::Rc;
+
+use embedded_hal::blocking::delay::{DelayMs, DelayUs};
+use embedded_hal::digital::v2::InputPin;
+
+use crate::sim::Simulator;
+use crate::{RegisterTable, Rx8900, Transport};
+
+/// A handle on the simulator of a `FakeBoard`, used as the bus of its driver.
+#[derive(Clone, Debug)]
+pub struct SimBus {
+    simulator: Rc<RefCell<Simulator>>,
+}
+
+impl Transport for SimBus {
+    type Error = Infallible;
+
+    fn read_registers(&mut self, register: u8, data: &mut [u8]) -> Result<(), Infallible> {
+        self.simulator.borrow_mut().read_registers(register, data)
+    }
+
+    fn write_registers(&mut self, register: u8, data: &[u8]) -> Result<(), Infallible> {
+        self.simulator.borrow_mut().write_registers(register, data)
+    }
+}
+
+/// A delay that returns immediately and adds up the time it was asked to wait.
+#[derive(Clone, Debug, Default)]
+pub struct FakeDelay {
+    elapsed_us: Rc<RefCell<u64>>,
+}
+
+impl FakeDelay {
+    /// Returns the total time waited so far, in microseconds.
+    pub fn elapsed_us(&self) -> u64 {
+        *self.elapsed_us.borrow()
+    }
+}
+
+impl DelayMs<u32> for FakeDelay {
+    fn delay_ms(&mut self, ms: u32) {
+        *self.elapsed_us.borrow_mut() += u64::from(ms) * 1000;
+    }
+}
+
+impl DelayUs<u32> for FakeDelay {
+    fn delay_us(&mut self, us: u32) {
+        *self.elapsed_us.borrow_mut() += u64::from(us);
+    }
+}
+
+/// The /INT output of the simulated chip, low while an enabled interrupt flag is set.
+#[derive(Clone, Debug)]
+pub struct FakeIntPin {
+    simulator: Rc<RefCell<Simulator>>,
+}
+
+impl InputPin for FakeIntPin {
+    type Error = Infallible;
+
+    fn is_high(&self) -> Result<bool, Infallible> {
+        self.is_low().map(|low| !low)
+    }
+
+    fn is_low(&self) -> Result<bool, Infallible> {
+        let simulator = self.simulator.borrow();
+        let flags = simulator.register(RegisterTable::CompatibleFlagRegister as u8);
+        let control = simulator.register(RegisterTable::CompatibleControlRegister as u8);
+        // UF/UIE, TF/TIE and AF/AIE share the same bit positions.
+        Ok(flags & control & 0b00111000 != 0)
+    }
+}
+
+/// A simulated board: the driver, a delay and the /INT pin, all backed by one `Simulator`.
+///
+/// ```ignore
+/// let mut board = FakeBoard::new();
+/// board.rtc.set_datetime(start)?;
+/// board.simulator_mut().set_register(0x0E, 0b00001000); // AF
+/// assert!(board.int.is_low()?);
+/// ```
+pub struct FakeBoard {
+    /// The driver, talking to the simulator.
+    pub rtc: Rx8900<SimBus>,
+    /// A delay adding up the time waited.
+    pub delay: FakeDelay,
+    /// The /INT pin of the simulated chip.
+    pub int: FakeIntPin,
+    simulator: Rc<RefCell<Simulator>>,
+}
+
+impl Default for FakeBoard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FakeBoard {
+    /// Creates a board with a simulator in the state of a freshly powered chip.
+    pub fn new() -> Self {
+        Self::with_simulator(Simulator::new())
+    }
+
+    /// Creates a board around a prepared simulator, e.g. one created from a register image.
+    ///
+    /// # Arguments
+    /// * `simulator` - The simulated chip.
+    pub fn with_simulator(simulator: Simulator) -> Self {
+        let simulator = Rc::new(RefCell::new(simulator));
+        Self {
+            rtc: Rx8900::new_unchecked(SimBus { simulator: simulator.clone() }),
+            delay: FakeDelay::default(),
+            int: FakeIntPin { simulator: simulator.clone() },
+            simulator,
+        }
+    }
+
+    /// Returns the simulated chip, to inspect its registers.
+    pub fn simulator(&self) -> Ref<'_, Simulator> {
+        self.simulator.borrow()
+    }
+
+    /// Returns the simulated chip, to change its registers behind the driver's back.
+    pub fn simulator_mut(&self) -> RefMut<'_, Simulator> {
+        self.simulator.borrow_mut()
+    }
+}
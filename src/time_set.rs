@@ -0,0 +1,45 @@
+//! The correction policy applied by every API that moves the clock to a new time.
+
+/// Decides how a time correction is applied: stepped at once, slewed in bounded steps, or rejected.
+///
+/// Offsets up to `max_step` seconds are applied in full. Larger offsets are applied at most
+/// `slew_rate` seconds at a time, so a periodically called sync helper converges gradually instead
+/// of making the clock jump; with a `slew_rate` of 0 they are rejected with `Error::InvalidInput`.
+///
+/// The policy is used by `set_timestamp`, `set_timestamp_from`, `sync_from_ntp` and `adjust_seconds`. `set_datetime`
+/// always writes the given time unconditionally.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimeSetPolicy {
+    /// Largest correction, in seconds, applied at once.
+    pub max_step: u32,
+    /// Largest correction, in seconds, applied per call when the offset exceeds `max_step`.
+    pub slew_rate: u32,
+}
+
+impl TimeSetPolicy {
+    /// Always steps the clock to the requested time; this is the default.
+    pub const STEP: Self = Self { max_step: u32::MAX, slew_rate: 0 };
+
+    /// Returns the part of an offset to apply now.
+    ///
+    /// # Arguments
+    /// * `offset` - The difference between the requested and the current time, in seconds.
+    ///
+    /// # Returns
+    /// * `Option<i64>` - The correction to apply in seconds, or None if the policy rejects the offset.
+    pub fn correction(&self, offset: i64) -> Option<i64> {
+        if offset.unsigned_abs() <= self.max_step as u64 {
+            Some(offset)
+        } else if self.slew_rate == 0 {
+            None
+        } else {
+            Some(offset.clamp(-(self.slew_rate as i64), self.slew_rate as i64))
+        }
+    }
+}
+
+impl Default for TimeSetPolicy {
+    fn default() -> Self {
+        Self::STEP
+    }
+}
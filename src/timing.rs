@@ -0,0 +1,82 @@
+//! Estimates of the I2C bus time taken by RTC operations, for power budgets and bus load checks.
+
+use chrono::Duration;
+
+#[cfg(feature = "timer")]
+use crate::Error;
+use crate::{Rx8900, MAX_WRITE_LEN};
+
+/// The bus frequency assumed until `Rx8900::set_bus_frequency_hint` is called: standard mode, 100 kHz.
+pub const DEFAULT_BUS_FREQUENCY_HZ: u32 = 100_000;
+
+/// The share of the bus time, in percent, a periodic mode may take for handling its interrupts.
+#[cfg(feature = "timer")]
+const MAX_BUS_LOAD_PERCENT: i64 = 50;
+
+/// A bus operation whose duration `Rx8900::estimated_transaction_time` estimates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BusOperation {
+    /// Reading consecutive registers, as many as given.
+    Read(u8),
+    /// Writing consecutive registers, as many as given.
+    Write(u8),
+    /// Reading the date and time with `datetime`, a burst of the 7 time registers.
+    ReadTime,
+    /// Handling a timer, update or alarm interrupt: reading the flag register, then clearing the flag.
+    ServiceInterrupt,
+}
+
+/// Returns the number of bit times of a transaction: the start, the address and register bytes, the data bytes
+/// and the stop, each byte taking 9 bits with its acknowledge. A read adds a repeated start and the address again.
+fn transaction_bits(read: bool, len: usize) -> u64 {
+    let overhead = if read { 1 + 9 + 9 + 1 + 9 + 1 } else { 1 + 9 + 9 + 1 };
+    overhead + 9 * len as u64
+}
+
+impl<I2C> Rx8900<I2C> {
+    /// Estimates the bus time of an operation at the frequency set with `set_bus_frequency_hint`.
+    ///
+    /// The estimate counts the bits clocked on the bus, including the splits of `set_burst_limit`, and ignores
+    /// clock stretching, the bus free time between transactions and the overhead of the HAL.
+    ///
+    /// # Arguments
+    /// * `op` - The operation.
+    ///
+    /// # Returns
+    /// * `Duration` - The estimated bus time, rounded up to a whole microsecond.
+    pub fn estimated_transaction_time(&self, op: BusOperation) -> Duration {
+        let bits = match op {
+            BusOperation::Read(len) => self.burst_bits(true, usize::from(len)),
+            BusOperation::Write(len) => self.burst_bits(false, usize::from(len)),
+            BusOperation::ReadTime => self.burst_bits(true, 7),
+            BusOperation::ServiceInterrupt => transaction_bits(true, 1) + transaction_bits(false, 1),
+        };
+        let hz = u64::from(self.bus_frequency_hint());
+        Duration::microseconds(((bits * 1_000_000).div_ceil(hz)) as i64)
+    }
+
+    /// Returns the number of bit times of a burst, split into transactions as `read_registers` and
+    /// `write_registers` do.
+    fn burst_bits(&self, read: bool, len: usize) -> u64 {
+        let chunk_len = if read { self.chunk_len(len) } else { self.chunk_len(len).min(MAX_WRITE_LEN) };
+        let (full, rest) = (len / chunk_len, len % chunk_len);
+        full as u64 * transaction_bits(read, chunk_len) + if rest > 0 { transaction_bits(read, rest) } else { 0 }
+    }
+
+    /// Checks that handling an interrupt every `period` keeps the bus load acceptable at the hinted frequency.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if servicing the interrupts takes at most half of the bus time, or
+    ///   `Error::BusSaturated` if it would take more.
+    #[cfg(feature = "timer")]
+    pub(crate) fn check_bus_load<E>(&self, period: Duration) -> Result<(), Error<E>> {
+        let busy = self.estimated_transaction_time(BusOperation::ServiceInterrupt);
+        let busy = busy.num_microseconds().unwrap_or(i64::MAX);
+        // A period too long to count in microseconds leaves the bus idle for all practical purposes.
+        let budget = period.num_microseconds().and_then(|us| us.checked_mul(MAX_BUS_LOAD_PERCENT)).unwrap_or(i64::MAX);
+        if busy.saturating_mul(100) > budget {
+            return Err(Error::BusSaturated);
+        }
+        Ok(())
+    }
+}
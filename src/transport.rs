@@ -0,0 +1,104 @@
+//! The byte-level access to the registers, implemented for any blocking I2C bus of `embedded-hal` and, with the
+//! `async` feature, any async I2C bus of `embedded-hal-async`.
+
+use embedded_hal::blocking::i2c::{Read, Write, WriteRead};
+
+use crate::RX8900_ADDR;
+
+/// Reads and writes consecutive registers of the RX8900.
+///
+/// Every blocking I2C bus of `embedded-hal` is a transport, talking to the chip at its fixed address. Implement it
+/// for a bus the driver cannot use directly, such as an I2C-over-SPI bridge or a bit-banged bus without the
+/// `embedded-hal` traits, then pass it to `Rx8900::uninitialized` or `Rx8900::new_unchecked` like an I2C bus.
+pub trait Transport {
+    /// The error reported by the bus.
+    type Error;
+
+    /// Reads consecutive registers in one transaction.
+    ///
+    /// # Arguments
+    /// * `register` - The address of the first register (0x00-0x1F).
+    /// * `data` - The buffer receiving the register values.
+    ///
+    /// # Returns
+    /// * `Result<(), Self::Error>` - Ok if the read was successful, or an error if the read fails.
+    fn read_registers(&mut self, register: u8, data: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Writes consecutive registers in one transaction.
+    ///
+    /// # Arguments
+    /// * `register` - The address of the first register (0x00-0x1F).
+    /// * `data` - The register values, at most 16 bytes.
+    ///
+    /// # Returns
+    /// * `Result<(), Self::Error>` - Ok if the write was successful, or an error if the write fails.
+    fn write_registers(&mut self, register: u8, data: &[u8]) -> Result<(), Self::Error>;
+}
+
+impl<I2C, E> Transport for I2C
+where
+    I2C: Read<Error = E> + WriteRead<Error = E> + Write<Error = E>,
+{
+    type Error = E;
+
+    fn read_registers(&mut self, register: u8, data: &mut [u8]) -> Result<(), E> {
+        self.write_read(RX8900_ADDR, &[register], data)
+    }
+
+    fn write_registers(&mut self, register: u8, data: &[u8]) -> Result<(), E> {
+        let mut buffer = [0; 17];
+        buffer[0] = register;
+        buffer[1..=data.len()].copy_from_slice(data);
+        self.write(RX8900_ADDR, &buffer[..=data.len()])
+    }
+}
+
+/// Reads and writes consecutive registers of the RX8900 without blocking the executor.
+///
+/// The async counterpart of `Transport`, used by `Rx8900Async`. Every async I2C bus of `embedded-hal-async` is an
+/// async transport, talking to the chip at its fixed address.
+#[cfg(feature = "async")]
+#[allow(async_fn_in_trait)]
+pub trait AsyncTransport {
+    /// The error reported by the bus.
+    type Error;
+
+    /// Reads consecutive registers in one transaction.
+    ///
+    /// # Arguments
+    /// * `register` - The address of the first register (0x00-0x1F).
+    /// * `data` - The buffer receiving the register values.
+    ///
+    /// # Returns
+    /// * `Result<(), Self::Error>` - Ok if the read was successful, or an error if the read fails.
+    async fn read_registers(&mut self, register: u8, data: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Writes consecutive registers in one transaction.
+    ///
+    /// # Arguments
+    /// * `register` - The address of the first register (0x00-0x1F).
+    /// * `data` - The register values, at most 16 bytes.
+    ///
+    /// # Returns
+    /// * `Result<(), Self::Error>` - Ok if the write was successful, or an error if the write fails.
+    async fn write_registers(&mut self, register: u8, data: &[u8]) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "async")]
+impl<I2C> AsyncTransport for I2C
+where
+    I2C: embedded_hal_async::i2c::I2c,
+{
+    type Error = I2C::Error;
+
+    async fn read_registers(&mut self, register: u8, data: &mut [u8]) -> Result<(), I2C::Error> {
+        self.write_read(RX8900_ADDR, &[register], data).await
+    }
+
+    async fn write_registers(&mut self, register: u8, data: &[u8]) -> Result<(), I2C::Error> {
+        let mut buffer = [0; 17];
+        buffer[0] = register;
+        buffer[1..=data.len()].copy_from_slice(data);
+        self.write(RX8900_ADDR, &buffer[..=data.len()]).await
+    }
+}
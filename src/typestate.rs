@@ -0,0 +1,169 @@
+//! Initialization states of the driver, making it a compile error to trust the chip before `init`.
+
+use core::marker::PhantomData;
+
+use crate::{Error, InitConfig, IntoNaiveUtc, Rx8900, Transport};
+
+/// The state of a driver whose chip has not been initialized: only `init`, `init_with` and `release` are
+/// available.
+#[derive(Clone, Copy, Debug)]
+pub struct Uninitialized;
+
+/// The state of a driver whose chip was initialized, with all the getters and setters; the default.
+#[derive(Clone, Copy, Debug)]
+pub struct Ready;
+
+/// The state of a driver whose chip was initialized after losing its time: only `set_datetime` and `release` are
+/// available.
+#[derive(Clone, Copy, Debug)]
+pub struct TimeInvalid;
+
+/// A driver whose lost time could not be set, handed back with the error so the time can be set again.
+pub type TimeNotSet<I2C, E> = (Rx8900<I2C, TimeInvalid>, Error<E>);
+
+/// The driver returned by `init`, `Ready` only if the time survived.
+pub enum Initialized<I2C> {
+    /// VLF was clear: the time registers hold the time kept through the power loss.
+    Ready(Rx8900<I2C>),
+    /// VLF was set: the time was lost and has to be set before it can be read.
+    TimeInvalid(Rx8900<I2C, TimeInvalid>),
+}
+
+impl<I2C, E> Initialized<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Returns the ready driver, setting the date and time first if it was lost.
+    ///
+    /// # Arguments
+    /// * `data` - The date and time to set if VLF was set, e.g. the firmware build time.
+    ///
+    /// # Returns
+    /// * `Result<Rx8900<I2C>, TimeNotSet<I2C, E>>` - The ready driver; or the driver and the error if setting the
+    ///   time fails, as for `Rx8900<I2C, TimeInvalid>::set_datetime`.
+    // The driver is returned in the error, so the time can be set again without a new `init` clearing VLF.
+    #[allow(clippy::result_large_err)]
+    pub fn or_set_datetime(self, data: impl IntoNaiveUtc) -> Result<Rx8900<I2C>, TimeNotSet<I2C, E>> {
+        match self {
+            Initialized::Ready(rx8900) => Ok(rx8900),
+            Initialized::TimeInvalid(rx8900) => rx8900.set_datetime(data),
+        }
+    }
+}
+
+impl<I2C> Rx8900<I2C, Uninitialized> {
+    /// Creates a driver that has to be initialized with `init` before it can read the time.
+    ///
+    /// # Arguments
+    /// * `i2c` - An instance of the I2C peripheral to communicate with the RX8900.
+    pub fn uninitialized(i2c: I2C) -> Self {
+        Rx8900::new_unchecked(i2c).into_state()
+    }
+
+    /// Destroys the driver and returns the I2C peripheral.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+}
+
+impl<I2C, E> Rx8900<I2C, Uninitialized>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Checks the voltage low flag, then initializes the chip as `Rx8900::init` does.
+    ///
+    /// `init` clears VLF, so its value is kept in the state of the returned driver: it is only `Ready` if the flag
+    /// was clear, and has to be given the time with `set_datetime` otherwise.
+    ///
+    /// # Returns
+    /// * `Result<Initialized<I2C>, (I2C, Error<E>)>` - The initialized driver, `Ready` if the time survived; or the
+    ///   bus and the error if an operation fails, so the bus can be handed to a fallback.
+    pub fn init(self) -> Result<Initialized<I2C>, (I2C, Error<E>)> {
+        self.initialize(&InitConfig::default())
+    }
+
+    /// Checks the voltage low flag, then initializes the chip as `Rx8900::init_with` does.
+    ///
+    /// # Arguments
+    /// * `config` - The settings to program.
+    ///
+    /// # Returns
+    /// * `Result<Initialized<I2C>, (I2C, Error<E>)>` - As for `init`, with `Error::InvalidInput` if the backup
+    ///   sampling time of `config` is out of range.
+    pub fn init_with(self, config: &InitConfig) -> Result<Initialized<I2C>, (I2C, Error<E>)> {
+        if !config.is_valid() {
+            return Err((self.i2c, Error::InvalidInput));
+        }
+        self.initialize(config)
+    }
+
+    /// Checks the voltage low flag, then programs a validated configuration.
+    fn initialize(self, config: &InitConfig) -> Result<Initialized<I2C>, (I2C, Error<E>)> {
+        let mut rx8900: Rx8900<I2C> = self.into_state();
+        let result = rx8900.voltage_low_flag().map_err(Error::Bus).and_then(|voltage_low| {
+            rx8900.write_init_config(config)?;
+            Ok(voltage_low)
+        });
+        match result {
+            Ok(false) => Ok(Initialized::Ready(rx8900)),
+            Ok(true) => Ok(Initialized::TimeInvalid(rx8900.into_state())),
+            Err(error) => Err((rx8900.i2c, error)),
+        }
+    }
+}
+
+impl<I2C> Rx8900<I2C, TimeInvalid> {
+    /// Destroys the driver and returns the I2C peripheral.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+}
+
+impl<I2C, E> Rx8900<I2C, TimeInvalid>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Sets the lost date and time, as `Rx8900::set_datetime` does, making the driver `Ready`.
+    ///
+    /// # Arguments
+    /// * `data` - The `NaiveDateTime` or `DateTime<Tz>` containing the date and time to be set.
+    ///
+    /// # Returns
+    /// * `Result<Rx8900<I2C>, TimeNotSet<I2C, E>>` - The ready driver; or this driver and `Error::InvalidInput` if
+    ///   the year is outside 2000-2099 or the bus error if the write fails, so the time can be set again.
+    // The driver is returned in the error, so the time can be set again without a new `init` clearing VLF.
+    #[allow(clippy::result_large_err)]
+    pub fn set_datetime(self, data: impl IntoNaiveUtc) -> Result<Rx8900<I2C>, TimeNotSet<I2C, E>> {
+        let mut rx8900: Rx8900<I2C> = self.into_state();
+        match rx8900.set_datetime(data) {
+            Ok(()) => Ok(rx8900),
+            Err(error) => Err((rx8900.into_state(), error)),
+        }
+    }
+}
+
+impl<I2C, S> Rx8900<I2C, S> {
+    /// Moves the driver and its settings to another state.
+    fn into_state<T>(self) -> Rx8900<I2C, T> {
+        Rx8900 {
+            i2c: self.i2c,
+            shadow: self.shadow,
+            time_set_policy: self.time_set_policy,
+            leap_seconds: self.leap_seconds,
+            monotonic: self.monotonic,
+            history: self.history,
+            quality: self.quality,
+            vdet_config: self.vdet_config,
+            decode_mode: self.decode_mode,
+            week_start: self.week_start,
+            role: self.role,
+            burst_limit: self.burst_limit,
+            bus_frequency_hz: self.bus_frequency_hz,
+            #[cfg(feature = "alarm")]
+            min_alarm_interval: self.min_alarm_interval,
+            #[cfg(feature = "alarm")]
+            last_alarm: self.last_alarm,
+            state: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! The weekday encoding of the WEEK and WEEK Alarm registers, where each day has its own bit from Sunday (bit 0)
+//! to Saturday (bit 6), and the order of the days in a week.
+
+use chrono::{Datelike, NaiveDate, Weekday};
+
+/// Converts a `Weekday` into its bit in the WEEK register.
+///
+/// # Arguments
+/// * `day` - The day of the week.
+///
+/// # Returns
+/// * `u8` - The bit of the day, from `0b00000001` for Sunday to `0b01000000` for Saturday.
+pub const fn weekday_to_bit(day: Weekday) -> u8 {
+    match day {
+        Weekday::Sun => 0b00000001,
+        Weekday::Mon => 0b00000010,
+        Weekday::Tue => 0b00000100,
+        Weekday::Wed => 0b00001000,
+        Weekday::Thu => 0b00010000,
+        Weekday::Fri => 0b00100000,
+        Weekday::Sat => 0b01000000,
+    }
+}
+
+/// Converts a bit of the WEEK register into a `Weekday`.
+///
+/// # Arguments
+/// * `bit` - The register value, with exactly one of bits 0-6 set.
+///
+/// # Returns
+/// * `Option<Weekday>` - The day of the week, or None if the value is not a single weekday bit.
+pub const fn bit_to_weekday(bit: u8) -> Option<Weekday> {
+    match bit {
+        0b00000001 => Some(Weekday::Sun),
+        0b00000010 => Some(Weekday::Mon),
+        0b00000100 => Some(Weekday::Tue),
+        0b00001000 => Some(Weekday::Wed),
+        0b00010000 => Some(Weekday::Thu),
+        0b00100000 => Some(Weekday::Fri),
+        0b01000000 => Some(Weekday::Sat),
+        _ => None,
+    }
+}
+
+/// Returns the day of the week of a date, as the driver writes it along with the date.
+///
+/// # Arguments
+/// * `year` - The year, e.g. 2024.
+/// * `month` - The month (1-12).
+/// * `day` - The day of the month (1-31).
+///
+/// # Returns
+/// * `Option<Weekday>` - The day of the week, or None if the date does not exist.
+pub fn weekday_from_date(year: i32, month: u32, day: u32) -> Option<Weekday> {
+    NaiveDate::from_ymd_opt(year, month, day).map(|date| date.weekday())
+}
+
+/// The first day of the week, which sets the order weekdays are listed in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WeekStart {
+    /// Weeks run from Sunday to Saturday, the order of the register bits.
+    #[default]
+    Sunday,
+    /// Weeks run from Monday to Sunday, as in ISO 8601.
+    Monday,
+}
+
+impl WeekStart {
+    /// Returns the days of the week in order, starting with the first one.
+    pub const fn days(self) -> [Weekday; 7] {
+        use Weekday::*;
+        match self {
+            WeekStart::Sunday => [Sun, Mon, Tue, Wed, Thu, Fri, Sat],
+            WeekStart::Monday => [Mon, Tue, Wed, Thu, Fri, Sat, Sun],
+        }
+    }
+
+    /// Returns the position of a day in the week.
+    ///
+    /// # Arguments
+    /// * `day` - The day of the week.
+    ///
+    /// # Returns
+    /// * `u32` - The position, 0 for the first day of the week to 6 for the last one.
+    pub fn position(self, day: Weekday) -> u32 {
+        match self {
+            WeekStart::Sunday => day.num_days_from_sunday(),
+            WeekStart::Monday => day.num_days_from_monday(),
+        }
+    }
+
+    /// Lists the days set in a WEEK register value, in the order of the week.
+    ///
+    /// # Arguments
+    /// * `bits` - The register value, one bit per day from Sunday (bit 0) to Saturday (bit 6).
+    ///
+    /// # Returns
+    /// * `impl Iterator<Item = Weekday>` - The days whose bit is set.
+    pub fn weekdays(self, bits: u8) -> impl Iterator<Item = Weekday> {
+        self.days().into_iter().filter(move |&day| bits & weekday_to_bit(day) != 0)
+    }
+
+    /// Returns the first day of the week containing a date.
+    ///
+    /// # Arguments
+    /// * `date` - A date in the week.
+    ///
+    /// # Returns
+    /// * `Option<NaiveDate>` - The first day of the week, or None if it is out of the range of `NaiveDate`.
+    pub fn week_of(self, date: NaiveDate) -> Option<NaiveDate> {
+        date.checked_sub_days(chrono::Days::new(u64::from(self.position(date.weekday()))))
+    }
+
+    /// Returns the next day after `day` that is set in a WEEK register value, within the same week.
+    ///
+    /// # Arguments
+    /// * `bits` - The register value, one bit per day from Sunday (bit 0) to Saturday (bit 6).
+    /// * `day` - The day to search after.
+    ///
+    /// # Returns
+    /// * `Option<Weekday>` - The next day set, or None if no later day of the week is set.
+    pub fn next_in_week(self, bits: u8, day: Weekday) -> Option<Weekday> {
+        let position = self.position(day);
+        self.weekdays(bits).find(|&next| self.position(next) > position)
+    }
+}
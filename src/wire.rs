@@ -0,0 +1,134 @@
+//! A compact binary message distributing the time from the MCU owning the RTC to other MCUs.
+
+use chrono::{DateTime, NaiveDateTime};
+
+use crate::{AdjustmentSource, Error, Rx8900, Transport};
+
+/// Format version stored in the first byte of an encoded `TimeMessage`.
+const VERSION: u8 = 1;
+
+/// Seconds between 1970-01-01 and 2000-01-01.
+const EPOCH_2000: i64 = 946_684_800;
+
+/// The time of the sending MCU, with its validity and quality.
+///
+/// The encoded message is `TimeMessage::LEN` bytes long, so it fits in a single classic CAN frame:
+///
+/// | Byte | Contents |
+/// |------|----------|
+/// | 0    | Format version |
+/// | 1    | Bit 7: valid, bits 1-0: source (Manual, Ntp, Gps, Other) |
+/// | 2-5  | Seconds since 2000-01-01 00:00:00 UTC, little endian |
+/// | 6    | Uncertainty as a number of bits of milliseconds |
+/// | 7    | CRC-8 (polynomial 0x07) of bytes 0-6 |
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimeMessage {
+    /// The date and time of the sender (UTC).
+    pub datetime: NaiveDateTime,
+    /// Whether the sender's clock holds a valid time, i.e. VLF was clear.
+    pub valid: bool,
+    /// Where the sender's time came from.
+    pub source: AdjustmentSource,
+    /// The estimated error of the sender's time in milliseconds; encoding rounds it up to `2^n - 1`.
+    pub uncertainty_ms: u32,
+}
+
+impl TimeMessage {
+    /// Size of an encoded message in bytes.
+    pub const LEN: usize = 8;
+
+    /// Encodes the message.
+    ///
+    /// # Returns
+    /// * `Option<[u8; TimeMessage::LEN]>` - The encoded message, or None if the date and time cannot be
+    ///   represented (before 2000 or after 2136).
+    pub fn encode(&self) -> Option<[u8; Self::LEN]> {
+        let seconds = self.datetime.and_utc().timestamp() - EPOCH_2000;
+        let seconds = u32::try_from(seconds).ok()?;
+        let source = match self.source {
+            AdjustmentSource::Manual => 0,
+            AdjustmentSource::Ntp => 1,
+            AdjustmentSource::Gps => 2,
+            AdjustmentSource::Other => 3,
+        };
+        let mut bytes = [0; Self::LEN];
+        bytes[0] = VERSION;
+        bytes[1] = (self.valid as u8) << 7 | source;
+        bytes[2..6].copy_from_slice(&seconds.to_le_bytes());
+        bytes[6] = (u32::BITS - self.uncertainty_ms.leading_zeros()) as u8;
+        bytes[7] = crc8(&bytes[..7]);
+        Some(bytes)
+    }
+
+    /// Decodes a message.
+    ///
+    /// # Arguments
+    /// * `bytes` - Bytes returned by `encode`.
+    ///
+    /// # Returns
+    /// * `Option<TimeMessage>` - The message, or None if the length, format version or checksum does not match.
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        let bytes: [u8; Self::LEN] = bytes.try_into().ok()?;
+        if bytes[0] != VERSION || bytes[7] != crc8(&bytes[..7]) || bytes[1] & 0b01111100 != 0 || bytes[6] > 32 {
+            return None;
+        }
+        let seconds = u32::from_le_bytes([bytes[2], bytes[3], bytes[4], bytes[5]]);
+        let datetime = DateTime::from_timestamp(EPOCH_2000 + seconds as i64, 0)?.naive_utc();
+        let source = match bytes[1] & 0b11 {
+            0 => AdjustmentSource::Manual,
+            1 => AdjustmentSource::Ntp,
+            2 => AdjustmentSource::Gps,
+            _ => AdjustmentSource::Other,
+        };
+        let uncertainty_ms = u32::MAX.checked_shr(32 - bytes[6] as u32).unwrap_or(0);
+        Some(Self { datetime, valid: bytes[1] & 0b10000000 != 0, source, uncertainty_ms })
+    }
+}
+
+/// Computes the CRC-8 of `bytes` with the polynomial 0x07 and a zero initial value.
+fn crc8(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0, |crc, &byte| {
+        (0..8).fold(crc ^ byte, |crc, _| if crc & 0x80 != 0 { crc << 1 ^ 0x07 } else { crc << 1 })
+    })
+}
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Reads the date and time and VLF into a `TimeMessage` to distribute to other MCUs.
+    ///
+    /// # Arguments
+    /// * `source` - Where the time of this clock came from.
+    /// * `uncertainty_ms` - The estimated error of this clock in milliseconds.
+    ///
+    /// # Returns
+    /// * `Result<TimeMessage, Error<E>>` - The message, `Error::InvalidTime` if the time registers hold no valid date
+    ///   and time, or an error if the read fails.
+    pub fn time_message(&mut self, source: AdjustmentSource, uncertainty_ms: u32) -> Result<TimeMessage, Error<E>> {
+        let valid = !self.vlf()?;
+        let datetime = self.datetime()?;
+        Ok(TimeMessage { datetime, valid, source, uncertainty_ms })
+    }
+
+    /// Moves the clock to the time of a received `TimeMessage`, applying the time set policy, and clears VLF.
+    ///
+    /// The adjustment is recorded in the adjustment history with the source of the message. The transmission
+    /// delay is not compensated.
+    ///
+    /// # Arguments
+    /// * `message` - The received message.
+    ///
+    /// # Returns
+    /// * `Result<i64, Error<E>>` - The correction applied in seconds, `Error::InvalidInput` if the message is not
+    ///   valid, out of range or rejected by the policy, or an error if the operation fails.
+    pub fn apply_time_message(&mut self, message: &TimeMessage) -> Result<i64, Error<E>> {
+        if !message.valid {
+            return Err(Error::InvalidInput);
+        }
+        let correction = self.set_posix_timestamp(message.datetime.and_utc().timestamp(), message.source)?;
+        // VLF
+        self.clear_flags(0b00000010)?;
+        Ok(correction)
+    }
+}
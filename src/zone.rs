@@ -0,0 +1,201 @@
+//! Named fixed UTC offsets, a human-readable time zone selection for products without `chrono-tz`.
+
+use chrono::{DateTime, FixedOffset};
+
+use crate::master::OWNER_MASK;
+use crate::{Error, RegisterTable, Rx8900, Transport};
+
+/// A named fixed offset from UTC.
+///
+/// Offsets are those of standard time; daylight saving time is not applied. The discriminant is the code stored
+/// by `Rx8900::store_zone`, so the order of the variants is stable. Codes start at 1, so a user RAM that was
+/// erased or never written holds no zone.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NamedOffset {
+    /// Coordinated Universal Time, UTC+00:00.
+    #[default]
+    Utc = 1,
+    /// Central European Time, UTC+01:00.
+    Cet = 2,
+    /// Eastern European Time, UTC+02:00.
+    Eet = 3,
+    /// Moscow Standard Time, UTC+03:00.
+    Msk = 4,
+    /// India Standard Time, UTC+05:30.
+    Ist = 5,
+    /// Indochina Time, UTC+07:00.
+    Ict = 6,
+    /// China Standard Time, UTC+08:00.
+    ChinaSt = 7,
+    /// Japan Standard Time, UTC+09:00.
+    Jst = 8,
+    /// Australian Eastern Standard Time, UTC+10:00.
+    Aest = 9,
+    /// New Zealand Standard Time, UTC+12:00.
+    Nzst = 10,
+    /// Hawaii-Aleutian Standard Time, UTC-10:00.
+    Hst = 11,
+    /// Alaska Standard Time, UTC-09:00.
+    Akst = 12,
+    /// Pacific Standard Time, UTC-08:00.
+    Pst = 13,
+    /// Mountain Standard Time, UTC-07:00.
+    Mst = 14,
+    /// Central Standard Time, UTC-06:00.
+    Cst = 15,
+    /// Eastern Standard Time, UTC-05:00.
+    Est = 16,
+    /// Atlantic Standard Time, UTC-04:00.
+    Ast = 17,
+    /// Brasília Time, UTC-03:00.
+    Brt = 18,
+}
+
+impl NamedOffset {
+    /// Every named offset, in the order of their codes.
+    pub const ALL: [NamedOffset; 18] = [
+        NamedOffset::Utc,
+        NamedOffset::Cet,
+        NamedOffset::Eet,
+        NamedOffset::Msk,
+        NamedOffset::Ist,
+        NamedOffset::Ict,
+        NamedOffset::ChinaSt,
+        NamedOffset::Jst,
+        NamedOffset::Aest,
+        NamedOffset::Nzst,
+        NamedOffset::Hst,
+        NamedOffset::Akst,
+        NamedOffset::Pst,
+        NamedOffset::Mst,
+        NamedOffset::Cst,
+        NamedOffset::Est,
+        NamedOffset::Ast,
+        NamedOffset::Brt,
+    ];
+
+    /// Returns the abbreviation of the zone, e.g. "JST".
+    pub const fn name(self) -> &'static str {
+        match self {
+            NamedOffset::Utc => "UTC",
+            NamedOffset::Cet => "CET",
+            NamedOffset::Eet => "EET",
+            NamedOffset::Msk => "MSK",
+            NamedOffset::Ist => "IST",
+            NamedOffset::Ict => "ICT",
+            NamedOffset::ChinaSt => "CST+8",
+            NamedOffset::Jst => "JST",
+            NamedOffset::Aest => "AEST",
+            NamedOffset::Nzst => "NZST",
+            NamedOffset::Hst => "HST",
+            NamedOffset::Akst => "AKST",
+            NamedOffset::Pst => "PST",
+            NamedOffset::Mst => "MST",
+            NamedOffset::Cst => "CST",
+            NamedOffset::Est => "EST",
+            NamedOffset::Ast => "AST",
+            NamedOffset::Brt => "BRT",
+        }
+    }
+
+    /// Returns the offset east of UTC in seconds.
+    pub const fn seconds(self) -> i32 {
+        const HOUR: i32 = 3600;
+        match self {
+            NamedOffset::Utc => 0,
+            NamedOffset::Cet => HOUR,
+            NamedOffset::Eet => 2 * HOUR,
+            NamedOffset::Msk => 3 * HOUR,
+            NamedOffset::Ist => 5 * HOUR + 1800,
+            NamedOffset::Ict => 7 * HOUR,
+            NamedOffset::ChinaSt => 8 * HOUR,
+            NamedOffset::Jst => 9 * HOUR,
+            NamedOffset::Aest => 10 * HOUR,
+            NamedOffset::Nzst => 12 * HOUR,
+            NamedOffset::Hst => -10 * HOUR,
+            NamedOffset::Akst => -9 * HOUR,
+            NamedOffset::Pst => -8 * HOUR,
+            NamedOffset::Mst => -7 * HOUR,
+            NamedOffset::Cst => -6 * HOUR,
+            NamedOffset::Est => -5 * HOUR,
+            NamedOffset::Ast => -4 * HOUR,
+            NamedOffset::Brt => -3 * HOUR,
+        }
+    }
+
+    /// Returns the offset as a `chrono` time zone.
+    pub fn offset(self) -> FixedOffset {
+        // Every offset is within a day, so it is always valid.
+        FixedOffset::east_opt(self.seconds()).unwrap()
+    }
+
+    /// Returns the code identifying the zone, as stored by `Rx8900::store_zone`.
+    pub const fn code(self) -> u8 {
+        self as u8
+    }
+
+    /// Returns the zone identified by a code.
+    ///
+    /// # Arguments
+    /// * `code` - A code returned by `code`.
+    ///
+    /// # Returns
+    /// * `Option<NamedOffset>` - The zone, or None if the code is unknown or 0, which marks no zone.
+    pub fn from_code(code: u8) -> Option<Self> {
+        Self::ALL.get(usize::from(code.checked_sub(1)?)).copied()
+    }
+
+    /// Returns the zone with an abbreviation, ignoring case.
+    ///
+    /// # Arguments
+    /// * `name` - An abbreviation returned by `name`.
+    ///
+    /// # Returns
+    /// * `Option<NamedOffset>` - The zone, or None if the name is unknown.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|zone| zone.name().eq_ignore_ascii_case(name))
+    }
+}
+
+impl<I2C, E> Rx8900<I2C>
+where
+    I2C: Transport<Error = E>,
+{
+    /// Reads the current date and time in a named zone.
+    ///
+    /// # Arguments
+    /// * `zone` - The zone to convert the UTC time kept by the chip to.
+    ///
+    /// # Returns
+    /// * `Result<DateTime<FixedOffset>, Error<E>>` - The local date and time, or an error if the read fails or the
+    ///   registers do not hold a valid date and time.
+    pub fn local_datetime(&mut self, zone: NamedOffset) -> Result<DateTime<FixedOffset>, Error<E>> {
+        let utc = self.datetime()?.and_utc();
+        Ok(utc.with_timezone(&zone.offset()))
+    }
+
+    /// Persists the selected zone in the user RAM register, so it survives a reset of the MCU.
+    ///
+    /// The code takes the low 6 bits of the user RAM, the upper ones holding the ownership marker of `claim`, so do
+    /// not use it if the RAM holds other data.
+    ///
+    /// # Arguments
+    /// * `zone` - The zone to store.
+    ///
+    /// # Returns
+    /// * `Result<(), Error<E>>` - Ok if the zone was stored, or an error if the write fails.
+    pub fn store_zone(&mut self, zone: NamedOffset) -> Result<(), Error<E>> {
+        let ram = self.read_register(RegisterTable::CompatibleRAM)?;
+        self.write_register(RegisterTable::CompatibleRAM, ram & OWNER_MASK | zone.code())
+    }
+
+    /// Reads the zone persisted by `store_zone`.
+    ///
+    /// # Returns
+    /// * `Result<Option<NamedOffset>, E>` - The zone, None if the user RAM does not hold a zone code, e.g. if it
+    ///   was erased or zeroed, or an error if the read fails.
+    pub fn stored_zone(&mut self) -> Result<Option<NamedOffset>, E> {
+        Ok(NamedOffset::from_code(self.read_register(RegisterTable::CompatibleRAM)? & !OWNER_MASK))
+    }
+}
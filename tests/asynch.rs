@@ -0,0 +1,114 @@
+//! `Rx8900Async` against the simulator: its accessors issue the same transactions as the blocking ones they are
+//! generated with.
+//!
+//! ```console
+//! $ cargo test --target x86_64-unknown-linux-gnu --features testsupport,async --test asynch
+//! ```
+
+#![cfg(all(feature = "testsupport", feature = "async"))]
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::pin;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake};
+
+use chrono::NaiveTime;
+use rx8900::sim::Simulator;
+use rx8900::{AsyncTransport, Error, Flag, Rx8900, Rx8900Async, Transport};
+
+mod common;
+use common::{AF, FLAGS, SEC, VLF, datetime};
+
+/// The simulator behind the async transport, shared with the test; its transfers complete immediately.
+#[derive(Clone)]
+struct AsyncSimulator(Rc<RefCell<Simulator>>);
+
+impl AsyncSimulator {
+    fn new(simulator: Simulator) -> Self {
+        Self(Rc::new(RefCell::new(simulator)))
+    }
+
+    fn transactions(&self) -> u32 {
+        self.0.borrow().transactions()
+    }
+}
+
+impl AsyncTransport for AsyncSimulator {
+    type Error = <Simulator as Transport>::Error;
+
+    async fn read_registers(&mut self, register: u8, data: &mut [u8]) -> Result<(), Self::Error> {
+        self.0.borrow_mut().read_registers(register, data)
+    }
+
+    async fn write_registers(&mut self, register: u8, data: &[u8]) -> Result<(), Self::Error> {
+        self.0.borrow_mut().write_registers(register, data)
+    }
+}
+
+struct NoopWake;
+
+impl Wake for NoopWake {
+    fn wake(self: Arc<Self>) {}
+}
+
+/// Polls a future that never waits to completion.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Arc::new(NoopWake).into();
+    let mut context = Context::from_waker(&waker);
+    match pin!(future).poll(&mut context) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("the simulator never makes a transfer wait"),
+    }
+}
+
+#[test]
+fn async_accessors_issue_the_transactions_of_the_blocking_ones() {
+    let now = datetime(2024, 5, 1, 12, 34, 56);
+    let mut blocking = Rx8900::new_unchecked(Simulator::new());
+    blocking.set_datetime(now).unwrap();
+    assert_eq!(blocking.datetime().unwrap(), now);
+    let raw = blocking.read_time_raw().unwrap();
+
+    let simulator = AsyncSimulator::new(Simulator::new());
+    let mut rtc = Rx8900Async::new(simulator.clone());
+    block_on(rtc.set_datetime(now)).unwrap();
+    assert_eq!(block_on(rtc.datetime()).unwrap(), now);
+    assert_eq!(block_on(rtc.read_time_raw()).unwrap(), raw);
+    assert_eq!(simulator.transactions(), blocking.release().transactions());
+    assert_eq!(simulator.0.borrow().time(), Some(now));
+}
+
+#[test]
+fn async_time_reads_honor_the_burst_limit() {
+    let simulator = AsyncSimulator::new(Simulator::new());
+    let mut rtc = Rx8900Async::new(simulator.clone());
+    block_on(rtc.set_datetime(datetime(2024, 5, 1, 12, 34, 56))).unwrap();
+    rtc.set_burst_limit(Some(3));
+    let before = simulator.transactions();
+    assert_eq!(block_on(rtc.datetime_fast()).unwrap(), NaiveTime::from_hms_opt(12, 34, 56).unwrap());
+    assert!(block_on(rtc.datetime()).is_ok());
+    // One transaction for the time of day, three for the seven time registers.
+    assert_eq!(simulator.transactions() - before, 1 + 3);
+}
+
+#[test]
+fn async_flags_are_cleared_without_touching_the_others() {
+    let mut simulator = Simulator::new();
+    simulator.set_register(FLAGS, VLF | AF);
+    let mut rtc = Rx8900Async::new(AsyncSimulator::new(simulator));
+    assert!(block_on(rtc.vlf()).unwrap());
+    block_on(rtc.clear_flag(Flag::VoltageLow)).unwrap();
+    assert!(!block_on(rtc.vlf()).unwrap());
+    assert!(block_on(rtc.af()).unwrap());
+}
+
+#[test]
+fn async_reads_report_invalid_times_and_writes_reject_other_centuries() {
+    let mut simulator = Simulator::new();
+    simulator.set_register(SEC, 0x7A);
+    let mut rtc = Rx8900Async::new(AsyncSimulator::new(simulator));
+    assert!(matches!(block_on(rtc.datetime()), Err(Error::InvalidTime(_))));
+    assert!(matches!(block_on(rtc.set_datetime(datetime(2100, 1, 1, 0, 0, 0))), Err(Error::InvalidInput)));
+}
@@ -0,0 +1,45 @@
+//! The public BCD conversions.
+//!
+//! ```console
+//! $ cargo test --target x86_64-unknown-linux-gnu --test bcd
+//! ```
+
+use rx8900::bcd::{checked_from_bcd, checked_to_bcd, from_bcd, to_bcd, BcdError};
+
+/// Register values built at compile time.
+const HALF_PAST: u8 = to_bcd(30);
+const TWENTY_THREE: u8 = from_bcd(0x23);
+
+#[test]
+fn every_two_digit_value_round_trips() {
+    for value in 0..=99 {
+        let encoded = to_bcd(value);
+        assert_eq!(encoded >> 4, value / 10);
+        assert_eq!(encoded & 0x0F, value % 10);
+        assert_eq!(from_bcd(encoded), value);
+        assert_eq!(checked_to_bcd(value), Ok(encoded));
+        assert_eq!(checked_from_bcd(encoded), Ok(value));
+    }
+}
+
+#[test]
+fn conversions_are_usable_in_constants() {
+    assert_eq!(HALF_PAST, 0x30);
+    assert_eq!(TWENTY_THREE, 23);
+}
+
+#[test]
+fn checked_conversions_reject_what_does_not_fit() {
+    for value in 100..=u8::MAX {
+        assert_eq!(checked_to_bcd(value), Err(BcdError::OutOfRange(value)));
+    }
+    for data in [0x0A, 0x1F, 0xA0, 0xF9, 0xFF] {
+        assert_eq!(checked_from_bcd(data), Err(BcdError::InvalidDigit(data)));
+    }
+}
+
+#[test]
+fn errors_name_the_rejected_value() {
+    assert_eq!(BcdError::OutOfRange(120).to_string(), "120 does not fit in two BCD digits");
+    assert_eq!(BcdError::InvalidDigit(0x1F).to_string(), "0x1F is not a valid BCD byte");
+}
@@ -0,0 +1,276 @@
+//! Bus-level behavior of the driver, counted against the simulator: how many transactions an operation takes and
+//! how bursts are split.
+//!
+//! ```console
+//! $ cargo test --target x86_64-unknown-linux-gnu --features testsupport --test bus
+//! ```
+
+#![cfg(feature = "testsupport")]
+
+use std::convert::Infallible;
+
+use chrono::{Duration, NaiveTime};
+use embedded_hal::blocking::i2c::{Read, Write};
+use rx8900::sim::{Faulty, Nack, Simulator};
+use rx8900::testsupport::FakeBoard;
+mod common;
+use common::datetime;
+
+#[cfg(feature = "timer")]
+use rx8900::PeriodicTimer;
+use rx8900::{BusOperation, Century, Error, Rx8900, Transport, UnwrapInfallible, DEFAULT_BUS_FREQUENCY_HZ};
+
+/// Returns the number of transactions `f` issues on the bus.
+fn transactions(board: &mut FakeBoard, f: impl FnOnce(&mut FakeBoard)) -> u32 {
+    let before = board.simulator().transactions();
+    f(board);
+    board.simulator().transactions() - before
+}
+
+#[test]
+fn time_is_read_in_one_burst_by_default() {
+    let mut board = FakeBoard::new();
+    board.rtc.set_datetime(datetime(2024, 5, 1, 12, 34, 56)).unwrap();
+    assert_eq!(board.rtc.burst_limit(), None);
+    assert_eq!(transactions(&mut board, |board| assert!(board.rtc.read_time_raw().is_ok())), 1);
+}
+
+#[test]
+fn time_is_written_in_one_burst() {
+    let mut board = FakeBoard::new();
+    let now = datetime(2024, 5, 1, 12, 34, 56);
+    assert_eq!(transactions(&mut board, |board| board.rtc.set_datetime(now).unwrap()), 1);
+    assert_eq!(board.simulator().time(), Some(now));
+}
+
+#[test]
+fn years_outside_the_century_are_not_written() {
+    let mut board = FakeBoard::new();
+    for year in [1999, 2100, -2024] {
+        let set = transactions(&mut board, |board| {
+            assert!(matches!(board.rtc.set_datetime(datetime(year, 5, 1, 12, 0, 0)), Err(Error::InvalidInput)));
+        });
+        assert_eq!(set, 0);
+    }
+}
+
+#[test]
+fn burst_limit_splits_reads_and_writes() {
+    let mut board = FakeBoard::new();
+    board.rtc.set_burst_limit(Some(3));
+    let now = datetime(2024, 5, 1, 12, 34, 56);
+
+    let writes = transactions(&mut board, |board| {
+        let mut delay = board.delay.clone();
+        board.rtc.set_datetime_safe(now, 0, &mut delay).unwrap();
+    });
+    // One read of the seconds, then the seven time registers in three writes.
+    assert_eq!(writes, 1 + 3);
+    assert_eq!(board.simulator().time(), Some(now));
+
+    let mut time = None;
+    let reads = transactions(&mut board, |board| time = Some(board.rtc.datetime().unwrap()));
+    assert_eq!(reads, 3);
+    assert_eq!(time, Some(now));
+}
+
+#[test]
+fn burst_limit_of_zero_transfers_one_register_at_a_time() {
+    let mut board = FakeBoard::new();
+    board.rtc.set_burst_limit(Some(0));
+    assert_eq!(board.rtc.burst_limit(), Some(1));
+    assert_eq!(transactions(&mut board, |board| assert!(board.rtc.read_time_raw().is_ok())), 7);
+
+    board.rtc.set_burst_limit(None);
+    assert_eq!(transactions(&mut board, |board| assert!(board.rtc.read_time_raw().is_ok())), 1);
+}
+
+#[test]
+fn caller_buffers_are_read_and_written_in_place() {
+    let mut board = FakeBoard::new();
+    // E.g. a slice of a DMA-safe pool.
+    let mut pool = [0u8; 16];
+    pool[4..8].copy_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+    assert_eq!(transactions(&mut board, |board| board.rtc.write_registers_from(0x07, &pool[4..8]).unwrap()), 1);
+    assert_eq!(board.simulator().register(0x07), 0xDE);
+    assert_eq!(board.simulator().register(0x0A), 0xEF);
+
+    pool.fill(0);
+    board.rtc.read_registers_into(0x07, &mut pool[8..12]).unwrap();
+    assert_eq!(pool[8..12], [0xDE, 0xAD, 0xBE, 0xEF]);
+
+    board.rtc.set_datetime(datetime(2024, 5, 1, 12, 34, 56)).unwrap();
+    let mut time = [0; 7];
+    board.rtc.read_time_raw_into(&mut time).unwrap();
+    assert_eq!(time, board.rtc.read_time_raw().unwrap());
+}
+
+#[test]
+fn caller_buffers_past_the_register_file_are_rejected() {
+    let mut board = FakeBoard::new();
+    let mut buffer = [0; 4];
+    assert!(board.rtc.read_registers_into(0x1C, &mut buffer).is_ok());
+    assert!(matches!(board.rtc.read_registers_into(0x1D, &mut buffer), Err(Error::InvalidInput)));
+    assert!(matches!(board.rtc.write_registers_from(0x1D, &buffer), Err(Error::InvalidInput)));
+    assert_eq!(transactions(&mut board, |board| assert!(board.rtc.read_registers_into(0x20, &mut []).is_err())), 0);
+}
+
+#[test]
+fn long_writes_are_split_at_16_registers() {
+    let mut board = FakeBoard::new();
+    // Rewrites the whole register file with its current contents, except for the RAM.
+    let mut image = [0; 0x20];
+    board.rtc.read_registers_into(0x00, &mut image).unwrap();
+    image[0x07] = 0x5A;
+    assert_eq!(transactions(&mut board, |board| board.rtc.write_registers_from(0x00, &image).unwrap()), 2);
+    assert_eq!(board.simulator().register(0x07), 0x5A);
+}
+
+/// A bridge supporting plain writes and reads, but no combined transfer.
+struct NoRepeatedStart(Simulator);
+
+impl Write for NoRepeatedStart {
+    type Error = Infallible;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Infallible> {
+        self.0.write(address, bytes)
+    }
+}
+
+impl Read for NoRepeatedStart {
+    type Error = Infallible;
+
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Infallible> {
+        self.0.read(address, buffer)
+    }
+}
+
+#[test]
+fn stop_start_reads_with_a_write_and_a_separate_read() {
+    let mut rtc = Rx8900::new_stop_start(NoRepeatedStart(Simulator::new()));
+    let now = datetime(2024, 5, 1, 12, 34, 56);
+    rtc.set_datetime(now).unwrap();
+    assert_eq!(rtc.datetime().unwrap(), now);
+
+    // Reading the time takes a write of the address and a read.
+    let mut rtc = Rx8900::new_stop_start(NoRepeatedStart(Simulator::new()));
+    rtc.read_time_raw().unwrap();
+    assert_eq!(rtc.release().release().0.transactions(), 2);
+}
+
+/// Another device at the RX8900 address, returning the register address as data.
+struct OtherDevice;
+
+impl Transport for OtherDevice {
+    type Error = Infallible;
+
+    fn read_registers(&mut self, register: u8, data: &mut [u8]) -> Result<(), Infallible> {
+        for (offset, value) in data.iter_mut().enumerate() {
+            *value = register + offset as u8;
+        }
+        Ok(())
+    }
+
+    fn write_registers(&mut self, _register: u8, _data: &[u8]) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+#[test]
+fn is_present_only_reads() {
+    let mut board = FakeBoard::new();
+    board.rtc.set_ram(0x5A).unwrap();
+    assert_eq!(transactions(&mut board, |board| assert!(board.rtc.is_present().unwrap())), 2);
+    assert_eq!(board.simulator().register(0x07), 0x5A);
+
+    // A device that acknowledges but does not mirror the registers is not an RX8900.
+    assert!(!Rx8900::new_unchecked(OtherDevice).is_present().unwrap_infallible());
+
+    // Nothing acknowledges.
+    let mut rtc = Rx8900::new_unchecked(Faulty::new(Simulator::new(), 1));
+    assert!(matches!(rtc.is_present(), Err(Nack)));
+    assert_eq!(rtc.release().nacks(), 1);
+}
+
+#[test]
+fn faulty_buses_fail_every_nth_transaction_only() {
+    let mut rtc = Rx8900::new_unchecked(Faulty::new(Simulator::new(), 3));
+    assert!(rtc.set_ram(0x11).is_ok());
+    assert!(rtc.set_ram(0x22).is_ok());
+    assert!(matches!(rtc.set_ram(0x33), Err(Error::Bus(Nack))));
+    assert_eq!(rtc.ram().unwrap(), 0x22);
+
+    let bus = rtc.release();
+    assert_eq!(bus.nacks(), 1);
+    // The failed transaction never reached the chip.
+    assert_eq!(bus.inner().transactions(), 3);
+}
+
+#[test]
+fn transaction_time_follows_the_bus_frequency() {
+    let mut board = FakeBoard::new();
+    assert_eq!(board.rtc.bus_frequency_hint(), DEFAULT_BUS_FREQUENCY_HZ);
+    // 30 bits of addressing and 7 bytes of 9 bits at 100 kHz.
+    assert_eq!(board.rtc.estimated_transaction_time(BusOperation::ReadTime), Duration::microseconds(930));
+    assert_eq!(
+        board.rtc.estimated_transaction_time(BusOperation::ReadTime),
+        board.rtc.estimated_transaction_time(BusOperation::Read(7))
+    );
+    assert_eq!(board.rtc.estimated_transaction_time(BusOperation::Write(2)), Duration::microseconds(380));
+
+    board.rtc.set_bus_frequency_hint(400_000);
+    assert_eq!(board.rtc.estimated_transaction_time(BusOperation::ReadTime), Duration::microseconds(233));
+
+    // Three transactions of 3, 3 and 1 registers.
+    board.rtc.set_bus_frequency_hint(100_000);
+    board.rtc.set_burst_limit(Some(3));
+    assert_eq!(board.rtc.estimated_transaction_time(BusOperation::ReadTime), Duration::microseconds(1530));
+}
+
+#[cfg(feature = "timer")]
+#[test]
+fn fast_periodic_timers_are_rejected_on_a_slow_bus() {
+    let mut board = FakeBoard::new();
+    // Eight ticks of the 4096 Hz source, 1.95 ms.
+    let period = Duration::nanoseconds(1_953_125);
+    assert!(board.rtc.estimated_transaction_time(BusOperation::ServiceInterrupt) < period / 2);
+    board.rtc.start_countdown(period).unwrap();
+
+    board.rtc.set_bus_frequency_hint(10_000);
+    assert!(matches!(board.rtc.start_countdown(period), Err(Error::BusSaturated)));
+
+    // 680 ms of bus time for each interrupt of a 1 s period.
+    let mut timer = PeriodicTimer::new(Duration::seconds(1)).unwrap();
+    assert!(timer.start(&mut board.rtc).is_ok());
+    board.rtc.set_bus_frequency_hint(100);
+    assert!(matches!(timer.start(&mut board.rtc), Err(Error::BusSaturated)));
+}
+
+#[test]
+fn datetime_fast_reads_three_registers_in_one_transaction() {
+    let mut board = FakeBoard::new();
+    board.rtc.set_datetime(datetime(2024, 5, 1, 12, 34, 56)).unwrap();
+    let mut time = None;
+    assert_eq!(transactions(&mut board, |board| time = Some(board.rtc.datetime_fast().unwrap())), 1);
+    assert_eq!(time, NaiveTime::from_hms_opt(12, 34, 56));
+    assert_eq!(board.rtc.estimated_transaction_time(BusOperation::Read(3)), Duration::microseconds(570));
+
+    // Split down to single registers, the read takes three transactions.
+    board.rtc.set_burst_limit(Some(1));
+    assert_eq!(board.rtc.estimated_transaction_time(BusOperation::Read(3)), Duration::microseconds(1170));
+    assert_eq!(transactions(&mut board, |board| assert!(board.rtc.datetime_fast().is_ok())), 3);
+
+    // An hour of 0x25 is not a valid time.
+    board.simulator_mut().set_register(0x02, 0x25);
+    assert!(matches!(board.rtc.datetime_fast(), Err(Error::InvalidTime([0x56, 0x34, 0x25, 0, 0, 0, 0]))));
+}
+
+#[test]
+fn century_reads_report_the_raw_registers_of_invalid_times() {
+    let mut board = FakeBoard::new();
+    board.rtc.set_datetime(datetime(2024, 5, 1, 12, 34, 56)).unwrap();
+    board.simulator_mut().set_register(0x00, 0x7A);
+    let raw = board.rtc.read_time_raw().unwrap();
+    let mut clock = Century::<_, 2100>::new(board.rtc);
+    assert!(matches!(clock.datetime(), Err(Error::InvalidTime(registers)) if registers == raw));
+}
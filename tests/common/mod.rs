@@ -0,0 +1,45 @@
+//! Helpers shared by the integration tests.
+//!
+//! Each test crate pulls in the whole module with `mod common;` but uses only part of it.
+
+#![allow(dead_code)]
+
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+#[cfg(feature = "testsupport")]
+use rx8900::testsupport::FakeBoard;
+
+pub const SEC: u8 = 0x00;
+pub const EXTENSION: u8 = 0x0D;
+pub const FLAGS: u8 = 0x0E;
+pub const CONTROL: u8 = 0x0F;
+
+// Flag register bits
+pub const UF: u8 = 0b00100000;
+pub const TF: u8 = 0b00010000;
+pub const AF: u8 = 0b00001000;
+pub const VLF: u8 = 0b00000010;
+pub const VDET: u8 = 0b00000001;
+
+pub fn datetime(y: i32, mo: u32, d: u32, h: u32, mi: u32, s: u32) -> NaiveDateTime {
+    NaiveDateTime::new(
+        NaiveDate::from_ymd_opt(y, mo, d).unwrap(),
+        NaiveTime::from_hms_opt(h, mi, s).unwrap(),
+    )
+}
+
+/// Creates a board whose clock reads `now`, with every flag cleared.
+#[cfg(feature = "testsupport")]
+pub fn board_at(now: NaiveDateTime) -> FakeBoard {
+    let mut board = FakeBoard::new();
+    board.rtc.set_datetime(now).unwrap();
+    board.simulator_mut().set_register(FLAGS, 0);
+    board
+}
+
+/// Moves the clock to `now` and raises TF, as the expiry of the counter does.
+#[cfg(feature = "testsupport")]
+pub fn expire_at(board: &FakeBoard, now: NaiveDateTime) {
+    let mut simulator = board.simulator_mut();
+    simulator.set_time(now);
+    simulator.set_register(FLAGS, TF);
+}
@@ -0,0 +1,134 @@
+//! The serial provisioning console, parsing command lines into register writes on the simulator.
+//!
+//! ```console
+//! $ cargo test --target x86_64-unknown-linux-gnu --features testsupport,console --test console
+//! ```
+
+#![cfg(all(feature = "testsupport", feature = "console", feature = "alarm"))]
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::rc::Rc;
+
+use chrono::NaiveDateTime;
+
+use rx8900::console::{Console, LINE_LEN};
+use rx8900::testsupport::FakeBoard;
+
+mod common;
+use common::{AF, CONTROL, FLAGS, VLF, board_at, datetime};
+
+const ALARM_MINUTE: u8 = 0x08;
+const ALARM_HOUR: u8 = 0x09;
+
+/// The two directions of a serial port, shared by the test and the console.
+#[derive(Default)]
+struct Lines {
+    rx: VecDeque<u8>,
+    tx: Vec<u8>,
+}
+
+/// A serial port fed by the test, recording what the console answers.
+#[derive(Clone, Default)]
+struct Serial(Rc<RefCell<Lines>>);
+
+impl embedded_io::ErrorType for Serial {
+    type Error = Infallible;
+}
+
+impl embedded_io::Read for Serial {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Infallible> {
+        let mut lines = self.0.borrow_mut();
+        let count = buf.len().min(lines.rx.len());
+        for (slot, byte) in buf.iter_mut().zip(lines.rx.drain(..count)) {
+            *slot = byte;
+        }
+        Ok(count)
+    }
+}
+
+impl embedded_io::Write for Serial {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Infallible> {
+        self.0.borrow_mut().tx.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+/// A console on a simulated board, and the serial port it serves.
+struct Session {
+    console: Console<Serial>,
+    serial: Serial,
+    board: FakeBoard,
+}
+
+impl Session {
+    fn at(now: NaiveDateTime) -> Self {
+        let serial = Serial::default();
+        Self { console: Console::new(serial.clone()), serial, board: board_at(now) }
+    }
+
+    /// Sends bytes to the console, polls until they are consumed and returns the response.
+    fn send(&mut self, input: &str) -> String {
+        self.serial.0.borrow_mut().rx.extend(input.bytes());
+        while !self.serial.0.borrow().rx.is_empty() {
+            self.console.poll(&mut self.board.rtc).unwrap();
+        }
+        String::from_utf8(std::mem::take(&mut self.serial.0.borrow_mut().tx)).unwrap()
+    }
+}
+
+#[test]
+fn set_time_programs_the_clock_and_clears_vlf() {
+    let mut session = Session::at(datetime(2024, 1, 1, 0, 0, 0));
+    session.board.simulator_mut().set_register(FLAGS, VLF);
+
+    assert_eq!(session.send("SET TIME 2024-05-01T12:34:56\r\n"), "OK\r\n");
+    assert_eq!(session.board.rtc.datetime().unwrap(), datetime(2024, 5, 1, 12, 34, 56));
+    assert_eq!(session.board.simulator().register(FLAGS) & VLF, 0);
+    assert_eq!(session.send("GET TIME\n"), "OK 2024-05-01T12:34:56\r\n");
+}
+
+#[test]
+fn alarm_commands_program_the_alarm_registers() {
+    let mut session = Session::at(datetime(2024, 5, 1, 12, 0, 0));
+
+    assert_eq!(session.send("ALARM 07:30\r\n"), "OK\r\n");
+    assert_eq!(session.board.simulator().register(ALARM_MINUTE), 0x30);
+    assert_eq!(session.board.simulator().register(ALARM_HOUR), 0x07);
+    // AIE
+    assert_ne!(session.board.simulator().register(CONTROL) & 0b00001000, 0);
+
+    session.board.simulator_mut().set_register(FLAGS, AF);
+    assert_eq!(session.send("ALARM\r\n"), "OK AIE=1 AF=1\r\n");
+    assert_eq!(session.send("ALARM OFF\r\n"), "OK\r\n");
+    assert_eq!(session.board.simulator().register(CONTROL) & 0b00001000, 0);
+    assert_eq!(session.board.simulator().register(FLAGS) & AF, 0);
+}
+
+#[test]
+fn rejected_lines_leave_the_registers_untouched() {
+    let mut session = Session::at(datetime(2024, 5, 1, 12, 0, 0));
+    let before = session.board.simulator().transactions();
+
+    assert_eq!(session.send("set time 2024-05-01T00:00:00\r\n"), "ERR unknown command\r\n");
+    assert_eq!(session.send("SET TIME 2024-13-01T00:00:00\r\n"), "ERR invalid time\r\n");
+    assert_eq!(session.send("ALARM 24:00\r\n"), "ERR invalid time\r\n");
+    let long = format!("SET TIME {}\r\n", "9".repeat(LINE_LEN));
+    assert_eq!(session.send(&long), "ERR line too long\r\n");
+    assert_eq!(session.board.simulator().transactions(), before);
+    assert_eq!(session.board.rtc.datetime().unwrap(), datetime(2024, 5, 1, 12, 0, 0));
+}
+
+#[test]
+fn commands_split_across_reads_are_executed_once_complete() {
+    let mut session = Session::at(datetime(2024, 5, 1, 12, 0, 0));
+
+    assert_eq!(session.send("SET TIME 2030-"), "");
+    assert_eq!(session.board.rtc.datetime().unwrap(), datetime(2024, 5, 1, 12, 0, 0));
+    assert_eq!(session.send("06-15T08:00:00\r\nGET TIME\r\n"), "OK\r\nOK 2030-06-15T08:00:00\r\n");
+}
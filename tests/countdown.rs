@@ -0,0 +1,149 @@
+//! Countdowns longer than the wake-up timer counts, and their resumption after a reset of the MCU, scripted against
+//! the simulator.
+//!
+//! ```console
+//! $ cargo test --target x86_64-unknown-linux-gnu --features testsupport --test countdown
+//! ```
+
+#![cfg(all(feature = "testsupport", feature = "timer"))]
+
+use chrono::{Duration, NaiveDateTime};
+use rx8900::testsupport::FakeBoard;
+use rx8900::{CountdownStorage, Error, SourceClock};
+
+mod common;
+use common::{CONTROL, EXTENSION, FLAGS, SEC, TF, board_at, datetime, expire_at};
+
+const TE: u8 = 0b00010000;
+const TIE: u8 = 0b00010000;
+
+/// Returns whether the timer and its interrupt are enabled.
+fn timer_running(board: &FakeBoard) -> bool {
+    let simulator = board.simulator();
+    simulator.register(EXTENSION) & TE != 0 && simulator.register(CONTROL) & TIE != 0
+}
+
+/// A retained RAM section keeping the target across resets.
+#[derive(Default)]
+struct Retained(Option<NaiveDateTime>);
+
+impl CountdownStorage for Retained {
+    fn save(&mut self, target: NaiveDateTime) {
+        self.0 = Some(target);
+    }
+
+    fn load(&mut self) -> Option<NaiveDateTime> {
+        self.0
+    }
+
+    fn clear(&mut self) {
+        self.0 = None;
+    }
+}
+
+/// Simulates a reset of the MCU: a new driver on the same chip, which kept counting.
+fn reset(board: &FakeBoard, now: NaiveDateTime) -> FakeBoard {
+    let mut simulator = board.simulator().clone();
+    simulator.set_time(now);
+    FakeBoard::with_simulator(simulator)
+}
+
+#[test]
+fn long_countdown_chains_cycles_until_the_target() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    let mut countdown = board.rtc.start_long_countdown(Duration::days(10)).unwrap();
+    assert_eq!(countdown.target(), datetime(2024, 5, 11, 12, 0, 0));
+    assert_eq!(board.rtc.source_clock().unwrap(), SourceClock::SourceClockMinute);
+    assert_eq!(board.rtc.timer_counter().unwrap(), 4095);
+    assert!(timer_running(&board));
+    assert!(!countdown.on_timer_interrupt(&mut board.rtc).unwrap());
+
+    // Three full cycles of 4095 minutes, then one a minute short of the 2114.5 left.
+    let mut now = datetime(2024, 5, 1, 12, 0, 30);
+    for _ in 0..3 {
+        now += Duration::minutes(4095);
+        expire_at(&board, now);
+        assert!(!countdown.on_timer_interrupt(&mut board.rtc).unwrap());
+        assert_eq!(board.simulator().register(FLAGS) & TF, 0);
+    }
+    assert_eq!(board.rtc.timer_counter().unwrap(), 2113);
+
+    // The last cycle counts the seconds to the target.
+    expire_at(&board, datetime(2024, 5, 11, 11, 59, 20));
+    assert!(!countdown.on_timer_interrupt(&mut board.rtc).unwrap());
+    assert_eq!(board.rtc.source_clock().unwrap(), SourceClock::SourceClockSecond);
+    assert_eq!(board.rtc.timer_counter().unwrap(), 40);
+
+    expire_at(&board, datetime(2024, 5, 11, 12, 0, 0));
+    assert!(countdown.on_timer_interrupt(&mut board.rtc).unwrap());
+    assert_eq!(countdown.cycles(), 5);
+    assert!(!timer_running(&board));
+}
+
+#[test]
+fn long_countdown_rounds_up_to_whole_seconds() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    let countdown = board.rtc.start_long_countdown(Duration::milliseconds(1500)).unwrap();
+    assert_eq!(countdown.target(), datetime(2024, 5, 1, 12, 0, 2));
+    assert!(matches!(board.rtc.start_long_countdown(Duration::zero()), Err(Error::InvalidInput)));
+}
+
+#[test]
+fn long_countdown_keeps_an_interrupt_whose_time_cannot_be_read() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    let mut countdown = board.rtc.start_long_countdown(Duration::hours(1)).unwrap();
+    expire_at(&board, datetime(2024, 5, 1, 13, 0, 0));
+    board.simulator_mut().set_register(SEC, 0x7A);
+    assert!(matches!(countdown.on_timer_interrupt(&mut board.rtc), Err(Error::InvalidTime(_))));
+    assert_eq!(board.simulator().register(FLAGS) & TF, TF);
+    assert_eq!(countdown.cycles(), 0);
+
+    board.simulator_mut().set_register(SEC, 0x00);
+    assert!(countdown.on_timer_interrupt(&mut board.rtc).unwrap());
+}
+
+#[test]
+fn persistent_countdown_resumes_after_a_reset() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    let mut storage = Retained::default();
+    board.rtc.start_persistent_countdown(Duration::days(3), &mut storage).unwrap();
+    assert_eq!(storage.0, Some(datetime(2024, 5, 4, 12, 0, 0)));
+
+    let mut board = reset(&board, datetime(2024, 5, 4, 11, 0, 0));
+    let mut countdown = board.rtc.resume_countdown(&mut storage).unwrap().unwrap();
+    assert_eq!(countdown.target(), datetime(2024, 5, 4, 12, 0, 0));
+    assert_eq!(countdown.cycles(), 0);
+    assert_eq!(board.rtc.source_clock().unwrap(), SourceClock::SourceClockSecond);
+    assert_eq!(board.rtc.timer_counter().unwrap(), 3600);
+    assert!(timer_running(&board));
+
+    expire_at(&board, datetime(2024, 5, 4, 12, 0, 0));
+    assert!(countdown.on_timer_interrupt(&mut board.rtc).unwrap());
+    // Forgetting the target is left to the application.
+    assert_eq!(storage.0, Some(datetime(2024, 5, 4, 12, 0, 0)));
+    storage.clear();
+    assert_eq!(board.rtc.resume_countdown(&mut storage).unwrap(), None);
+}
+
+#[test]
+fn a_target_passed_during_the_reset_ends_at_the_next_second() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    let mut storage = Retained::default();
+    board.rtc.start_persistent_countdown(Duration::minutes(5), &mut storage).unwrap();
+
+    let mut board = reset(&board, datetime(2024, 5, 1, 12, 10, 0));
+    let mut countdown = board.rtc.resume_countdown(&mut storage).unwrap().unwrap();
+    assert_eq!(board.rtc.source_clock().unwrap(), SourceClock::SourceClockSecond);
+    assert_eq!(board.rtc.timer_counter().unwrap(), 1);
+
+    expire_at(&board, datetime(2024, 5, 1, 12, 10, 1));
+    assert!(countdown.on_timer_interrupt(&mut board.rtc).unwrap());
+    assert!(!timer_running(&board));
+}
+
+#[test]
+fn nothing_is_resumed_without_a_stored_target() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    assert_eq!(board.rtc.resume_countdown(&mut Retained::default()).unwrap(), None);
+    assert!(!timer_running(&board));
+}
@@ -0,0 +1,71 @@
+//! Clock drift reproduced by the `ErrorModel` of the simulator, and the drift the driver estimates from the
+//! corrections of later synchronizations.
+//!
+//! ```console
+//! $ cargo test --target x86_64-unknown-linux-gnu --features testsupport --test drift
+//! ```
+
+#![cfg(feature = "testsupport")]
+
+use chrono::Duration;
+use rx8900::sim::ErrorModel;
+use rx8900::AdjustmentSource;
+
+mod common;
+use common::{board_at, datetime};
+
+/// Temperatures of a day, each held for six hours, in degrees Celsius.
+const DAILY_TEMPERATURES: [i32; 4] = [-10, 25, 60, 25];
+
+/// Deviation of an uncompensated tuning fork crystal: -0.034 ppm/°C² around its 25 °C turnover point.
+fn crystal_ppm(temperature: i32) -> i32 {
+    -(temperature - 25) * (temperature - 25) * 34 / 1000
+}
+
+#[test]
+fn drift_follows_the_ppm_curve() {
+    assert_eq!(DAILY_TEMPERATURES.map(crystal_ppm), [-41, 0, -41, 0]);
+
+    let start = datetime(2024, 5, 1, 0, 0, 0);
+    let board = board_at(start);
+    let mut drift_ns = 0;
+    for (segment, temperature) in DAILY_TEMPERATURES.into_iter().cycle().take(8).enumerate() {
+        let ppm = crystal_ppm(temperature);
+        let mut simulator = board.simulator_mut();
+        simulator.set_error_model(ErrorModel { drift_ppm: ppm });
+        simulator.elapse(Duration::hours(6));
+        // Six hours are 21.6e12 ns, each ppm adding 21.6e6 ns.
+        drift_ns += 21_600_000 * i64::from(ppm);
+        let elapsed = Duration::hours(6 * (segment as i64 + 1));
+        let expected = start + elapsed + Duration::nanoseconds(drift_ns);
+        // The calendar only shows whole seconds.
+        assert_eq!(simulator.time(), Some(expected - Duration::nanoseconds(drift_ns.rem_euclid(1_000_000_000))));
+    }
+    // Two days at -41 ppm for half the time lose 1.7712 s each.
+    assert_eq!(drift_ns, -3_542_400_000);
+}
+
+#[test]
+fn synchronizations_measure_the_drift_of_the_curve() {
+    let start = datetime(2024, 5, 1, 0, 0, 0);
+    let mut board = board_at(start);
+    let true_time = |elapsed: Duration| (start + elapsed).and_utc().timestamp();
+    assert_eq!(board.rtc.set_timestamp_from(true_time(Duration::zero()), AdjustmentSource::Ntp).unwrap(), 0);
+    assert_eq!(board.rtc.time_quality().estimated_error_ppm, 5);
+
+    for temperature in DAILY_TEMPERATURES.into_iter().cycle().take(40) {
+        let mut simulator = board.simulator_mut();
+        simulator.set_error_model(ErrorModel { drift_ppm: crystal_ppm(temperature) });
+        simulator.elapse(Duration::hours(6));
+    }
+    // Ten days lose 17.712 s, so the clock shows 863982.288 s elapsed and reads 18 s late.
+    assert_eq!(board.rtc.datetime().unwrap(), start + Duration::days(10) - Duration::seconds(18));
+    let correction = board.rtc.set_timestamp_from(true_time(Duration::days(10)), AdjustmentSource::Ntp).unwrap();
+    assert_eq!(correction, 18);
+    assert_eq!(board.rtc.datetime().unwrap(), start + Duration::days(10));
+
+    // 18 s over the 863982 s the clock counted, against the 20.5 ppm the curve averages.
+    let quality = board.rtc.time_quality();
+    assert_eq!(quality.estimated_error_ppm, 20);
+    assert_eq!(quality.estimated_error_ms(start + Duration::days(11)), Some(1728));
+}
@@ -0,0 +1,71 @@
+//! The wake-up schedule of `DutyCycle`, run on the simulated alarm.
+//!
+//! ```console
+//! $ cargo test --target x86_64-unknown-linux-gnu --features testsupport --test duty
+//! ```
+
+#![cfg(all(feature = "testsupport", feature = "alarm"))]
+
+use chrono::{Duration, NaiveDateTime};
+use rx8900::testsupport::FakeBoard;
+use rx8900::DutyCycle;
+
+mod common;
+use common::{board_at, datetime};
+
+/// Sleeps until the alarm fires, checking that it does not fire a minute early.
+fn sleep_until_alarm(board: &mut FakeBoard, wake: NaiveDateTime) {
+    let now = board.rtc.datetime().unwrap();
+    board.simulator_mut().advance(wake - now - Duration::minutes(1));
+    assert!(!board.rtc.af().unwrap());
+    board.simulator_mut().advance(Duration::minutes(1));
+    assert!(board.rtc.af().unwrap());
+    assert_eq!(board.rtc.datetime().unwrap(), wake);
+}
+
+#[test]
+fn wake_ups_stay_within_the_budget() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 30));
+    // 2 s per hour at 150 ms per wake-up: every 4.5 minutes, rounded up to 5.
+    let mut duty = DutyCycle::new(2_000, 150).unwrap();
+    assert_eq!(duty.interval(), Duration::minutes(5));
+    assert_eq!(duty.next_wake(), None);
+
+    let mut wake = duty.start(&mut board.rtc).unwrap();
+    assert_eq!(wake, datetime(2024, 5, 1, 12, 5, 0));
+    assert_eq!((board.rtc.hour_alarm().unwrap(), board.rtc.min_alarm().unwrap()), (12, 5));
+
+    let mut wake_ups = 0;
+    while wake < datetime(2024, 5, 1, 13, 0, 30) {
+        sleep_until_alarm(&mut board, wake);
+        wake_ups += 1;
+        let next = duty.on_wake(&mut board.rtc, 150).unwrap();
+        assert_eq!(next - wake, Duration::minutes(5));
+        assert_eq!(duty.next_wake(), Some(next));
+        wake = next;
+    }
+    assert_eq!(wake_ups, 12);
+    assert!(wake_ups * duty.awake_ms() <= 2_000);
+}
+
+#[test]
+fn longer_wake_ups_stretch_the_schedule() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    let mut duty = DutyCycle::new(2_000, 150).unwrap();
+    let wake = duty.start(&mut board.rtc).unwrap();
+    sleep_until_alarm(&mut board, wake);
+
+    // The estimate moves a quarter of the way: (3 * 150 + 750) / 4 = 300 ms, every 9 minutes.
+    let next = duty.on_wake(&mut board.rtc, 750).unwrap();
+    assert_eq!(duty.awake_ms(), 300);
+    assert_eq!(next, datetime(2024, 5, 1, 12, 14, 0));
+    assert_eq!((board.rtc.hour_alarm().unwrap(), board.rtc.min_alarm().unwrap()), (12, 14));
+    sleep_until_alarm(&mut board, next);
+
+    // Short wake-ups bring the schedule back, never below one minute.
+    let mut duty = DutyCycle::new(3_600_000, 1).unwrap();
+    assert_eq!(duty.interval(), Duration::minutes(1));
+    let next = duty.start(&mut board.rtc).unwrap();
+    assert_eq!(next, datetime(2024, 5, 1, 12, 15, 0));
+    assert_eq!(DutyCycle::new(0, 150), None);
+}
@@ -0,0 +1,56 @@
+//! Interrupt event handlers reading a corrupted time, scripted against the simulator: the event must stay pending
+//! rather than be cleared and lost.
+//!
+//! ```console
+//! $ cargo test --target x86_64-unknown-linux-gnu --features testsupport --test events
+//! ```
+
+#![cfg(all(feature = "testsupport", feature = "alarm"))]
+
+use std::cell::RefCell;
+
+use chrono::Duration;
+use rx8900::{Error, Event, EventLog, Rollover};
+
+mod common;
+use common::{AF, FLAGS, SEC, UF, board_at, datetime};
+
+#[test]
+fn rollover_keeps_the_update_pending_if_the_time_cannot_be_read() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 59, 58));
+    let minutes = RefCell::new(Vec::new());
+    let mut on_minute = |now| minutes.borrow_mut().push(now);
+    let mut rollover = Rollover::new();
+    rollover.on_minute(&mut on_minute);
+
+    board.simulator_mut().advance(Duration::seconds(1));
+    assert!(rollover.on_update_interrupt(&mut board.rtc).unwrap());
+
+    // The next update comes with a glitched seconds register.
+    board.simulator_mut().advance(Duration::seconds(1));
+    board.simulator_mut().set_register(SEC, 0x7A);
+    assert!(matches!(rollover.on_update_interrupt(&mut board.rtc), Err(Error::InvalidTime(_))));
+    assert_eq!(board.simulator().register(FLAGS) & UF, UF);
+
+    board.simulator_mut().set_register(SEC, 0x00);
+    assert!(rollover.on_update_interrupt(&mut board.rtc).unwrap());
+    assert_eq!(board.simulator().register(FLAGS) & UF, 0);
+    assert_eq!(*minutes.borrow(), [datetime(2024, 5, 1, 13, 0, 0)]);
+}
+
+#[test]
+fn event_log_keeps_the_events_pending_if_the_time_cannot_be_read() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    let mut log = EventLog::<4>::new();
+    board.simulator_mut().set_register(FLAGS, UF | AF);
+    board.simulator_mut().set_register(SEC, 0x7A);
+    assert!(matches!(board.rtc.log_events(&mut log), Err(Error::InvalidTime(_))));
+    assert_eq!(board.simulator().register(FLAGS), UF | AF);
+    assert!(log.is_empty());
+
+    board.simulator_mut().set_register(SEC, 0x00);
+    assert_eq!(board.rtc.log_events(&mut log).unwrap(), 2);
+    assert_eq!(board.simulator().register(FLAGS), 0);
+    let now = datetime(2024, 5, 1, 12, 0, 0);
+    assert_eq!(log.iter().copied().collect::<Vec<_>>(), [(Event::Alarm, now), (Event::Update, now)]);
+}
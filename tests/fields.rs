@@ -0,0 +1,77 @@
+//! The validated year, month and day fields and their register encoding, checked against the simulator.
+//!
+//! ```console
+//! $ cargo test --target x86_64-unknown-linux-gnu --features testsupport --test fields
+//! ```
+
+#![cfg(feature = "testsupport")]
+
+use rx8900::testsupport::FakeBoard;
+use rx8900::{Day, Error, Month, Year};
+
+mod common;
+use common::{board_at, datetime};
+
+const DAY: u8 = 0x04;
+const MONTH: u8 = 0x05;
+const YEAR: u8 = 0x06;
+
+#[test]
+fn fields_check_their_range_once() {
+    assert_eq!(Year::new(99).map(Year::get), Some(99));
+    assert_eq!(Year::new(100), None);
+    assert_eq!(Month::new(0), None);
+    assert_eq!(Month::new(13), None);
+    assert_eq!(Day::new(0), None);
+    assert_eq!(Day::new(32), None);
+    assert_eq!((Month::MIN.get(), Month::MAX.get()), (1, 12));
+    assert_eq!((Day::MIN.get(), Day::MAX.get()), (1, 31));
+    assert_eq!(Month::try_from(13), Err(13));
+    assert_eq!(u8::from(Day::try_from(31).unwrap()), 31);
+}
+
+#[test]
+fn typed_setters_write_bcd_registers() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    board.rtc.set_year_typed(Year::new(37).unwrap()).unwrap();
+    board.rtc.set_month_typed(Month::new(12).unwrap()).unwrap();
+    board.rtc.set_day_typed(Day::new(31).unwrap()).unwrap();
+    {
+        let simulator = board.simulator();
+        assert_eq!((simulator.register(YEAR), simulator.register(MONTH), simulator.register(DAY)), (0x37, 0x12, 0x31));
+    }
+    assert_eq!(board.rtc.datetime().unwrap(), datetime(2037, 12, 31, 12, 0, 0));
+}
+
+#[test]
+fn every_valid_value_round_trips_through_the_registers() {
+    let mut board = FakeBoard::new();
+    for value in 0..=99 {
+        board.rtc.set_year_typed(Year::new(value).unwrap()).unwrap();
+        assert_eq!(board.rtc.year_typed().unwrap().get(), value);
+    }
+    for value in 1..=12 {
+        board.rtc.set_month_typed(Month::new(value).unwrap()).unwrap();
+        assert_eq!(board.rtc.month_typed().unwrap().get(), value);
+    }
+    for value in 1..=31 {
+        board.rtc.set_day_typed(Day::new(value).unwrap()).unwrap();
+        assert_eq!(board.rtc.day_typed().unwrap().get(), value);
+    }
+}
+
+#[test]
+fn corrupt_registers_are_not_decoded() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    for (month, expected) in [(0x00, None), (0x13, None), (0x0A, None), (0x09, Some(9)), (0xE9, Some(9))] {
+        board.simulator_mut().set_register(MONTH, month);
+        match expected {
+            Some(expected) => assert_eq!(board.rtc.month_typed().unwrap().get(), expected),
+            None => assert!(matches!(board.rtc.month_typed(), Err(Error::InvalidDeviceData)), "{:#04X}", month),
+        }
+    }
+    board.simulator_mut().set_register(DAY, 0x32);
+    assert!(matches!(board.rtc.day_typed(), Err(Error::InvalidDeviceData)));
+    board.simulator_mut().set_register(YEAR, 0x9A);
+    assert!(matches!(board.rtc.year_typed(), Err(Error::InvalidDeviceData)));
+}
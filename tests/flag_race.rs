@@ -0,0 +1,151 @@
+//! Regression tests for flags raised between the read of the flag register and the write clearing it.
+//!
+//! The simulator raises the injected flags right after the host reads the flag register, which is the window a
+//! read-modify-write clear would lose them in.
+//!
+//! ```console
+//! $ cargo test --target x86_64-unknown-linux-gnu --features testsupport --test flag_race
+//! ```
+
+#![cfg(all(feature = "testsupport", feature = "alarm", feature = "timer"))]
+
+use embedded_hal::blocking::i2c::{Write, WriteRead};
+use rx8900::testsupport::FakeBoard;
+use rx8900::{EventLog, Flag, InterruptEvents};
+
+mod common;
+use common::{AF, FLAGS, TF, UF, datetime};
+
+const ADDRESS: u8 = 0x32;
+
+/// Creates a board with every flag cleared.
+fn board() -> FakeBoard {
+    let board = FakeBoard::new();
+    board.simulator_mut().set_register(FLAGS, 0);
+    board
+}
+
+fn raise(board: &FakeBoard, flags: u8) {
+    let mut simulator = board.simulator_mut();
+    let current = simulator.register(FLAGS);
+    simulator.set_register(FLAGS, current | flags);
+}
+
+fn flags(board: &FakeBoard) -> u8 {
+    board.simulator().register(FLAGS)
+}
+
+fn events(update: bool, timer: bool, alarm: bool) -> InterruptEvents {
+    InterruptEvents { update, timer, alarm }
+}
+
+#[test]
+fn read_modify_write_loses_the_raced_flag() {
+    // Reproduces the race without the driver, to prove the injection hits the window.
+    let board = board();
+    raise(&board, AF);
+    board.simulator_mut().inject_flag_race(TF);
+    let mut data = [0];
+    board.simulator_mut().write_read(ADDRESS, &[FLAGS], &mut data).unwrap();
+    assert_eq!(data[0], AF);
+    assert_eq!(flags(&board), AF | TF);
+    board.simulator_mut().write(ADDRESS, &[FLAGS, data[0] & !AF]).unwrap();
+    assert_eq!(flags(&board), 0, "the timer event was lost");
+}
+
+#[test]
+fn clear_flag_keeps_a_flag_raised_after_the_read() {
+    let mut board = board();
+    raise(&board, AF);
+    board.simulator_mut().inject_flag_race(TF);
+
+    assert!(board.rtc.alarm_flag().unwrap());
+    board.rtc.clear_flag(Flag::Alarm).unwrap();
+    assert_eq!(flags(&board), TF);
+    assert!(board.rtc.timer_flag().unwrap());
+}
+
+#[test]
+fn sleep_until_keeps_a_flag_raised_while_it_arms_the_alarm() {
+    let mut board = board();
+    raise(&board, AF);
+    board.simulator_mut().inject_flag_race(TF);
+
+    board.rtc.sleep_until(datetime(2024, 5, 1, 12, 30, 0)).unwrap();
+    // Any read of the flag register raises the flag still pending from the race.
+    assert!(!board.rtc.alarm_flag().unwrap());
+    assert_eq!(flags(&board), TF);
+}
+
+#[test]
+fn a_flag_raised_again_before_its_clear_is_one_event() {
+    // A flag raised again before it is cleared is one pending event on the chip; clearing it acknowledges both.
+    let mut board = board();
+    raise(&board, UF);
+    board.simulator_mut().inject_flag_race(UF | AF);
+
+    assert!(board.rtc.update_flag().unwrap());
+    board.rtc.clear_flag(Flag::Update).unwrap();
+    assert_eq!(flags(&board), AF);
+}
+
+#[test]
+fn poll_events_reports_a_raced_flag_on_the_next_poll() {
+    let mut board = board();
+    raise(&board, AF);
+    board.simulator_mut().inject_flag_race(TF);
+
+    let polled = board.rtc.poll_events().unwrap();
+    assert_eq!(polled.interrupts, events(false, false, true));
+    assert_eq!(flags(&board), TF);
+
+    let polled = board.rtc.poll_events().unwrap();
+    assert_eq!(polled.interrupts, events(false, true, false));
+    assert_eq!(flags(&board), 0);
+    assert!(board.rtc.poll_events().unwrap().interrupts.is_empty());
+}
+
+#[test]
+fn poll_events_never_loses_an_event() {
+    let mut board = board();
+    let (mut raised, mut seen) = ([0u32; 3], [0u32; 3]);
+    let sources = [UF, TF, AF];
+    for round in 0..300u32 {
+        // Flags set before the poll, and flags racing it, in every combination over the rounds. A flag still set
+        // when it is raised again is one event on the chip, so only new events are raised.
+        let pending = flags(&board);
+        let before = sources[(round % 3) as usize] & !pending;
+        let racing = sources[(round / 3 % 3) as usize] & !(pending | before);
+        raise(&board, before);
+        board.simulator_mut().inject_flag_race(racing);
+        for (count, flag) in raised.iter_mut().zip(sources) {
+            *count += u32::from((before | racing) & flag != 0);
+        }
+
+        let polled = board.rtc.poll_events().unwrap().interrupts;
+        let found = [polled.update, polled.timer, polled.alarm];
+        for (count, found) in seen.iter_mut().zip(found) {
+            *count += u32::from(found);
+        }
+    }
+    // Drain the flags raced into the last poll.
+    let polled = board.rtc.poll_events().unwrap().interrupts;
+    for (count, found) in seen.iter_mut().zip([polled.update, polled.timer, polled.alarm]) {
+        *count += u32::from(found);
+    }
+    assert_eq!(seen, raised);
+    assert_eq!(flags(&board), 0);
+}
+
+#[test]
+fn event_log_records_raced_flags() {
+    let mut board = board();
+    let mut log = EventLog::<8>::new();
+    raise(&board, AF);
+    board.simulator_mut().inject_flag_race(TF);
+
+    assert_eq!(board.rtc.log_events(&mut log).unwrap(), 1);
+    assert_eq!(board.rtc.log_events(&mut log).unwrap(), 1);
+    assert_eq!(log.len(), 2);
+    assert_eq!(flags(&board), 0);
+}
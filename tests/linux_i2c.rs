@@ -0,0 +1,164 @@
+//! Hardware-in-loop tests against a real RX8900 attached to a Linux I2C bus.
+//!
+//! These tests are ignored by default. Point `RX8900_I2C_DEV` at the bus the chip is on and run
+//! them explicitly, for example on a Raspberry Pi:
+//!
+//! ```console
+//! $ RX8900_I2C_DEV=/dev/i2c-1 cargo test --target x86_64-unknown-linux-gnu --test linux_i2c -- --ignored
+//! ```
+//!
+//! The tests change the date, time and configuration of the chip.
+
+#![cfg(all(
+    target_os = "linux",
+    feature = "alarm",
+    feature = "timer",
+    feature = "temperature"
+))]
+
+use std::sync::{Mutex, MutexGuard};
+
+use chrono::Weekday;
+use heapless::Vec;
+use linux_embedded_hal::I2cdev;
+use rx8900::{
+    AlarmType, CompensationIntervalType, Flag, FoutFrequency, Rx8900, SourceClock, UpdateInterruptType,
+};
+
+mod common;
+use common::datetime;
+
+/// Serializes the tests, which all share the same chip.
+static CHIP: Mutex<()> = Mutex::new(());
+
+/// Opens the I2C device named by `RX8900_I2C_DEV` and initializes the RTC.
+fn rx8900() -> (MutexGuard<'static, ()>, Rx8900<I2cdev>) {
+    let guard = CHIP.lock().unwrap_or_else(|e| e.into_inner());
+    let path = std::env::var("RX8900_I2C_DEV").expect("RX8900_I2C_DEV is not set");
+    let i2c = I2cdev::new(&path).unwrap_or_else(|e| panic!("failed to open {path}: {e}"));
+    let mut rx8900 = Rx8900::new_unchecked(i2c);
+    rx8900.init().unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    (guard, rx8900)
+}
+
+#[test]
+#[ignore = "requires an RX8900 on RX8900_I2C_DEV"]
+fn datetime_round_trip() {
+    let (_guard, mut rx8900) = rx8900();
+    let expected = datetime(2024, 2, 29, 23, 59, 58);
+    rx8900.set_datetime(expected).unwrap();
+    let actual = rx8900.datetime().unwrap();
+    assert!(actual >= expected && (actual - expected).num_seconds() <= 1);
+    assert_eq!(rx8900.week().unwrap(), Weekday::Thu);
+
+    // Rolls over into March 1st.
+    std::thread::sleep(std::time::Duration::from_secs(3));
+    assert_eq!(rx8900.month().unwrap(), 3);
+    assert_eq!(rx8900.day().unwrap(), 1);
+}
+
+#[test]
+#[ignore = "requires an RX8900 on RX8900_I2C_DEV"]
+fn ram_round_trip() {
+    let (_guard, mut rx8900) = rx8900();
+    for value in [0x00, 0x55, 0xAA, 0xFF] {
+        rx8900.set_ram(value).unwrap();
+        assert_eq!(rx8900.ram().unwrap(), value);
+    }
+}
+
+#[test]
+#[ignore = "requires an RX8900 on RX8900_I2C_DEV"]
+fn alarm_registers_round_trip() {
+    let (_guard, mut rx8900) = rx8900();
+    rx8900.set_min_alarm(45, true).unwrap();
+    rx8900.set_hour_alarm(21, false).unwrap();
+    assert_eq!(rx8900.min_alarm().unwrap(), 45);
+    assert!(rx8900.min_alarm_enabled().unwrap());
+    assert_eq!(rx8900.hour_alarm().unwrap(), 21);
+    assert!(!rx8900.hour_alarm_enabled().unwrap());
+
+    rx8900.set_alarm_type(AlarmType::DayAlarm).unwrap();
+    rx8900.set_day_alarm(31, false).unwrap();
+    assert_eq!(rx8900.alarm_type().unwrap(), AlarmType::DayAlarm);
+    assert_eq!(rx8900.day_alarm().unwrap(), 31);
+
+    rx8900.set_alarm_type(AlarmType::WeekAlarm).unwrap();
+    let mut weekdays = Vec::<Weekday, 7>::new();
+    weekdays.push(Weekday::Mon).unwrap();
+    weekdays.push(Weekday::Fri).unwrap();
+    rx8900.set_week_alarm(&weekdays).unwrap();
+    assert_eq!(rx8900.alarm_type().unwrap(), AlarmType::WeekAlarm);
+    assert_eq!(rx8900.week_alarm().unwrap(), weekdays);
+}
+
+#[test]
+#[ignore = "requires an RX8900 on RX8900_I2C_DEV"]
+fn alarm_fires() {
+    let (_guard, mut rx8900) = rx8900();
+    rx8900.set_datetime(datetime(2024, 1, 1, 6, 59, 58)).unwrap();
+    rx8900.sleep_until(datetime(2024, 1, 1, 7, 0, 0)).unwrap();
+    assert!(!rx8900.af().unwrap());
+    std::thread::sleep(std::time::Duration::from_secs(3));
+    assert!(rx8900.af().unwrap());
+    rx8900.clear_flag(Flag::Alarm).unwrap();
+    rx8900.reset_alarm_interrupt_enable().unwrap();
+    assert!(!rx8900.af().unwrap());
+}
+
+#[test]
+#[ignore = "requires an RX8900 on RX8900_I2C_DEV"]
+fn timer_fires() {
+    let (_guard, mut rx8900) = rx8900();
+    rx8900.set_source_clock(SourceClock::SourceClock64Hz).unwrap();
+    assert_eq!(rx8900.source_clock().unwrap(), SourceClock::SourceClock64Hz);
+    rx8900.set_timer_counter(32).unwrap();
+    assert_eq!(rx8900.timer_counter().unwrap(), 32);
+    rx8900.clear_flag(Flag::Timer).unwrap();
+    rx8900.set_timer_enable().unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(600));
+    rx8900.reset_timer_enable().unwrap();
+    assert!(rx8900.tf().unwrap());
+    rx8900.clear_flag(Flag::Timer).unwrap();
+    assert!(!rx8900.tf().unwrap());
+}
+
+#[test]
+#[ignore = "requires an RX8900 on RX8900_I2C_DEV"]
+fn configuration_round_trip() {
+    let (_guard, mut rx8900) = rx8900();
+    for frequency in [
+        FoutFrequency::FoutFrequency1Hz,
+        FoutFrequency::FoutFrequency1024Hz,
+        FoutFrequency::FoutFrequency32_768kHz,
+    ] {
+        rx8900.set_fout_frequency(frequency).unwrap();
+        assert_eq!(rx8900.fout_frequency().unwrap(), frequency);
+    }
+    for interval in [
+        CompensationIntervalType::CompensationInterval0_5s,
+        CompensationIntervalType::CompensationInterval10s,
+        CompensationIntervalType::CompensationInterval30s,
+        CompensationIntervalType::CompensationInterval2_0s,
+    ] {
+        rx8900.set_compensation_interval_type(interval).unwrap();
+        assert_eq!(rx8900.compensation_interval_type().unwrap(), interval);
+    }
+    rx8900.set_update_interrupt_type(UpdateInterruptType::EveryMinute).unwrap();
+    assert_eq!(rx8900.update_interrupt_type().unwrap(), UpdateInterruptType::EveryMinute);
+    rx8900.set_update_interrupt_type(UpdateInterruptType::EverySecond).unwrap();
+    assert_eq!(rx8900.update_interrupt_type().unwrap(), UpdateInterruptType::EverySecond);
+    rx8900.set_backup_mode_sampling_time(0b10).unwrap();
+    assert_eq!(rx8900.backup_mode_sampling_time().unwrap(), 0b10);
+    assert!(rx8900.switch_off().unwrap());
+    assert!(!rx8900.voltage_detector_off().unwrap());
+}
+
+#[test]
+#[ignore = "requires an RX8900 on RX8900_I2C_DEV"]
+fn temperature_is_plausible() {
+    let (_guard, mut rx8900) = rx8900();
+    let millicelsius = rx8900.temp_in_millicelsius().unwrap();
+    assert!((-40_000..=85_000).contains(&millicelsius), "{millicelsius} mdegC");
+}
@@ -0,0 +1,83 @@
+//! The alarm- and timer-driven `Logger`, scripted against the simulator.
+//!
+//! ```console
+//! $ cargo test --target x86_64-unknown-linux-gnu --features testsupport --test logger
+//! ```
+
+#![cfg(all(feature = "testsupport", feature = "alarm", feature = "timer"))]
+
+use std::cell::RefCell;
+
+use chrono::Duration;
+use rx8900::{Error, Logger};
+
+mod common;
+use common::{AF, CONTROL, FLAGS, TF, board_at, datetime};
+
+const AIE: u8 = 0b00001000;
+
+#[test]
+fn alarm_logger_samples_at_the_start_of_each_period() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 7, 30));
+    let samples = RefCell::new(Vec::new());
+    let mut logger = Logger::start(&mut board.rtc, Duration::minutes(15), |at| samples.borrow_mut().push(at)).unwrap();
+    assert_eq!(logger.next_sample(), datetime(2024, 5, 1, 12, 22, 0));
+
+    // Nothing is due before the alarm.
+    board.simulator_mut().advance(Duration::minutes(10));
+    assert!(!logger.poll(&mut board.rtc).unwrap());
+
+    board.simulator_mut().advance(Duration::minutes(5));
+    assert!(logger.poll(&mut board.rtc).unwrap());
+    assert_eq!(logger.next_sample(), datetime(2024, 5, 1, 12, 37, 0));
+    assert_eq!(board.simulator().register(FLAGS) & AF, 0);
+
+    board.simulator_mut().advance(Duration::minutes(15));
+    assert!(logger.poll(&mut board.rtc).unwrap());
+    assert_eq!(*samples.borrow(), [datetime(2024, 5, 1, 12, 22, 0), datetime(2024, 5, 1, 12, 37, 0)]);
+}
+
+#[test]
+fn alarm_logger_skips_the_samples_missed_while_busy() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 7, 30));
+    let samples = RefCell::new(Vec::new());
+    let mut logger = Logger::start(&mut board.rtc, Duration::minutes(15), |at| samples.borrow_mut().push(at)).unwrap();
+
+    // The MCU only gets to the interrupt 40 minutes later.
+    board.simulator_mut().advance(Duration::minutes(55));
+    assert!(logger.poll(&mut board.rtc).unwrap());
+    assert_eq!(*samples.borrow(), [datetime(2024, 5, 1, 12, 22, 0)]);
+    assert_eq!(logger.next_sample(), datetime(2024, 5, 1, 13, 7, 0));
+}
+
+#[test]
+fn timer_logger_samples_on_each_timer_flag() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    let samples = RefCell::new(Vec::new());
+    let mut logger = Logger::start(&mut board.rtc, Duration::seconds(10), |at| samples.borrow_mut().push(at)).unwrap();
+    assert!(!logger.poll(&mut board.rtc).unwrap());
+
+    for _ in 0..2 {
+        board.simulator_mut().set_register(FLAGS, TF | AF);
+        assert!(logger.poll(&mut board.rtc).unwrap());
+        // Only TF is cleared; the alarm flag belongs to someone else.
+        assert_eq!(board.simulator().register(FLAGS), AF);
+    }
+    assert_eq!(*samples.borrow(), [datetime(2024, 5, 1, 12, 0, 10), datetime(2024, 5, 1, 12, 0, 20)]);
+    assert_eq!(logger.next_sample(), datetime(2024, 5, 1, 12, 0, 30));
+}
+
+#[test]
+fn stop_disarms_the_alarm() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 7, 30));
+    let logger = Logger::start(&mut board.rtc, Duration::minutes(1), |_| {}).unwrap();
+    assert_ne!(board.simulator().register(CONTROL) & AIE, 0);
+    let _sample = logger.stop(&mut board.rtc).unwrap();
+    assert_eq!(board.simulator().register(CONTROL) & AIE, 0);
+}
+
+#[test]
+fn periods_the_alarm_cannot_tell_apart_are_rejected() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    assert!(matches!(Logger::start(&mut board.rtc, Duration::days(29), |_| {}), Err(Error::InvalidInput)));
+}
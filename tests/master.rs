@@ -0,0 +1,116 @@
+//! The ownership marker shared by several bus masters, checked on every write of the user RAM register.
+//!
+//! ```console
+//! $ cargo test --target x86_64-unknown-linux-gnu --features testsupport --test master
+//! ```
+
+#![cfg(feature = "testsupport")]
+
+use std::cell::RefCell;
+use std::convert::Infallible;
+use std::rc::Rc;
+
+use embedded_hal::blocking::delay::DelayMs;
+use rx8900::sim::Simulator;
+use rx8900::{Error, Flag, Role, Rx8900, Transport};
+
+const RAM: u8 = 0x07;
+const OWNER_MASK: u8 = 0b11000000;
+
+/// The simulator, recording every value written to the user RAM register.
+struct RamWatch {
+    simulator: Simulator,
+    ram_writes: Rc<RefCell<Vec<u8>>>,
+}
+
+impl Transport for RamWatch {
+    type Error = Infallible;
+
+    fn read_registers(&mut self, register: u8, data: &mut [u8]) -> Result<(), Infallible> {
+        self.simulator.read_registers(register, data)
+    }
+
+    fn write_registers(&mut self, register: u8, data: &[u8]) -> Result<(), Infallible> {
+        if let Some(&value) = RAM.checked_sub(register).and_then(|index| data.get(usize::from(index))) {
+            self.ram_writes.borrow_mut().push(value);
+        }
+        self.simulator.write_registers(register, data)
+    }
+}
+
+struct NoDelay;
+
+impl DelayMs<u32> for NoDelay {
+    fn delay_ms(&mut self, _ms: u32) {}
+}
+
+/// Creates a driver that claimed the chip as master 2, and the log of the user RAM writes that follow.
+fn claimed() -> (Rx8900<RamWatch>, Rc<RefCell<Vec<u8>>>) {
+    let ram_writes = Rc::new(RefCell::new(Vec::new()));
+    let mut rx8900 = Rx8900::new_unchecked(RamWatch { simulator: Simulator::new(), ram_writes: ram_writes.clone() });
+    assert!(rx8900.claim(2).unwrap());
+    rx8900.set_ram(0x15).unwrap();
+    ram_writes.borrow_mut().clear();
+    (rx8900, ram_writes)
+}
+
+/// Checks that every recorded write kept the marker of master 2.
+fn assert_marker_kept(ram_writes: &RefCell<Vec<u8>>) {
+    let writes = ram_writes.borrow();
+    assert!(!writes.is_empty());
+    assert!(writes.iter().all(|value| value & OWNER_MASK == 2 << 6), "{writes:02X?}");
+}
+
+#[test]
+fn set_ram_keeps_the_marker_once_claimed() {
+    let (mut rx8900, ram_writes) = claimed();
+    rx8900.set_ram(0xFF).unwrap();
+    assert_eq!(rx8900.ram().unwrap(), 2 << 6 | 0x3F);
+    assert_eq!(rx8900.owner().unwrap(), Some(2));
+    assert_marker_kept(&ram_writes);
+}
+
+#[test]
+fn probe_keeps_the_marker_while_testing_the_ram() {
+    let (mut rx8900, ram_writes) = claimed();
+    assert!(rx8900.probe().unwrap());
+    assert_eq!(rx8900.ram().unwrap(), 2 << 6 | 0x15);
+    assert_marker_kept(&ram_writes);
+}
+
+#[test]
+fn stress_test_keeps_the_marker_while_cycling_patterns() {
+    let (mut rx8900, ram_writes) = claimed();
+    let report = rx8900.stress_test(24, &mut NoDelay).unwrap();
+    assert!(report.passed());
+    assert_eq!(rx8900.ram().unwrap(), 2 << 6 | 0x15);
+    assert_marker_kept(&ram_writes);
+}
+
+#[test]
+fn observers_cannot_write_as_owner() {
+    let (mut rx8900, _) = claimed();
+    rx8900.observe_only();
+    assert_eq!(rx8900.role(), Role::Observer);
+    assert!(matches!(rx8900.as_owner(|rx8900| rx8900.set_ram(0)), Err(Error::NotOwner)));
+}
+
+#[test]
+fn observers_cannot_write_the_chip_at_all() {
+    let (mut rx8900, ram_writes) = claimed();
+    rx8900.observe_only();
+    let writes = ram_writes.borrow().len();
+    let now = chrono::NaiveDate::from_ymd_opt(2024, 5, 1).unwrap().and_hms_opt(12, 0, 0).unwrap();
+    assert!(matches!(rx8900.set_datetime(now), Err(Error::NotOwner)));
+    assert!(matches!(rx8900.set_ram(0), Err(Error::NotOwner)));
+    assert!(matches!(rx8900.clear_flag(Flag::Update), Err(Error::NotOwner)));
+    assert_eq!(ram_writes.borrow().len(), writes);
+    assert_ne!(rx8900.datetime().unwrap(), now);
+}
+
+#[test]
+fn without_a_role_the_whole_ram_is_free() {
+    let mut rx8900 = Rx8900::new_unchecked(Simulator::new());
+    rx8900.set_ram(0xFF).unwrap();
+    assert_eq!(rx8900.ram().unwrap(), 0xFF);
+}
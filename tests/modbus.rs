@@ -0,0 +1,77 @@
+//! The Modbus holding register bank, scripted against the simulator.
+//!
+//! ```console
+//! $ cargo test --target x86_64-unknown-linux-gnu --features testsupport,modbus --test modbus
+//! ```
+
+#![cfg(all(feature = "testsupport", feature = "modbus"))]
+
+use rx8900::modbus::{self, REGISTER_COUNT};
+use rx8900::Error;
+
+mod common;
+use common::{AF, FLAGS, UF, board_at, datetime};
+
+const ALARM_MINUTE: u8 = 0x08;
+const ALARM_HOUR: u8 = 0x09;
+const TEMP: u8 = 0x17;
+
+#[test]
+fn date_time_and_temperature_are_read_as_one_bank() {
+    let mut board = board_at(datetime(2024, 2, 29, 23, 59, 58));
+    board.simulator_mut().set_register(TEMP, 128);
+    let mut values = [0; modbus::FLAGS as usize];
+    board.rtc.read_holding_registers(modbus::YEAR, &mut values).unwrap();
+    // 2024-02-29 is a Thursday; TEMP=128 is 21.382 °C.
+    assert_eq!(values, [2024, 2, 29, 23, 59, 58, 4, 2138]);
+}
+
+#[test]
+fn partial_date_time_writes_keep_the_other_fields() {
+    let mut board = board_at(datetime(2024, 2, 29, 23, 59, 58));
+    board.rtc.write_holding_registers(modbus::HOUR, &[12, 34, 56]).unwrap();
+    assert_eq!(board.rtc.datetime().unwrap(), datetime(2024, 2, 29, 12, 34, 56));
+
+    // Merged with the current date, the month would not have this day.
+    assert!(matches!(board.rtc.write_holding_registers(modbus::MONTH, &[4, 31]), Err(Error::InvalidInput)));
+    assert!(matches!(board.rtc.write_holding_registers(modbus::YEAR, &[2100]), Err(Error::InvalidInput)));
+    assert_eq!(board.rtc.datetime().unwrap(), datetime(2024, 2, 29, 12, 34, 56));
+}
+
+#[test]
+fn writing_0_to_a_flag_clears_it() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    board.simulator_mut().set_register(FLAGS, UF | AF);
+    let mut flags = [0];
+    board.rtc.read_holding_registers(modbus::FLAGS, &mut flags).unwrap();
+    assert_eq!(flags, [u16::from(UF | AF)]);
+
+    board.rtc.write_holding_registers(modbus::FLAGS, &[u16::from(UF)]).unwrap();
+    assert_eq!(board.simulator().register(FLAGS), UF);
+}
+
+#[test]
+fn alarm_registers_map_to_the_alarm_objects() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    board.rtc.write_holding_registers(modbus::ALARM_MINUTE, &[30, 7, 0xFF, 1]).unwrap();
+    assert_eq!(board.simulator().register(ALARM_MINUTE), 0x30);
+    assert_eq!(board.simulator().register(ALARM_HOUR), 0x07);
+
+    let mut values = [0; 4];
+    board.rtc.read_holding_registers(modbus::ALARM_MINUTE, &mut values).unwrap();
+    assert_eq!(values, [30, 7, 0xFF, 1]);
+
+    assert!(matches!(board.rtc.write_holding_registers(modbus::ALARM_HOUR, &[24]), Err(Error::InvalidInput)));
+    assert_eq!(board.simulator().register(ALARM_HOUR), 0x07);
+}
+
+#[test]
+fn read_only_and_out_of_range_registers_are_rejected() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    assert!(matches!(board.rtc.write_holding_registers(modbus::WEEKDAY, &[1]), Err(Error::InvalidInput)));
+    assert!(matches!(board.rtc.write_holding_registers(modbus::SECOND, &[0, 1]), Err(Error::InvalidInput)));
+    assert!(matches!(board.rtc.read_holding_registers(REGISTER_COUNT, &mut [0]), Err(Error::InvalidInput)));
+    assert!(matches!(board.rtc.read_holding_registers(modbus::ALARM_ENABLE, &mut [0, 0]), Err(Error::InvalidInput)));
+    // The rejected writes changed nothing.
+    assert_eq!(board.rtc.datetime().unwrap(), datetime(2024, 5, 1, 12, 0, 0));
+}
@@ -0,0 +1,74 @@
+//! Checks that the default build of the driver contains no floating point code.
+//!
+//! The test builds `ci/no-float`, which instantiates the driver API for a Cortex-M0, and looks for soft-float
+//! routines among the symbols the driver's objects link. It needs the `thumbv6m-none-eabi` target and GNU `nm`,
+//! so it is ignored by default:
+//!
+//! ```console
+//! $ rustup target add thumbv6m-none-eabi
+//! $ cargo test --target x86_64-unknown-linux-gnu --test no_float -- --ignored
+//! ```
+
+use std::path::Path;
+use std::process::Command;
+
+/// The Cortex-M0 target, which has no FPU, so every f32/f64 operation becomes a call into a soft-float routine.
+const TARGET: &str = "thumbv6m-none-eabi";
+
+/// Returns whether a symbol is a soft-float routine of compiler-builtins, such as `__aeabi_fadd` or `__divsf3`.
+fn is_soft_float(symbol: &str) -> bool {
+    if symbol.starts_with("__aeabi_f") || symbol.starts_with("__aeabi_d") || symbol.starts_with("__aeabi_i2")
+        || symbol.starts_with("__aeabi_ui2") || symbol.starts_with("__aeabi_l2") || symbol.starts_with("__aeabi_ul2")
+    {
+        return true;
+    }
+    let Some(name) = symbol.strip_prefix("__") else { return false };
+    let name = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    ["add", "sub", "mul", "div", "fix", "float", "extend", "trunc", "neg", "cmp", "eq", "ne", "lt", "le", "gt", "ge"]
+        .iter()
+        .any(|op| name.strip_prefix(op).is_some_and(|rest| rest.contains("sf") || rest.contains("df")))
+}
+
+#[test]
+#[ignore = "needs the thumbv6m-none-eabi target and GNU nm"]
+fn default_build_has_no_soft_float_routines() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("ci/no-float");
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("no-float");
+    let status = Command::new(env!("CARGO"))
+        .args(["build", "--release", "--target", TARGET, "--target-dir"])
+        .arg(&target_dir)
+        .current_dir(&crate_dir)
+        .status()
+        .expect("failed to run cargo");
+    assert!(status.success(), "building ci/no-float for {TARGET} failed");
+
+    let library = target_dir.join(TARGET).join("release/librx8900_no_float.a");
+    let output = Command::new("nm").arg("-A").arg("--undefined-only").arg(&library).output().expect("failed to run nm");
+    assert!(output.status.success(), "nm failed: {}", String::from_utf8_lossy(&output.stderr));
+
+    // Only the objects of the driver and the check crate matter; `core` carries its own float code.
+    let offending: Vec<_> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, ':');
+            let (_, member, symbol) = (fields.next()?, fields.next()?, fields.next()?);
+            let symbol = symbol.split_whitespace().last()?;
+            (member.contains("rx8900") && is_soft_float(symbol)).then(|| format!("{member}: {symbol}"))
+        })
+        .collect();
+    assert!(offending.is_empty(), "the default build links floating point code:\n{}", offending.join("\n"));
+}
+
+#[test]
+fn soft_float_routines_are_recognized() {
+    for symbol in ["__aeabi_fadd", "__aeabi_dmul", "__aeabi_i2f", "__aeabi_ui2d", "__divsf3", "__floatsisf",
+        "__fixunsdfsi", "__extendsfdf2", "__truncdfsf2", "__eqsf2"]
+    {
+        assert!(is_soft_float(symbol), "{symbol}");
+    }
+    for symbol in ["__aeabi_uidiv", "__aeabi_idivmod", "__aeabi_lmul", "__aeabi_memcpy", "__udivsi3", "__divdi3",
+        "memcpy"]
+    {
+        assert!(!is_soft_float(symbol), "{symbol}");
+    }
+}
@@ -0,0 +1,74 @@
+//! The `u32` object dictionary, read and written against the simulator.
+//!
+//! ```console
+//! $ cargo test --target x86_64-unknown-linux-gnu --features testsupport,objects --test objects
+//! ```
+
+#![cfg(all(feature = "testsupport", feature = "objects", feature = "alarm"))]
+
+use rx8900::{Error, RtcObject};
+
+mod common;
+use common::{AF, EXTENSION, FLAGS, VLF, board_at, datetime};
+
+const RAM: u8 = 0x07;
+const ALARM_MINUTE: u8 = 0x08;
+
+#[test]
+fn keys_are_canopen_index_and_subindex() {
+    assert_eq!(RtcObject::AlarmHour.key(), 0x200102);
+    assert_eq!((RtcObject::AlarmHour.index(), RtcObject::AlarmHour.subindex()), (0x2001, 0x02));
+    assert_eq!(RtcObject::from_key(0x200301), Some(RtcObject::Ram));
+    assert_eq!(RtcObject::from_key(0x200300), None);
+    assert!(RtcObject::Ram.is_writable());
+    assert!(!RtcObject::Flags.is_writable());
+}
+
+#[test]
+fn timestamp_object_reads_and_sets_the_clock() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    assert_eq!(board.rtc.read_object(RtcObject::Timestamp).unwrap(), 1_714_564_800);
+
+    board.rtc.write_object(RtcObject::Timestamp, 1_735_689_599).unwrap();
+    assert_eq!(board.rtc.datetime().unwrap(), datetime(2024, 12, 31, 23, 59, 59));
+}
+
+#[test]
+fn register_objects_map_to_their_registers() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+
+    board.rtc.write_object(RtcObject::Ram, 0xA5).unwrap();
+    assert_eq!(board.simulator().register(RAM), 0xA5);
+    assert_eq!(board.rtc.read_object(RtcObject::Ram).unwrap(), 0xA5);
+
+    board.rtc.write_object(RtcObject::AlarmMinute, 30).unwrap();
+    assert_eq!(board.simulator().register(ALARM_MINUTE), 0x30);
+    assert_eq!(board.rtc.read_object(RtcObject::AlarmMinute).unwrap(), 30);
+    // 0xFF sets AE, so the alarm matches any minute.
+    board.rtc.write_object(RtcObject::AlarmMinute, 0xFF).unwrap();
+    assert_eq!(board.simulator().register(ALARM_MINUTE), 0x80);
+    assert_eq!(board.rtc.read_object(RtcObject::AlarmMinute).unwrap(), 0xFF);
+
+    // FSEL (bits 3-2)
+    board.rtc.write_object(RtcObject::FoutFrequency, 2).unwrap();
+    assert_eq!(board.simulator().register(EXTENSION) & 0b00001100, 0b00001000);
+    assert_eq!(board.rtc.read_object(RtcObject::FoutFrequency).unwrap(), 2);
+
+    board.simulator_mut().set_register(FLAGS, AF | VLF);
+    assert_eq!(board.rtc.read_object(RtcObject::Flags).unwrap(), u32::from(AF | VLF));
+    assert_eq!(board.rtc.read_object(RtcObject::TimeValid).unwrap(), 0);
+}
+
+#[test]
+fn invalid_writes_leave_the_registers_untouched() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    board.rtc.write_object(RtcObject::AlarmMinute, 30).unwrap();
+    let before = board.simulator().transactions();
+
+    assert!(matches!(board.rtc.write_object(RtcObject::AlarmMinute, 60), Err(Error::InvalidInput)));
+    assert!(matches!(board.rtc.write_object(RtcObject::Ram, 0x100), Err(Error::InvalidInput)));
+    assert!(matches!(board.rtc.write_object(RtcObject::TimeValid, 1), Err(Error::InvalidInput)));
+    assert!(matches!(board.rtc.write_object(RtcObject::Flags, 0), Err(Error::InvalidInput)));
+    assert_eq!(board.simulator().transactions(), before);
+    assert_eq!(board.simulator().register(ALARM_MINUTE), 0x30);
+}
@@ -0,0 +1,151 @@
+//! Periodic sampling on the wake-up timer, scripted against the simulator: the jitter statistics of `every` and
+//! the re-arming and drift accounting of `PeriodicTimer`.
+//!
+//! ```console
+//! $ cargo test --target x86_64-unknown-linux-gnu --features testsupport --test periodic
+//! ```
+
+#![cfg(all(feature = "testsupport", feature = "timer"))]
+
+use std::cell::RefCell;
+use std::convert::Infallible;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use chrono::Duration;
+use embedded_hal::blocking::delay::DelayMs;
+use rx8900::sim::Simulator;
+use rx8900::{Error, JitterStats, PeriodicStats, PeriodicTimer, Rx8900, SourceClock, Transport};
+
+mod common;
+use common::{FLAGS, TF, board_at, datetime, expire_at};
+
+/// A simulator shared between the driver and the test, so the test can raise TF while an `Every` borrows the
+/// driver.
+#[derive(Clone)]
+struct Shared(Rc<RefCell<Simulator>>);
+
+impl Transport for Shared {
+    type Error = Infallible;
+
+    fn read_registers(&mut self, register: u8, data: &mut [u8]) -> Result<(), Infallible> {
+        self.0.borrow_mut().read_registers(register, data)
+    }
+
+    fn write_registers(&mut self, register: u8, data: &[u8]) -> Result<(), Infallible> {
+        self.0.borrow_mut().write_registers(register, data)
+    }
+}
+
+/// A delay failing the test if the flag is ever polled twice: every expiry is raised before the iterator waits.
+struct NoWait;
+
+impl DelayMs<u32> for NoWait {
+    fn delay_ms(&mut self, _ms: u32) {
+        panic!("the timer flag was not set");
+    }
+}
+
+/// The microsecond clock of the MCU handed to `with_jitter_clock`.
+static CLOCK_US: AtomicU32 = AtomicU32::new(0);
+
+fn clock() -> u32 {
+    CLOCK_US.load(Ordering::Relaxed)
+}
+
+#[test]
+fn every_measures_the_jitter_of_the_expiries() {
+    let shared = Shared(Rc::new(RefCell::new(Simulator::new())));
+    let mut rx8900 = Rx8900::new_unchecked(shared.clone());
+    rx8900.set_datetime(datetime(2024, 5, 1, 12, 0, 0)).unwrap();
+    let mut delay = NoWait;
+    let mut every = rx8900.every(Duration::seconds(1), &mut delay).unwrap().with_jitter_clock(clock);
+
+    // Expiries seen 200 µs late, then 100 µs early, across the wrapping of the counter.
+    for instant in [u32::MAX - 999_999, 200, 1_000_100] {
+        CLOCK_US.store(instant, Ordering::Relaxed);
+        shared.0.borrow_mut().set_register(FLAGS, TF);
+        assert!(every.next().unwrap().is_ok());
+        assert_eq!(shared.0.borrow().register(FLAGS) & TF, 0);
+    }
+    assert_eq!(every.last_instant(), Some(1_000_100));
+    assert_eq!(every.jitter(), JitterStats { count: 2, max: 200, average: 150 });
+
+    every.reset_jitter();
+    assert_eq!(every.jitter(), JitterStats { count: 0, max: 0, average: 0 });
+}
+
+#[test]
+fn hardware_periods_count_the_missed_ones() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    let mut timer = PeriodicTimer::new(Duration::seconds(10)).unwrap();
+    timer.start(&mut board.rtc).unwrap();
+    assert_eq!(timer.next_due(), Some(datetime(2024, 5, 1, 12, 0, 10)));
+    assert!(!timer.on_timer_interrupt(&mut board.rtc).unwrap());
+
+    expire_at(&board, datetime(2024, 5, 1, 12, 0, 10));
+    assert!(timer.on_timer_interrupt(&mut board.rtc).unwrap());
+    assert_eq!(board.simulator().register(FLAGS) & TF, 0);
+
+    // Handled 35 s late: three expiries were missed, and the phase is kept.
+    expire_at(&board, datetime(2024, 5, 1, 12, 0, 55));
+    assert!(timer.on_timer_interrupt(&mut board.rtc).unwrap());
+    assert_eq!(timer.next_due(), Some(datetime(2024, 5, 1, 12, 1, 0)));
+    assert_eq!(timer.stats(), PeriodicStats { periods: 2, missed: 3, rearms: 0, residual: 35, max_residual: 35 });
+}
+
+#[test]
+fn long_periods_are_rearmed_towards_their_nominal_end() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    // 2 hours and 30 seconds cannot be counted down by the 12-bit counter in one go.
+    let mut timer = PeriodicTimer::new(Duration::seconds(2 * 3600 + 30)).unwrap();
+    timer.start(&mut board.rtc).unwrap();
+    assert_eq!(timer.next_due(), Some(datetime(2024, 5, 1, 14, 0, 30)));
+    assert_eq!(board.rtc.source_clock().unwrap(), SourceClock::SourceClockMinute);
+    assert_eq!(board.rtc.timer_counter().unwrap(), 119);
+
+    // The minute chunk ends 42 s short, the interrupt being handled with some latency.
+    expire_at(&board, datetime(2024, 5, 1, 13, 59, 48));
+    assert!(!timer.on_timer_interrupt(&mut board.rtc).unwrap());
+    assert_eq!(board.rtc.source_clock().unwrap(), SourceClock::SourceClockSecond);
+    assert_eq!(board.rtc.timer_counter().unwrap(), 42);
+
+    // Handled 2 s late; the next period is still counted from the nominal end of this one.
+    expire_at(&board, datetime(2024, 5, 1, 14, 0, 32));
+    assert!(timer.on_timer_interrupt(&mut board.rtc).unwrap());
+    assert_eq!(timer.next_due(), Some(datetime(2024, 5, 1, 16, 1, 0)));
+    assert_eq!(board.rtc.timer_counter().unwrap(), 119);
+    assert_eq!(timer.stats(), PeriodicStats { periods: 1, missed: 0, rearms: 1, residual: 2, max_residual: 2 });
+}
+
+#[test]
+fn interrupts_whose_time_cannot_be_read_stay_pending() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    let mut timer = PeriodicTimer::new(Duration::seconds(10)).unwrap();
+    timer.start(&mut board.rtc).unwrap();
+    expire_at(&board, datetime(2024, 5, 1, 12, 0, 10));
+    board.simulator_mut().set_register(0x00, 0x7A);
+    assert!(matches!(timer.on_timer_interrupt(&mut board.rtc), Err(Error::InvalidTime(_))));
+    assert_eq!(board.simulator().register(FLAGS) & TF, TF);
+    assert_eq!(timer.stats(), PeriodicStats::default());
+}
+
+#[test]
+fn periods_ending_past_the_calendar_are_rejected() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    let mut timer = PeriodicTimer::new(Duration::days(365 * 300_000)).unwrap();
+    assert!(matches!(timer.start(&mut board.rtc), Err(Error::InvalidInput)));
+    assert!(matches!(timer.on_timer_interrupt(&mut board.rtc), Err(Error::InvalidInput)));
+}
+
+#[test]
+fn the_longest_periods_pass_the_bus_load_check() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    // chrono caps durations at i64::MAX milliseconds.
+    let longest = Duration::seconds(i64::MAX / 1000);
+    let mut timer = PeriodicTimer::new(longest).unwrap();
+    assert!(matches!(timer.start(&mut board.rtc), Err(Error::InvalidInput)));
+
+    let mut timer = PeriodicTimer::new(Duration::days(365 * 10_000)).unwrap();
+    timer.start(&mut board.rtc).unwrap();
+}
@@ -0,0 +1,345 @@
+//! Power-sequence scenarios scripted against the simulator: backup battery removal and reinsertion, main supply
+//! loss with the battery in place, and brown-outs of the backup supply.
+//!
+//! ```console
+//! $ cargo test --target x86_64-unknown-linux-gnu --features testsupport --test power
+//! ```
+
+#![cfg(all(feature = "testsupport", feature = "alarm", feature = "timer"))]
+
+use chrono::{Duration, NaiveDateTime};
+use rx8900::sim::{Faulty, Nack, Simulator};
+use rx8900::testsupport::FakeBoard;
+use rx8900::{
+    BackupPowerProfile, CompensationIntervalType, Config, DecodeMode, Error, FoutFrequency, InitConfig, Initialized,
+    Rx8900, StateBlob, UpdateInterruptType,
+};
+
+mod common;
+use common::{CONTROL, FLAGS, VDET, VLF, datetime};
+
+/// Brings a board up as the firmware does on its first boot, with the clock set and the flags cleared.
+fn provisioned(now: NaiveDateTime) -> FakeBoard {
+    let mut board = FakeBoard::new();
+    board.rtc.init().unwrap();
+    board.rtc.set_datetime(now).unwrap();
+    board
+}
+
+/// Removes and reinserts the backup battery while the main supply is off: the chip starts over from its power-on
+/// reset state.
+fn replace_battery(board: &FakeBoard) {
+    *board.simulator_mut() = Simulator::new();
+}
+
+#[test]
+fn battery_replacement_is_detected_and_the_clock_reprogrammed() {
+    let mut board = provisioned(datetime(2024, 5, 1, 12, 0, 0));
+    assert!(!board.rtc.voltage_low_flag().unwrap());
+
+    replace_battery(&board);
+    assert!(board.rtc.voltage_low_flag().unwrap());
+    assert_eq!(board.rtc.datetime().unwrap(), datetime(2000, 1, 1, 0, 0, 0));
+    assert!(matches!(board.rtc.require_at_least(datetime(2024, 1, 1, 0, 0, 0)), Err(Error::ClockTooEarly)));
+
+    // The firmware boots again and restores the time from another source.
+    let restored = datetime(2024, 5, 1, 12, 5, 0);
+    assert!(board.rtc.set_if_invalid(restored).unwrap());
+    assert!(!board.rtc.voltage_low_flag().unwrap());
+    assert_eq!(board.rtc.datetime().unwrap(), restored);
+
+    // Later boots keep the running clock.
+    board.simulator_mut().advance(Duration::minutes(1));
+    assert!(!board.rtc.set_if_invalid(datetime(2000, 1, 1, 0, 0, 0)).unwrap());
+    assert_eq!(board.rtc.datetime().unwrap(), datetime(2024, 5, 1, 12, 6, 0));
+}
+
+#[test]
+fn battery_loss_with_corrupt_registers_reports_the_raw_time() {
+    // A discharged battery can leave arbitrary contents behind, with VLF set.
+    let garbage = [0x7A, 0x6B, 0x3F, 0x00, 0x00, 0x1D, 0xA5, 0, 0, 0, 0, 0, 0, 0, VLF, 0];
+    let mut board = FakeBoard::with_simulator(Simulator::from_registers(&garbage));
+
+    match board.rtc.datetime() {
+        Err(Error::InvalidTime(raw)) => assert_eq!(raw, [0x7A, 0x6B, 0x3F, 0x00, 0x00, 0x1D, 0xA5]),
+        other => panic!("expected InvalidTime, got {other:?}"),
+    }
+    assert!(matches!(board.rtc.datetime_decoded(), Err(Error::InvalidTime(raw)) if raw == garbage[..7]));
+    assert!(matches!(board.rtc.ordinal(), Err(Error::InvalidTime([0, 0, 0, 0, 0x00, 0x1D, 0xA5]))));
+    board.rtc.set_decode_mode(DecodeMode::Lenient);
+    assert!(board.rtc.datetime_decoded().unwrap().clamped);
+    // The plain readers follow the decode mode too, without telling that the fields were clamped.
+    let clamped = datetime(2095, 12, 1, 23, 59, 59);
+    assert_eq!(board.rtc.datetime().unwrap(), clamped);
+    assert_eq!(board.rtc.datetime_coherent().unwrap(), clamped);
+    assert_eq!(board.rtc.datetime_fast().unwrap(), clamped.time());
+    assert_eq!(board.rtc.ordinal().unwrap(), 335);
+    assert!(board.rtc.is_today(clamped.date()).unwrap());
+
+    let restored = datetime(2024, 5, 1, 12, 0, 0);
+    assert!(board.rtc.set_if_invalid(restored).unwrap());
+    assert_eq!(board.rtc.datetime().unwrap(), restored);
+}
+
+#[test]
+fn main_supply_loss_keeps_the_time_on_the_battery() {
+    let board = provisioned(datetime(2024, 5, 1, 12, 0, 0));
+
+    // The MCU is off for a day while the chip runs on its backup battery; then the firmware starts a fresh driver.
+    let mut chip = board.simulator().clone();
+    drop(board);
+    chip.advance(Duration::days(1));
+    let mut board = FakeBoard::with_simulator(chip);
+    assert!(!board.rtc.voltage_low_flag().unwrap());
+    assert!(!board.rtc.set_if_invalid(datetime(2000, 1, 1, 0, 0, 0)).unwrap());
+    assert_eq!(board.rtc.datetime().unwrap(), datetime(2024, 5, 2, 12, 0, 0));
+}
+
+#[test]
+fn init_on_cold_start_clears_the_supply_flags_and_interrupts() {
+    let mut board = FakeBoard::new();
+    board.simulator_mut().set_register(FLAGS, VLF | VDET);
+    // AIE, TIE and UIE left set by a previous firmware.
+    board.simulator_mut().set_register(CONTROL, 0b01111000);
+
+    board.rtc.init().unwrap();
+    assert!(!board.rtc.voltage_low_flag().unwrap());
+    assert!(!board.rtc.voltage_detect_flag().unwrap());
+    let config = board.rtc.config().unwrap();
+    assert!(!config.alarm_interrupt_enable && !config.timer_interrupt_enable && !config.update_interrupt_enable);
+    assert!(!config.voltage_detector_off && config.switch_off);
+}
+
+#[test]
+fn brown_out_repairs_the_configuration() {
+    let mut board = provisioned(datetime(2024, 5, 1, 12, 0, 0));
+    let config = Config::default()
+        .fout(FoutFrequency::FoutFrequency1Hz)
+        .update_interrupt(UpdateInterruptType::EveryMinute)
+        .compensation(CompensationIntervalType::CompensationInterval30s);
+    board.rtc.apply_config(&config).unwrap();
+    board.rtc.set_reapply_on_vdet(Some(config)).unwrap();
+
+    // The backup supply sags: VDET is raised and the control register loses its compensation interval.
+    board.simulator_mut().advance(Duration::seconds(30));
+    let control = board.simulator().register(CONTROL);
+    board.simulator_mut().set_register(CONTROL, control & 0b00111111);
+    let flags = board.simulator().register(FLAGS);
+    board.simulator_mut().set_register(FLAGS, flags | VDET);
+
+    let polled = board.rtc.poll_events().unwrap();
+    assert!(polled.voltage_detect);
+    assert_eq!(polled.repaired.control, 0b11000000);
+    assert_eq!(board.rtc.config().unwrap(), config);
+    assert!(!board.rtc.voltage_detect_flag().unwrap());
+
+    // The time survived the brown-out, and a second poll has nothing left to repair.
+    assert_eq!(board.rtc.datetime().unwrap(), datetime(2024, 5, 1, 12, 0, 30));
+    assert!(!board.rtc.poll_events().unwrap().voltage_detect);
+}
+
+#[test]
+fn typestate_init_requires_setting_a_lost_time() {
+    let board = FakeBoard::new();
+    let bus = board.rtc.release();
+    // A cold start: VLF is set, so the time cannot be trusted until it is set.
+    let Ok(Initialized::TimeInvalid(rtc)) = Rx8900::uninitialized(bus).init() else {
+        panic!("a lost time was reported as valid");
+    };
+    let Err((rtc, Error::InvalidInput)) = rtc.set_datetime(datetime(2100, 1, 1, 0, 0, 0)) else {
+        panic!("a year of another century was written");
+    };
+    let mut rtc = rtc.set_datetime(datetime(2024, 5, 1, 12, 0, 0)).ok().unwrap();
+    assert!(!rtc.voltage_low_flag().unwrap());
+
+    let Ok(Initialized::Ready(mut rtc)) = Rx8900::uninitialized(rtc.release()).init() else {
+        panic!("the time was reported as lost");
+    };
+    assert_eq!(rtc.datetime().unwrap(), datetime(2024, 5, 1, 12, 0, 0));
+}
+
+#[test]
+fn typestate_init_sets_the_fallback_time_only_if_it_was_lost() {
+    let board = FakeBoard::new();
+    let fallback = datetime(2024, 1, 1, 0, 0, 0);
+    let initialized = Rx8900::uninitialized(board.rtc.release()).init().ok().unwrap();
+    let mut rtc = initialized.or_set_datetime(fallback).ok().unwrap();
+    assert_eq!(rtc.datetime().unwrap(), fallback);
+
+    rtc.set_datetime(datetime(2024, 5, 1, 12, 0, 0)).unwrap();
+    let initialized = Rx8900::uninitialized(rtc.release()).init().ok().unwrap();
+    let mut rtc = initialized.or_set_datetime(fallback).ok().unwrap();
+    assert_eq!(rtc.datetime().unwrap(), datetime(2024, 5, 1, 12, 0, 0));
+}
+
+#[test]
+fn typestate_init_returns_the_bus_on_errors() {
+    let bus = Faulty::new(Simulator::new(), 1);
+    match Rx8900::uninitialized(bus).init() {
+        Err((bus, Error::Bus(Nack))) => assert!(Rx8900::detect(bus).is_err()),
+        Err((_, error)) => panic!("unexpected error {:?}", error),
+        Ok(_) => panic!("init succeeded without a chip answering"),
+    }
+}
+
+#[test]
+fn init_with_programs_the_chosen_settings() {
+    let mut board = FakeBoard::new();
+    board.simulator_mut().set_register(FLAGS, VLF | VDET);
+    let init = InitConfig::default()
+        .fout(FoutFrequency::FoutFrequency1Hz)
+        .compensation(CompensationIntervalType::CompensationInterval30s)
+        .update_interrupt_enable(true)
+        .backup_mode_sampling_time(0b01);
+    board.rtc.init_with(&init).unwrap();
+
+    assert!(!board.rtc.voltage_low_flag().unwrap() && !board.rtc.voltage_detect_flag().unwrap());
+    let config = board.rtc.config().unwrap();
+    assert_eq!(config.fout_frequency, FoutFrequency::FoutFrequency1Hz);
+    assert_eq!(config.compensation_interval_type, CompensationIntervalType::CompensationInterval30s);
+    assert!(config.update_interrupt_enable && !config.alarm_interrupt_enable && !config.timer_interrupt_enable);
+    assert!(!config.voltage_detector_off && config.switch_off);
+    assert_eq!(config.backup_mode_sampling_time, 0b01);
+
+    // The defaults are those of init, which leaves the compensation interval and sampling time alone.
+    board.rtc.init_with(&InitConfig::default()).unwrap();
+    let config = board.rtc.config().unwrap();
+    assert_eq!(config.fout_frequency, FoutFrequency::FoutFrequency32_768kHz);
+    assert!(!config.update_interrupt_enable);
+    assert_eq!(config.compensation_interval_type, CompensationIntervalType::CompensationInterval30s);
+    assert_eq!(config.backup_mode_sampling_time, 0b01);
+}
+
+#[test]
+fn out_of_range_sampling_times_are_rejected_instead_of_masked() {
+    let mut board = FakeBoard::new();
+    board.rtc.set_bksmp(0b01).unwrap();
+    let before = board.simulator().transactions();
+
+    assert!(matches!(board.rtc.set_bksmp(0b100), Err(Error::InvalidInput)));
+    let init = InitConfig::default().backup_mode_sampling_time(4);
+    assert!(matches!(board.rtc.init_with(&init), Err(Error::InvalidInput)));
+    let config = Config::default().backup_mode_sampling_time(4);
+    assert!(matches!(board.rtc.apply_config(&config), Err(Error::InvalidInput)));
+    assert!(matches!(board.rtc.diff_config(&config), Err(Error::InvalidInput)));
+    assert!(matches!(board.rtc.set_reapply_on_vdet(Some(config)), Err(Error::InvalidInput)));
+    assert_eq!(board.rtc.reapply_on_vdet(), None);
+    assert_eq!(board.simulator().transactions(), before);
+    assert_eq!(board.rtc.backup_mode_sampling_time().unwrap(), 0b01);
+
+    let bus = board.rtc.release();
+    assert!(matches!(Rx8900::uninitialized(bus).init_with(&init), Err((_, Error::InvalidInput))));
+}
+
+#[test]
+fn backup_profiles_program_the_backup_function_register() {
+    let mut board = FakeBoard::new();
+    board.rtc.set_bksmp(0b11).unwrap();
+    let profiles = [
+        (BackupPowerProfile::CoinCell, false, true),
+        (BackupPowerProfile::Supercap, false, false),
+        (BackupPowerProfile::None, true, true),
+    ];
+    for (profile, voltage_detector_off, switch_off) in profiles {
+        board.rtc.configure_backup(profile).unwrap();
+        let config = board.rtc.config().unwrap();
+        assert_eq!((config.voltage_detector_off, config.switch_off), (voltage_detector_off, switch_off));
+        assert_eq!(config.backup_mode_sampling_time, 0b10);
+
+        // The same settings at initialization.
+        board.rtc.set_bksmp(0b11).unwrap();
+        board.rtc.init_with(&InitConfig::default().backup(profile)).unwrap();
+        assert_eq!(board.rtc.config().unwrap(), config);
+    }
+}
+
+#[test]
+fn compensation_interval_is_rounded_to_the_nearest_supported_one() {
+    let mut board = FakeBoard::new();
+    let chosen = board.rtc.set_compensation_interval(Duration::seconds(7)).unwrap();
+    assert_eq!(chosen, CompensationIntervalType::CompensationInterval10s);
+    assert_eq!(board.rtc.compensation_interval_type().unwrap(), chosen);
+
+    // Ties go to the shorter interval.
+    let tie = CompensationIntervalType::from_duration(Duration::seconds(20));
+    assert_eq!(tie, Some(CompensationIntervalType::CompensationInterval10s));
+
+    // Durations too far from every interval to measure are rejected instead of overflowing.
+    assert_eq!(CompensationIntervalType::from_duration(Duration::MIN), None);
+    assert!(matches!(board.rtc.set_compensation_interval(Duration::MIN), Err(Error::InvalidInput)));
+    assert_eq!(board.rtc.compensation_interval_type().unwrap(), chosen);
+}
+
+#[test]
+fn exported_state_is_restored_after_a_battery_swap() {
+    let mut board = provisioned(datetime(2024, 5, 1, 12, 0, 0));
+    board.simulator_mut().set_register(0x07, 0x5A);
+    board.rtc.configure_backup(BackupPowerProfile::Supercap).unwrap();
+    let state = board.rtc.export_state().unwrap();
+    assert_eq!(state.year_base(), 2000);
+
+    replace_battery(&board);
+    board.rtc.import_state(&state).unwrap();
+    assert_eq!(board.simulator_mut().register(0x07), 0x5A);
+    assert_eq!(board.rtc.export_state().unwrap(), state);
+
+    // A state exported for another century is not applied to a driver reading 2000-2099.
+    let mut bytes = *state.as_bytes();
+    bytes[10..12].copy_from_slice(&2100i16.to_le_bytes());
+    let other = rx8900::StateBlob::from_bytes(&bytes).unwrap();
+    assert_eq!(other.year_base(), 2100);
+    assert!(matches!(board.rtc.import_state(&other), Err(Error::InvalidInput)));
+}
+
+/// A register image with every restored register set, and the `StateBlob` exported from it.
+const STATE_REGISTERS: [(u8, u8); 10] = [
+    (0x07, 0x5A),
+    (0x08, 0x30),
+    (0x09, 0x87),
+    (0x0A, 0x15),
+    (0x0B, 0x34),
+    (0x0C, 0x02),
+    (0x0D, 0x4A),
+    (0x0E, 0x00),
+    (0x0F, 0x58),
+    (0x18, 0x0D),
+];
+const STATE_BYTES: [u8; StateBlob::LEN] = [2, 0x5A, 0x30, 0x87, 0x15, 0x34, 0x02, 0x4A, 0x58, 0x0D, 0xD0, 0x07];
+
+#[test]
+fn state_blob_layout_is_stable() {
+    let mut simulator = Simulator::from_registers(&[]);
+    for (address, value) in STATE_REGISTERS {
+        simulator.set_register(address, value);
+    }
+    // TEST and RESET are never exported, the upper bits of the backup register neither.
+    simulator.set_register(0x0D, 0x4A | 0b10000000);
+    simulator.set_register(0x0F, 0x58 | 0b00000001);
+    simulator.set_register(0x18, 0x0D | 0b11110000);
+    let mut rtc = Rx8900::new_unchecked(simulator);
+    assert_eq!(rtc.export_state().unwrap().as_bytes(), &STATE_BYTES);
+
+    let mut rtc = Rx8900::new_unchecked(Simulator::new());
+    rtc.import_state(&StateBlob::from_bytes(&STATE_BYTES).unwrap()).unwrap();
+    let simulator = rtc.release();
+    for (address, value) in STATE_REGISTERS {
+        // Flags are left as the chip holds them.
+        let expected = if address == FLAGS { 0b00000010 } else { value };
+        assert_eq!(simulator.register(address), expected, "register {:#04X}", address);
+    }
+}
+
+#[test]
+fn importing_a_state_keeps_the_ownership_marker() {
+    let mut board = provisioned(datetime(2024, 5, 1, 12, 0, 0));
+    assert!(board.rtc.claim(2).unwrap());
+    let marker = board.simulator_mut().register(0x07) & 0b11000000;
+    assert_ne!(marker, 0);
+
+    // The state was exported from a chip another master had claimed.
+    let state = StateBlob::from_bytes(&STATE_BYTES).unwrap();
+    assert_ne!(STATE_BYTES[1] & 0b11000000, marker);
+    board.rtc.import_state(&state).unwrap();
+    assert_eq!(board.simulator_mut().register(0x07), marker | STATE_BYTES[1] & 0b00111111);
+    assert_eq!(board.rtc.owner().unwrap(), Some(2));
+}
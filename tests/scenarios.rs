@@ -0,0 +1,252 @@
+//! Calendar edge cases scripted against the simulator, so they run on any host without hardware.
+//!
+//! ```console
+//! $ cargo test --target x86_64-unknown-linux-gnu --features testsupport --test scenarios
+//! ```
+
+#![cfg(all(feature = "testsupport", feature = "alarm"))]
+
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc, Weekday,
+};
+use heapless::Vec;
+use rx8900::testsupport::FakeBoard;
+use rx8900::{AlarmConfig, AlarmDay, Error, Flag, NamedOffset};
+
+mod common;
+use common::{board_at, datetime};
+
+/// Moves the simulated clock forward.
+fn advance(board: &FakeBoard, duration: Duration) {
+    board.simulator_mut().advance(duration);
+}
+
+/// Arms an alarm and keeps it armed.
+fn arm(board: &mut FakeBoard, config: AlarmConfig) {
+    board.rtc.arm_alarm(&config).unwrap().keep();
+}
+
+/// Returns whether the alarm fired, clearing the flag.
+fn take_alarm(board: &mut FakeBoard) -> bool {
+    let fired = board.rtc.alarm_flag().unwrap();
+    board.rtc.clear_flag(Flag::Alarm).unwrap();
+    fired
+}
+
+#[test]
+fn year_99_rolls_over_to_00() {
+    let mut board = board_at(datetime(2099, 12, 31, 23, 59, 59));
+    assert_eq!(board.rtc.week().unwrap(), Weekday::Thu);
+
+    advance(&board, Duration::seconds(1));
+    // The chip keeps two digits of the year, so 2100 reads back as 2000; the weekday keeps rotating.
+    assert_eq!(board.rtc.datetime().unwrap(), datetime(2000, 1, 1, 0, 0, 0));
+    assert_eq!(board.rtc.year().unwrap(), 0);
+    assert_eq!(board.rtc.week().unwrap(), Weekday::Fri);
+}
+
+#[test]
+fn datetime_reads_the_rollover_in_one_transaction() {
+    let mut board = board_at(datetime(2024, 12, 31, 23, 59, 59));
+    advance(&board, Duration::seconds(1));
+    let before = board.simulator().transactions();
+    assert_eq!(board.rtc.datetime().unwrap(), datetime(2025, 1, 1, 0, 0, 0));
+    assert_eq!(board.simulator().transactions() - before, 1);
+}
+
+#[test]
+fn leap_day_is_kept_in_leap_years_only() {
+    let mut board = board_at(datetime(2024, 2, 28, 23, 59, 59));
+    advance(&board, Duration::seconds(1));
+    assert_eq!(board.rtc.datetime().unwrap(), datetime(2024, 2, 29, 0, 0, 0));
+    assert_eq!(board.rtc.week().unwrap(), Weekday::Thu);
+    advance(&board, Duration::days(1));
+    assert_eq!(board.rtc.datetime().unwrap(), datetime(2024, 3, 1, 0, 0, 0));
+
+    let mut board = board_at(datetime(2023, 2, 28, 23, 59, 59));
+    advance(&board, Duration::seconds(1));
+    assert_eq!(board.rtc.datetime().unwrap(), datetime(2023, 3, 1, 0, 0, 0));
+
+    // 2000 is divisible by 400, so it is a leap year.
+    let mut board = board_at(datetime(2000, 2, 28, 23, 59, 59));
+    advance(&board, Duration::seconds(1));
+    assert_eq!(board.rtc.datetime().unwrap(), datetime(2000, 2, 29, 0, 0, 0));
+}
+
+#[test]
+fn day_31_alarm_skips_short_months() {
+    let mut board = board_at(datetime(2024, 4, 1, 12, 0, 0));
+    arm(&mut board, AlarmConfig::default().hour(0).minute(0).day(AlarmDay::Day(31)));
+
+    advance(&board, Duration::days(30));
+    assert_eq!(board.rtc.datetime().unwrap(), datetime(2024, 5, 1, 12, 0, 0));
+    assert!(!take_alarm(&mut board), "April has no 31st");
+
+    advance(&board, Duration::days(29));
+    assert!(!take_alarm(&mut board));
+    advance(&board, Duration::hours(12));
+    assert_eq!(board.rtc.datetime().unwrap(), datetime(2024, 5, 31, 0, 0, 0));
+    assert!(take_alarm(&mut board));
+}
+
+#[test]
+fn sleep_until_crosses_the_end_of_the_month() {
+    let mut board = board_at(datetime(2024, 1, 31, 23, 58, 30));
+    let wake = datetime(2024, 2, 1, 0, 1, 0);
+    board.rtc.sleep_until(wake).unwrap();
+    assert_eq!(board.rtc.scheduled_wake().unwrap(), Some(wake));
+
+    advance(&board, Duration::seconds(149));
+    assert!(!take_alarm(&mut board));
+    advance(&board, Duration::seconds(1));
+    assert_eq!(board.rtc.datetime().unwrap(), wake);
+    assert!(take_alarm(&mut board));
+}
+
+#[test]
+fn switching_between_week_and_day_alarms() {
+    // Sunday
+    let mut board = board_at(datetime(2024, 6, 2, 12, 0, 0));
+    let days = Vec::from_slice(&[Weekday::Mon, Weekday::Wed]).unwrap();
+    arm(&mut board, AlarmConfig::default().hour(7).minute(0).day(AlarmDay::Weekdays(days)));
+    assert!(!board.rtc.wada().unwrap());
+
+    advance(&board, Duration::hours(19) - Duration::minutes(1));
+    assert!(!take_alarm(&mut board));
+    advance(&board, Duration::minutes(1));
+    assert_eq!(board.rtc.week().unwrap(), Weekday::Mon);
+    assert!(take_alarm(&mut board));
+
+    // Tuesday passes without an alarm.
+    advance(&board, Duration::days(1));
+    assert!(!take_alarm(&mut board));
+
+    // The day alarm replaces the week alarm: Wednesday the 5th no longer matches.
+    arm(&mut board, AlarmConfig::default().hour(7).minute(0).day(AlarmDay::Day(15)));
+    assert!(board.rtc.wada().unwrap());
+    advance(&board, Duration::days(1));
+    assert_eq!(board.rtc.week().unwrap(), Weekday::Wed);
+    assert!(!take_alarm(&mut board));
+
+    advance(&board, Duration::days(10));
+    assert_eq!(board.rtc.datetime().unwrap(), datetime(2024, 6, 15, 7, 0, 0));
+    assert!(take_alarm(&mut board));
+
+    // And back to a week alarm, on Sunday the 16th.
+    let days = Vec::from_slice(&[Weekday::Sun]).unwrap();
+    arm(&mut board, AlarmConfig::default().hour(7).minute(0).day(AlarmDay::Weekdays(days)));
+    advance(&board, Duration::days(1));
+    assert_eq!(board.rtc.week().unwrap(), Weekday::Sun);
+    assert!(take_alarm(&mut board));
+}
+
+/// The offset of Central European Time at a UTC instant: summer time from 01:00 UTC on the last Sunday of March
+/// to 01:00 UTC on the last Sunday of October.
+fn central_european_offset(utc: NaiveDateTime) -> FixedOffset {
+    let last_sunday = |month: u32| {
+        let last = NaiveDate::from_ymd_opt(utc.year(), month + 1, 1).unwrap().pred_opt().unwrap();
+        last - Duration::days(last.weekday().num_days_from_sunday() as i64)
+    };
+    let start = last_sunday(3).and_hms_opt(1, 0, 0).unwrap();
+    let end = last_sunday(10).and_hms_opt(1, 0, 0).unwrap();
+    let hours = if (start..end).contains(&utc) { 2 } else { 1 };
+    FixedOffset::east_opt(hours * 3600).unwrap()
+}
+
+fn local(board: &mut FakeBoard) -> DateTime<FixedOffset> {
+    let utc: DateTime<Utc> = board.rtc.now_utc().unwrap();
+    utc.with_timezone(&central_european_offset(utc.naive_utc()))
+}
+
+#[test]
+fn dst_rules_apply_on_top_of_the_utc_clock() {
+    // Set from local winter time; the chip keeps UTC.
+    let cet = FixedOffset::east_opt(3600).unwrap();
+    let set = cet.from_local_datetime(&datetime(2024, 3, 31, 1, 59, 59)).unwrap();
+    let mut board = FakeBoard::new();
+    board.rtc.set_datetime(set).unwrap();
+    assert_eq!(board.rtc.datetime().unwrap(), datetime(2024, 3, 31, 0, 59, 59));
+
+    // Spring forward: 01:59:59 CET is followed by 03:00:00 CEST.
+    assert_eq!(local(&mut board).naive_local(), datetime(2024, 3, 31, 1, 59, 59));
+    advance(&board, Duration::seconds(1));
+    let after = local(&mut board);
+    assert_eq!(after.naive_local(), datetime(2024, 3, 31, 3, 0, 0));
+    assert_eq!(after.offset().local_minus_utc(), 7200);
+
+    // Fall back: the local hour 02:00-03:00 repeats, the UTC clock does not.
+    board.rtc.set_datetime(datetime(2024, 10, 27, 0, 30, 0)).unwrap();
+    let before = board.rtc.timestamp().unwrap();
+    assert_eq!(local(&mut board).naive_local(), datetime(2024, 10, 27, 2, 30, 0));
+    advance(&board, Duration::hours(1));
+    assert_eq!(board.rtc.timestamp().unwrap() - before, 3600);
+    let after = local(&mut board);
+    assert_eq!(after.naive_local(), datetime(2024, 10, 27, 2, 30, 0));
+    assert_eq!(after.offset().local_minus_utc(), 3600);
+
+    // An alarm for 07:00 local time the next morning is armed in UTC.
+    let wake = cet.from_local_datetime(&datetime(2024, 10, 28, 7, 0, 0)).unwrap();
+    board.rtc.sleep_until(wake.naive_utc()).unwrap();
+    board.rtc.clear_flag(Flag::Alarm).unwrap();
+    let now = board.rtc.datetime().unwrap();
+    advance(&board, wake.naive_utc() - now);
+    assert!(take_alarm(&mut board));
+    assert_eq!(local(&mut board).time().hour(), 7);
+}
+
+#[test]
+fn out_of_range_alarm_fields_are_rejected_instead_of_masked() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    board.rtc.set_min_alarm(30, false).unwrap();
+    board.rtc.set_hour_alarm(7, false).unwrap();
+    board.rtc.set_day_alarm(15, false).unwrap();
+    let before = board.simulator().transactions();
+
+    assert!(matches!(board.rtc.set_min_alarm(60, false), Err(Error::InvalidInput)));
+    assert!(matches!(board.rtc.set_hour_alarm(99, true), Err(Error::InvalidInput)));
+    assert!(matches!(board.rtc.set_day_alarm(0, false), Err(Error::InvalidInput)));
+    assert!(matches!(board.rtc.set_day_alarm(32, false), Err(Error::InvalidInput)));
+    assert_eq!(board.simulator().transactions(), before);
+    assert_eq!(board.rtc.min_alarm().unwrap(), 30);
+    assert_eq!(board.rtc.hour_alarm().unwrap(), 7);
+    assert_eq!(board.rtc.day_alarm().unwrap(), 15);
+}
+
+#[test]
+fn corrupt_time_and_alarm_registers_are_reported_instead_of_decoded() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 34, 56));
+    assert_eq!(board.rtc.sec().unwrap(), 56);
+    assert_eq!(board.rtc.year().unwrap(), 24);
+
+    {
+        let mut simulator = board.simulator_mut();
+        simulator.set_register(0x00, 0x3F);
+        simulator.set_register(0x02, 0x24);
+        simulator.set_register(0x04, 0x00);
+        simulator.set_register(0x05, 0x13);
+        simulator.set_register(0x06, 0xFF);
+        simulator.set_register(0x08, 0x7A);
+    }
+    assert!(matches!(board.rtc.sec(), Err(Error::InvalidDeviceData)));
+    assert!(matches!(board.rtc.hour(), Err(Error::InvalidDeviceData)));
+    assert!(matches!(board.rtc.day(), Err(Error::InvalidDeviceData)));
+    assert!(matches!(board.rtc.month(), Err(Error::InvalidDeviceData)));
+    assert!(matches!(board.rtc.year(), Err(Error::InvalidDeviceData)));
+    assert!(matches!(board.rtc.min_alarm(), Err(Error::InvalidDeviceData)));
+    assert_eq!(board.rtc.min().unwrap(), 34);
+}
+
+#[test]
+fn erased_user_ram_holds_no_zone() {
+    let mut board = board_at(datetime(2024, 5, 1, 12, 0, 0));
+    for erased in [0x00, 0xFF, 0b11000000] {
+        board.simulator_mut().set_register(0x07, erased);
+        assert_eq!(board.rtc.stored_zone().unwrap(), None);
+    }
+
+    for zone in NamedOffset::ALL {
+        board.rtc.store_zone(zone).unwrap();
+        assert_eq!(board.rtc.stored_zone().unwrap(), Some(zone));
+    }
+    assert_eq!(NamedOffset::from_code(0), None);
+}
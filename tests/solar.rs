@@ -0,0 +1,107 @@
+//! Sunrise and sunset alarms of `SolarSchedule`, with a calculator implementing the NOAA approximation of the
+//! solar position.
+//!
+//! ```console
+//! $ cargo test --target x86_64-unknown-linux-gnu --features testsupport --test solar
+//! ```
+
+#![cfg(all(feature = "testsupport", feature = "alarm"))]
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Timelike};
+use rx8900::{SolarCalculator, SolarRule, SolarSchedule, SunTimes};
+
+mod common;
+use common::{board_at, datetime};
+
+/// A location, computing its sun times accurate to about a minute.
+#[derive(Clone, Copy)]
+struct Location {
+    latitude: f64,
+    longitude: f64,
+}
+
+const TOKYO: Location = Location { latitude: 35.6895, longitude: 139.6917 };
+const LONGYEARBYEN: Location = Location { latitude: 78.2232, longitude: 15.6267 };
+
+impl SolarCalculator for Location {
+    fn sun_times(&mut self, date: NaiveDate) -> Option<SunTimes> {
+        let gamma = 2.0 * std::f64::consts::PI / 365.0 * f64::from(date.ordinal0());
+        let equation_of_time = 229.18
+            * (0.000075 + 0.001868 * gamma.cos()
+                - 0.032077 * gamma.sin()
+                - 0.014615 * (2.0 * gamma).cos()
+                - 0.040849 * (2.0 * gamma).sin());
+        let declination = 0.006918 - 0.399912 * gamma.cos() + 0.070257 * gamma.sin() - 0.006758 * (2.0 * gamma).cos()
+            + 0.000907 * (2.0 * gamma).sin()
+            - 0.002697 * (3.0 * gamma).cos()
+            + 0.00148 * (3.0 * gamma).sin();
+        let latitude = self.latitude.to_radians();
+        // The sun is up while its center is above -0.833°, counting refraction and its radius.
+        let cos_hour_angle = 90.833f64.to_radians().cos() / (latitude.cos() * declination.cos())
+            - latitude.tan() * declination.tan();
+        if !(-1.0..=1.0).contains(&cos_hour_angle) {
+            return None;
+        }
+        let hour_angle = cos_hour_angle.acos().to_degrees();
+        let minutes = |angle: f64| {
+            let minutes = 720.0 - 4.0 * (self.longitude + angle) - equation_of_time;
+            date.and_hms_opt(0, 0, 0).unwrap() + Duration::seconds((minutes * 60.0).round() as i64)
+        };
+        Some(SunTimes { sunrise: minutes(hour_angle), sunset: minutes(-hour_angle) })
+    }
+}
+
+fn assert_within_two_minutes(time: NaiveDateTime, expected: NaiveDateTime) {
+    assert!((time - expected).num_seconds().abs() <= 120, "{} is not {}", time, expected);
+}
+
+#[test]
+fn tokyo_sun_times_on_the_equinox() {
+    // 05:45 and 17:53 JST, so the sunrise falls on the previous day in UTC.
+    let mut tokyo = TOKYO;
+    let sun = tokyo.sun_times(NaiveDate::from_ymd_opt(2024, 3, 20).unwrap()).unwrap();
+    assert_within_two_minutes(sun.sunrise, datetime(2024, 3, 19, 20, 45, 0));
+    assert_within_two_minutes(sun.sunset, datetime(2024, 3, 20, 8, 53, 0));
+}
+
+#[test]
+fn polar_night_and_day_have_no_sun_times() {
+    let mut calculator = LONGYEARBYEN;
+    assert_eq!(calculator.sun_times(NaiveDate::from_ymd_opt(2024, 12, 21).unwrap()), None);
+    assert_eq!(calculator.sun_times(NaiveDate::from_ymd_opt(2024, 6, 21).unwrap()), None);
+    assert!(calculator.sun_times(NaiveDate::from_ymd_opt(2024, 3, 20).unwrap()).is_some());
+}
+
+#[test]
+fn schedule_arms_the_alarm_at_sunrise_then_sunset_in_tokyo() {
+    let mut board = board_at(datetime(2024, 3, 20, 0, 0, 0));
+    let mut schedule = SolarSchedule::<_, 2>::new(TOKYO);
+    assert_eq!(schedule.add(SolarRule::sunrise(Duration::zero())), Some(0));
+    assert_eq!(schedule.add(SolarRule::sunset(Duration::minutes(-15))), Some(1));
+
+    // The sunrise of the 20th is already past at midnight UTC, so the sunset rule is next.
+    let (rule, sunset) = schedule.arm(&mut board.rtc).unwrap().unwrap();
+    assert_eq!(rule, Some(1));
+    assert_within_two_minutes(sunset, datetime(2024, 3, 20, 8, 38, 0));
+    assert_eq!((board.rtc.hour_alarm().unwrap(), board.rtc.min_alarm().unwrap()), (8, sunset.minute() as u8));
+
+    board.simulator_mut().advance(sunset - datetime(2024, 3, 20, 0, 0, 0));
+    assert_eq!(schedule.poll(&mut board.rtc).unwrap(), Some(1));
+    let (rule, sunrise) = schedule.next().unwrap();
+    assert_eq!(rule, Some(0));
+    assert_within_two_minutes(sunrise, datetime(2024, 3, 20, 20, 44, 0));
+    assert_eq!(board.rtc.hour_alarm().unwrap(), 20);
+}
+
+#[test]
+fn schedule_recomputes_at_midnight_during_the_polar_night() {
+    let mut board = board_at(datetime(2024, 12, 21, 10, 0, 0));
+    let mut schedule = SolarSchedule::<_, 1>::new(LONGYEARBYEN);
+    schedule.add(SolarRule::sunrise(Duration::zero())).unwrap();
+    assert_eq!(schedule.arm(&mut board.rtc).unwrap(), Some((None, datetime(2024, 12, 22, 0, 0, 0))));
+    assert_eq!((board.rtc.hour_alarm().unwrap(), board.rtc.min_alarm().unwrap()), (0, 0));
+
+    board.simulator_mut().advance(Duration::hours(14));
+    assert_eq!(schedule.poll(&mut board.rtc).unwrap(), None);
+    assert_eq!(schedule.next(), Some((None, datetime(2024, 12, 23, 0, 0, 0))));
+}
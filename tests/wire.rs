@@ -0,0 +1,122 @@
+//! The binary time message distributed to other MCUs.
+//!
+//! ```console
+//! $ cargo test --target x86_64-unknown-linux-gnu --test wire
+//! ```
+
+use rx8900::{AdjustmentSource, TimeMessage};
+
+mod common;
+use common::datetime;
+
+/// Valid GPS time of 2024-05-01 12:00:00 UTC, known within 100 ms.
+const GPS_NOON: [u8; TimeMessage::LEN] = [0x01, 0x82, 0x40, 0xEB, 0xC4, 0x2D, 0x07, 0x33];
+/// Invalid manual time of 2000-01-01 00:00:00 UTC, known exactly.
+const EPOCH: [u8; TimeMessage::LEN] = [0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xDF];
+/// The last second and the largest uncertainty that can be encoded, from another source.
+const LAST: [u8; TimeMessage::LEN] = [0x01, 0x03, 0xFF, 0xFF, 0xFF, 0xFF, 0x20, 0x50];
+
+fn gps_noon() -> TimeMessage {
+    TimeMessage {
+        datetime: datetime(2024, 5, 1, 12, 0, 0),
+        valid: true,
+        source: AdjustmentSource::Gps,
+        uncertainty_ms: 100,
+    }
+}
+
+#[test]
+fn messages_encode_to_the_documented_bytes() {
+    assert_eq!(gps_noon().encode(), Some(GPS_NOON));
+
+    let epoch = TimeMessage {
+        datetime: datetime(2000, 1, 1, 0, 0, 0),
+        valid: false,
+        source: AdjustmentSource::Manual,
+        uncertainty_ms: 0,
+    };
+    assert_eq!(epoch.encode(), Some(EPOCH));
+
+    let last = TimeMessage {
+        datetime: datetime(2136, 2, 7, 6, 28, 15),
+        valid: false,
+        source: AdjustmentSource::Other,
+        uncertainty_ms: u32::MAX,
+    };
+    assert_eq!(last.encode(), Some(LAST));
+}
+
+#[test]
+fn documented_bytes_decode_to_the_messages() {
+    // The uncertainty is rounded up to 2^n - 1 milliseconds.
+    assert_eq!(TimeMessage::decode(&GPS_NOON), Some(TimeMessage { uncertainty_ms: 127, ..gps_noon() }));
+
+    let epoch = TimeMessage::decode(&EPOCH).unwrap();
+    assert_eq!(epoch.datetime, datetime(2000, 1, 1, 0, 0, 0));
+    assert!(!epoch.valid);
+    assert_eq!(epoch.source, AdjustmentSource::Manual);
+    assert_eq!(epoch.uncertainty_ms, 0);
+
+    let last = TimeMessage::decode(&LAST).unwrap();
+    assert_eq!(last.datetime, datetime(2136, 2, 7, 6, 28, 15));
+    assert_eq!(last.source, AdjustmentSource::Other);
+    assert_eq!(last.uncertainty_ms, u32::MAX);
+}
+
+#[test]
+fn messages_round_trip() {
+    let sources = [AdjustmentSource::Manual, AdjustmentSource::Ntp, AdjustmentSource::Gps, AdjustmentSource::Other];
+    for (index, source) in sources.into_iter().enumerate() {
+        for bits in 0..=32 {
+            let message = TimeMessage {
+                datetime: datetime(2024 + index as i32, 2, 29 - index as u32, 23, 59, bits),
+                valid: index % 2 == 0,
+                source,
+                uncertainty_ms: u32::MAX.checked_shr(32 - bits).unwrap_or(0),
+            };
+            assert_eq!(TimeMessage::decode(&message.encode().unwrap()), Some(message));
+        }
+    }
+}
+
+#[test]
+fn times_outside_the_encoded_range_are_not_encoded() {
+    let early = TimeMessage { datetime: datetime(1999, 12, 31, 23, 59, 59), ..gps_noon() };
+    assert_eq!(early.encode(), None);
+    let late = TimeMessage { datetime: datetime(2136, 2, 7, 6, 28, 16), ..gps_noon() };
+    assert_eq!(late.encode(), None);
+}
+
+#[test]
+fn truncated_or_corrupted_messages_are_rejected() {
+    for len in 0..TimeMessage::LEN {
+        assert_eq!(TimeMessage::decode(&GPS_NOON[..len]), None);
+    }
+    let mut longer = [0; TimeMessage::LEN + 1];
+    longer[..TimeMessage::LEN].copy_from_slice(&GPS_NOON);
+    assert_eq!(TimeMessage::decode(&longer), None);
+
+    // Every single bit flip is caught by the CRC.
+    for byte in 0..TimeMessage::LEN {
+        for bit in 0..8 {
+            let mut corrupted = GPS_NOON;
+            corrupted[byte] ^= 1 << bit;
+            assert_eq!(TimeMessage::decode(&corrupted), None, "byte {} bit {}", byte, bit);
+        }
+    }
+
+    // A matching CRC does not make an unknown version, reserved bits or an oversized uncertainty valid.
+    for (byte, value) in [(0, 0x02), (1, 0x86), (6, 33)] {
+        let mut message = GPS_NOON;
+        message[byte] = value;
+        message[7] = crc8(&message[..7]);
+        assert_eq!(TimeMessage::decode(&message), None, "byte {}", byte);
+    }
+}
+
+/// The CRC-8 of the message format, to forge messages that pass the checksum.
+fn crc8(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0, |crc, &byte| {
+        (0..8).fold(crc ^ byte, |crc, _| if crc & 0x80 != 0 { crc << 1 ^ 0x07 } else { crc << 1 })
+    })
+}